@@ -1,7 +1,7 @@
 use crate::config::Config;
 use crate::format_arg;
 use log::*;
-use spatialos_sdk_code_generator::{generator, schema_bundle};
+use spatialos_sdk_code_generator::{generator, schema_parser::SchemaParser};
 use std::fs::{self, File};
 use std::io::prelude::*;
 use std::path::*;
@@ -17,16 +17,6 @@ pub fn run_codegen(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
         "Current directory should be the project root"
     );
 
-    // Ensure that the path to the Spatial SDK has been specified.
-    let spatial_lib_dir = config.spatial_lib_dir()
-        .map(PathBuf::from)
-        .ok_or("spatial_lib_dir value must be set in the config, or the SPATIAL_LIB_DIR environment variable must be set")?;
-
-    // Determine the paths the the schema compiler and protoc relative the the lib
-    // dir path.
-    let schema_compiler_path = spatial_lib_dir.join("schema-compiler/schema_compiler");
-    let std_lib_path = spatial_lib_dir.join("std-lib");
-
     // Calculate the various output directories relative to `output_dir`.
     let output_dir = PathBuf::from(config.schema_build_dir());
     let bundle_json_path = output_dir.join("bundle.json");
@@ -37,49 +27,65 @@ pub fn run_codegen(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
         .map_err(|_| format!("Failed to create {}", output_dir.display()))?;
     trace!("Created schema output dir: {}", output_dir.display());
 
-    // Prepare initial flags for the schema compiler.
-    let schema_path_arg = format_arg("schema_path", &std_lib_path);
-    let bundle_json_arg = format_arg("bundle_json_out", &bundle_json_path);
-    let descriptor_out_arg = format_arg("descriptor_set_out", &schema_descriptor_path);
+    // The standard schema library from the SDK is preferred, if it has been downloaded, over the
+    // copy built into the code generator.
+    let spatial_lib_dir = config.spatial_lib_dir().map(PathBuf::from);
+    let std_lib_path = spatial_lib_dir.as_ref().map(|dir| dir.join("std-lib"));
 
-    // Run the schema compiler for all schema files in the project.
-    //
-    // This will generated the schema descriptor file that SpatialOS loads directly, as
-    // well as the schema bundle file that's used for code generation. All schema files
-    // in the project are included, as well as the schema files in the standard schema
-    // library
-    let mut command = Command::new(&schema_compiler_path);
-    command
-        .arg(&schema_path_arg)
-        .arg(&bundle_json_arg)
-        .arg(&descriptor_out_arg)
-        .arg("--load_all_schema_on_schema_path");
-
-    // Add all the root schema paths.
+    // Parse all schema files in the project, as well as the schema files in the standard schema
+    // library, into the schema bundle that's used for code generation.
+    let mut parser = SchemaParser::new();
+    parser.add_standard_library();
+    if let Some(std_lib_path) = std_lib_path.as_ref().filter(|path| path.is_dir()) {
+        parser
+            .add_schema_path(std_lib_path)
+            .map_err(|e| format!("Failed to read {}: {}", std_lib_path.display(), e))?;
+    }
     for schema_path in &config.schema_paths {
-        command.arg(&format_arg("schema_path", schema_path));
+        parser
+            .add_schema_path(schema_path)
+            .map_err(|e| format!("Failed to read {}: {}", schema_path, e))?;
     }
+    let bundle = parser.parse()?;
 
-    trace!("{:#?}", command);
-    let status = command
-        .status()
-        .map_err(|_| "Failed to compile schema files")?;
+    // Write bundle.json, which is used by other commands to describe the schema definitions for
+    // all components.
+    let bundle_json = serde_json::to_string_pretty(&bundle)?;
+    fs::write(&bundle_json_path, bundle_json).map_err(|_| "Failed to write bundle.json")?;
 
-    if !status.success() {
-        return Err("Failed to run schema compilation".into());
-    }
+    // The schema descriptor that SpatialOS loads directly can only be generated by the schema
+    // compiler, so it's skipped if the SDK hasn't been downloaded.
+    let schema_compiler_path = spatial_lib_dir
+        .as_ref()
+        .map(|dir| dir.join("schema-compiler/schema_compiler"))
+        .filter(|path| path.exists());
+    match (schema_compiler_path, std_lib_path) {
+        (Some(schema_compiler_path), Some(std_lib_path)) => {
+            let mut command = Command::new(&schema_compiler_path);
+            command
+                .arg(format_arg("schema_path", &std_lib_path))
+                .arg(format_arg("descriptor_set_out", &schema_descriptor_path))
+                .arg("--load_all_schema_on_schema_path");
+
+            // Add all the root schema paths.
+            for schema_path in &config.schema_paths {
+                command.arg(&format_arg("schema_path", schema_path));
+            }
 
-    // Load bundle.json, which describes the schema definitions for all components.
-    let mut input_file = File::open(&bundle_json_path).map_err(|_| "Failed to open bundle.json")?;
-    let mut contents = String::new();
-    input_file
-        .read_to_string(&mut contents)
-        .map_err(|_| "Failed to read contents of bundle.json")?;
+            trace!("{:#?}", command);
+            let status = command
+                .status()
+                .map_err(|_| "Failed to compile schema files")?;
+
+            if !status.success() {
+                return Err("Failed to run schema compilation".into());
+            }
+        }
+        _ => info!("The SpatialOS SDK wasn't found, so the schema descriptor won't be generated"),
+    }
 
     // Run code generation.
-    let bundle = schema_bundle::load_bundle(&contents)
-        .map_err(|_| "Failed to parse contents of bundle.json")?;
-    let generated_file = generator::generate_code(bundle);
+    let generated_file = generator::generate_code(bundle, &config.codegen);
 
     // Write the generated code to the output file.
     File::create(&config.codegen_out)
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use spatialos_sdk_code_generator::generator::CodegenOptions;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
@@ -22,6 +23,9 @@ pub struct Config {
     /// Defaults to `src/generated.rs`.
     pub codegen_out: String,
 
+    /// Options for code generation, read from the `[codegen]` table.
+    pub codegen: CodegenOptions,
+
     /// The directories containing schema files for the project.
     ///
     /// Defaults to `./schema`.
@@ -56,6 +60,7 @@ impl Default for Config {
             spatial_sdk_version: "14.0.0".into(),
             workers: vec![".".into()],
             codegen_out: "src/generated.rs".into(),
+            codegen: CodegenOptions::default(),
             schema_paths: vec![],
             build_dir: "./build".into(),
             schema_build_dir: None,
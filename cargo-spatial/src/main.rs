@@ -1,4 +1,4 @@
-use cargo_spatial::{codegen, config::Config, download, local, opt::*};
+use cargo_spatial::{codegen, config::Config, download, local, opt::*, schema, snapshot};
 use log::*;
 use simplelog::*;
 use structopt::StructOpt;
@@ -32,6 +32,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             Download::Cli => download::download_cli()?,
             Download::Sdk(options) => download::download_sdk(Config::load(), options)?,
         },
+
+        Command::Snapshot { command } => match command {
+            Snapshot::ToJson(options) => snapshot::to_json(options)?,
+            Snapshot::FromJson(options) => snapshot::from_json(options)?,
+        },
+
+        Command::Schema { command } => match command {
+            Schema::Diff(options) => schema::diff(options)?,
+        },
     }
 
     Ok(())
@@ -54,6 +54,22 @@ pub enum Command {
         #[structopt(subcommand)]
         command: Download,
     },
+
+    /// Convert snapshots to and from JSON
+    ///
+    /// Requires cargo-spatial to be installed with the `snapshot` feature.
+    #[structopt(name = "snapshot")]
+    Snapshot {
+        #[structopt(subcommand)]
+        command: Snapshot,
+    },
+
+    /// Commands for checking changes to schema
+    #[structopt(name = "schema")]
+    Schema {
+        #[structopt(subcommand)]
+        command: Schema,
+    },
 }
 
 #[derive(StructOpt)]
@@ -107,3 +123,55 @@ pub struct DownloadSdk {
     #[structopt(long, short)]
     pub with_test_schema: bool,
 }
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum Snapshot {
+    /// Convert a snapshot file to JSON
+    #[structopt(name = "to-json")]
+    ToJson(SnapshotConvert),
+
+    /// Convert a JSON file to a snapshot
+    #[structopt(name = "from-json")]
+    FromJson(SnapshotConvert),
+}
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct SnapshotConvert {
+    /// The file to convert
+    #[structopt(parse(from_os_str))]
+    pub input: PathBuf,
+
+    /// The file to write the converted output to
+    #[structopt(parse(from_os_str))]
+    pub output: PathBuf,
+
+    /// The schema bundle describing the components in the snapshot
+    ///
+    /// If not specified, the bundle.json generated by `cargo spatial codegen` is used.
+    #[structopt(long, short, parse(from_os_str))]
+    pub bundle: Option<PathBuf>,
+}
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum Schema {
+    /// Compare two schema bundles, and report changes that break compatibility
+    ///
+    /// Exits with an error if any breaking changes are found.
+    #[structopt(name = "diff")]
+    Diff(SchemaDiff),
+}
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct SchemaDiff {
+    /// The bundle.json of the schema currently in use
+    #[structopt(parse(from_os_str))]
+    pub old: PathBuf,
+
+    /// The bundle.json of the changed schema
+    #[structopt(parse(from_os_str))]
+    pub new: PathBuf,
+}
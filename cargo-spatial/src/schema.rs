@@ -0,0 +1,48 @@
+//! Compatibility checks between versions of a project's schema.
+
+use crate::opt::SchemaDiff;
+use spatialos_sdk_code_generator::schema_bundle::{self, SchemaBundle};
+use spatialos_sdk_code_generator::schema_diff;
+use std::path::Path;
+
+/// Compares the bundles `options.old` and `options.new`, printing the breaking changes and
+/// additions. Returns an error if there are any breaking changes, so that it can be used to check
+/// schema changes before deploying them.
+pub fn diff(options: &SchemaDiff) -> Result<(), Box<dyn std::error::Error>> {
+    let old = load_bundle(&options.old)?;
+    let new = load_bundle(&options.new)?;
+    let diff = schema_diff::diff(&old, &new);
+
+    if !diff.breaking_changes.is_empty() {
+        println!("Breaking changes:");
+        for change in &diff.breaking_changes {
+            println!("  {}", change);
+        }
+    }
+    if !diff.additions.is_empty() {
+        println!("Additions:");
+        for addition in &diff.additions {
+            println!("  {}", addition);
+        }
+    }
+
+    if diff.is_breaking() {
+        return Err(format!(
+            "Found {} breaking schema change(s)",
+            diff.breaking_changes.len()
+        )
+        .into());
+    }
+    if diff.additions.is_empty() {
+        println!("No schema changes");
+    }
+    Ok(())
+}
+
+fn load_bundle(path: &Path) -> Result<SchemaBundle, Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+    let bundle = schema_bundle::load_bundle(&contents)
+        .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))?;
+    Ok(bundle)
+}
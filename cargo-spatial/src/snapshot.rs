@@ -0,0 +1,73 @@
+//! Conversion between snapshot files and JSON.
+//!
+//! Reading and writing snapshots requires the SpatialOS C API, which is only linked
+//! into cargo-spatial when it's built with the `snapshot` feature.
+
+use crate::config::Config;
+use crate::opt::SnapshotConvert;
+use std::path::PathBuf;
+
+/// Converts the snapshot file `options.input` to JSON.
+#[cfg(feature = "snapshot")]
+pub fn to_json(options: &SnapshotConvert) -> Result<(), Box<dyn std::error::Error>> {
+    use spatialos_sdk::worker::snapshot;
+
+    let registry = load_registry(options)?;
+    let json = snapshot::snapshot_to_json(&registry, &options.input)?;
+    std::fs::write(&options.output, serde_json::to_string_pretty(&json)?)
+        .map_err(|err| format!("Failed to write {}: {}", options.output.display(), err))?;
+    Ok(())
+}
+
+/// Converts the JSON file `options.input` to a snapshot.
+#[cfg(feature = "snapshot")]
+pub fn from_json(options: &SnapshotConvert) -> Result<(), Box<dyn std::error::Error>> {
+    use spatialos_sdk::worker::snapshot;
+
+    let registry = load_registry(options)?;
+    let contents = std::fs::read_to_string(&options.input)
+        .map_err(|err| format!("Failed to read {}: {}", options.input.display(), err))?;
+    let json = serde_json::from_str(&contents)
+        .map_err(|err| format!("Failed to parse {}: {}", options.input.display(), err))?;
+    snapshot::snapshot_from_json(&registry, &json, &options.output)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "snapshot"))]
+pub fn to_json(_: &SnapshotConvert) -> Result<(), Box<dyn std::error::Error>> {
+    Err(feature_disabled())
+}
+
+#[cfg(not(feature = "snapshot"))]
+pub fn from_json(_: &SnapshotConvert) -> Result<(), Box<dyn std::error::Error>> {
+    Err(feature_disabled())
+}
+
+#[cfg(feature = "snapshot")]
+fn load_registry(
+    options: &SnapshotConvert,
+) -> Result<spatialos_sdk::worker::dynamic::SchemaRegistry, Box<dyn std::error::Error>> {
+    let bundle = bundle_path(options)?;
+    Ok(spatialos_sdk::worker::dynamic::SchemaRegistry::load(
+        bundle,
+    )?)
+}
+
+#[cfg(not(feature = "snapshot"))]
+fn feature_disabled() -> Box<dyn std::error::Error> {
+    "cargo-spatial was built without snapshot support. Reinstall it with `--features snapshot` \
+     once the SpatialOS SDK has been downloaded."
+        .into()
+}
+
+// Returns the bundle passed on the command line, or the one generated by `cargo spatial codegen`.
+#[cfg_attr(not(feature = "snapshot"), allow(dead_code))]
+fn bundle_path(options: &SnapshotConvert) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match &options.bundle {
+        Some(bundle) => Ok(bundle.clone()),
+        None => {
+            let config = Config::load()?;
+            Ok(PathBuf::from(config.schema_build_dir()).join("bundle.json"))
+        }
+    }
+}
@@ -55,7 +55,7 @@ rustup component add clippy-preview
 
 cargo install --path cargo-spatial --force
 
-cargo spatial --verbose download sdk --sdk-version 14.0.0
+cargo spatial --verbose download sdk --sdk-version 14.0.0 --with-test-schema
 
 # HACK: It doesn't make sense to do codegen in the install step, but it needs
 # to be done before we attempt to build since it doesn't happen automatically.
@@ -66,6 +66,9 @@ pushd project-example
 popd
 
 pushd test-suite
-  mkdir schema
+  # Generate code for the exhaustive test schema alongside the test suite's own
+  # schema, so that every schema construct it covers is checked to compile.
+  mkdir -p schema/exhaustive
+  cp -R "${SPATIAL_LIB_DIR}/test-schema/." schema/exhaustive/
   cargo spatial --verbose codegen
 popd
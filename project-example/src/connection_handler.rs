@@ -110,7 +110,7 @@ pub fn get_connection(opt: Opt) -> Result<WorkerConnection, String> {
     if connect_with_poll {
         get_connection_poll(&mut future)
     } else {
-        future.wait()
+        future.wait().map_err(|e| e.to_string())
     }
 }
 
@@ -123,7 +123,7 @@ fn get_connection_poll(future: &mut WorkerConnectionFuture) -> Result<WorkerConn
                     return Ok(conn);
                 }
             }
-            Err(s) => return Err(s),
+            Err(e) => return Err(e.to_string()),
         };
 
         ::std::thread::sleep(::std::time::Duration::from_millis(
@@ -8,7 +8,7 @@
 
 use spatialos_sdk::worker::internal::schema::*;
 use spatialos_sdk::worker::component::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::generated as generated;
 
@@ -20,7 +20,7 @@ use super::generated as generated;
 pub mod example {
 use spatialos_sdk::worker::internal::schema::*;
 use spatialos_sdk::worker::component::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::super::generated as generated;
 
@@ -32,13 +32,18 @@ pub enum TestEnum {
     SECOND,
 }
 
-impl From<u32> for TestEnum {
-    fn from(value: u32) -> Self {
+impl std::convert::TryFrom<u32> for TestEnum {
+    type Error = spatialos_sdk::Error;
+
+    fn try_from(value: u32) -> Result<Self, spatialos_sdk::Error> {
         match value {
 
-            0 => TestEnum::FIRST, 
-            1 => TestEnum::SECOND, 
-            _ => panic!(format!("Could not convert {} to enum TestEnum.", value))
+            0 => Ok(TestEnum::FIRST), 
+            1 => Ok(TestEnum::SECOND), 
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.TestEnum",
+                format!("Unknown enum value {}.", value),
+            )),
         }
     }
 }
@@ -54,69 +59,69 @@ impl TestEnum {
 }
 
 /* Types. */
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CommandData {
     pub value: i32,
 }
 impl TypeConversion for CommandData {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             value: input.field::<SchemaInt32>(1).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaInt32>(1).add(input.value);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TestType {
     pub value: i32,
 }
 impl TypeConversion for TestType {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             value: input.field::<SchemaInt32>(1).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaInt32>(1).add(input.value);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TestType_Inner {
     pub number: f32,
 }
 impl TypeConversion for TestType_Inner {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             number: input.field::<SchemaFloat>(2).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaFloat>(2).add(input.number);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Vector3d {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 impl TypeConversion for Vector3d {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             x: input.field::<SchemaDouble>(1).get_or_default(),
             y: input.field::<SchemaDouble>(2).get_or_default(),
             z: input.field::<SchemaDouble>(3).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaDouble>(1).add(input.x);
         output.field::<SchemaDouble>(2).add(input.y);
         output.field::<SchemaDouble>(3).add(input.z);
@@ -125,17 +130,17 @@ impl TypeConversion for Vector3d {
 }
 
 /* Components. */ 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EntityIdTest {
     pub eid: spatialos_sdk::worker::EntityId,
 }
 impl TypeConversion for EntityIdTest {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             eid: input.field::<SchemaEntityId>(1).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaEntityId>(1).add(input.eid);
         Ok(())
     }
@@ -144,14 +149,25 @@ impl ComponentData<EntityIdTest> for EntityIdTest {
     fn merge(&mut self, update: EntityIdTestUpdate) {
         if let Some(value) = update.eid { self.eid = value; }
     }
+    fn diff(&self, new: &Self) -> Option<EntityIdTestUpdate> {
+        let mut update = EntityIdTestUpdate::default();
+        let mut changed = false;
+        if self.eid != new.eid {
+            update.eid = Some(new.eid);
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct EntityIdTestUpdate {
     pub eid: Option<spatialos_sdk::worker::EntityId>,
 }
 impl TypeConversion for EntityIdTestUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             eid: None,
         };
@@ -162,7 +178,7 @@ impl TypeConversion for EntityIdTestUpdate {
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(value) = input.eid {
             output.field::<SchemaEntityId>(1).add(value);
         }
@@ -175,11 +191,11 @@ impl ComponentUpdate<EntityIdTest> for EntityIdTestUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EntityIdTestCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EntityIdTestCommandResponse {
 }
 
@@ -190,39 +206,50 @@ impl Component for EntityIdTest {
 
     const ID: ComponentId = 2001;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::example::EntityIdTest, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::example::EntityIdTest, spatialos_sdk::Error> {
         <generated::example::EntityIdTest as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::EntityIdTestUpdate, String> {
-        <generated::example::EntityIdTestUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::EntityIdTestUpdate, spatialos_sdk::Error> {
+        let output = <generated::example::EntityIdTestUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::EntityIdTestCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::EntityIdTestCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component EntityIdTest.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.EntityIdTest",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::EntityIdTestCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::EntityIdTestCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component EntityIdTest.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.EntityIdTest",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::example::EntityIdTest) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::example::EntityIdTest) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::example::EntityIdTest as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::example::EntityIdTest as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::example::EntityIdTestUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::example::EntityIdTestUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::example::EntityIdTestUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::example::EntityIdTestUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::example::EntityIdTestCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::example::EntityIdTestCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -230,7 +257,7 @@ impl Component for EntityIdTest {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::example::EntityIdTestCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::example::EntityIdTestCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -251,19 +278,20 @@ impl Component for EntityIdTest {
     }
 }
 
+
 inventory::submit!(VTable::new::<EntityIdTest>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumTestComponent {
     pub test: generated::example::TestEnum,
 }
 impl TypeConversion for EnumTestComponent {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            test: generated::example::TestEnum::from(input.field::<SchemaEnum>(1).get_or_default()),
+            test: <generated::example::TestEnum as std::convert::TryFrom<u32>>::try_from(input.field::<SchemaEnum>(1).get_or_default()).map_err(|e| e.in_field(1))?,
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaEnum>(1).add(input.test.as_u32());
         Ok(())
     }
@@ -272,25 +300,36 @@ impl ComponentData<EnumTestComponent> for EnumTestComponent {
     fn merge(&mut self, update: EnumTestComponentUpdate) {
         if let Some(value) = update.test { self.test = value; }
     }
+    fn diff(&self, new: &Self) -> Option<EnumTestComponentUpdate> {
+        let mut update = EnumTestComponentUpdate::default();
+        let mut changed = false;
+        if self.test != new.test {
+            update.test = Some(new.test);
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct EnumTestComponentUpdate {
     pub test: Option<generated::example::TestEnum>,
 }
 impl TypeConversion for EnumTestComponentUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             test: None,
         };
         let _field_test = input.field::<SchemaEnum>(1);
         if _field_test.count() > 0 {
             let field = &_field_test;
-            output.test = Some(generated::example::TestEnum::from(field.get_or_default()));
+            output.test = Some(<generated::example::TestEnum as std::convert::TryFrom<u32>>::try_from(field.get_or_default()).map_err(|e| e.in_field(1))?);
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(value) = input.test {
             output.field::<SchemaEnum>(1).add(value.as_u32());
         }
@@ -303,11 +342,11 @@ impl ComponentUpdate<EnumTestComponent> for EnumTestComponentUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EnumTestComponentCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EnumTestComponentCommandResponse {
 }
 
@@ -318,39 +357,50 @@ impl Component for EnumTestComponent {
 
     const ID: ComponentId = 2002;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::example::EnumTestComponent, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::example::EnumTestComponent, spatialos_sdk::Error> {
         <generated::example::EnumTestComponent as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::EnumTestComponentUpdate, String> {
-        <generated::example::EnumTestComponentUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::EnumTestComponentUpdate, spatialos_sdk::Error> {
+        let output = <generated::example::EnumTestComponentUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::EnumTestComponentCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::EnumTestComponentCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component EnumTestComponent.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.EnumTestComponent",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::EnumTestComponentCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::EnumTestComponentCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component EnumTestComponent.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.EnumTestComponent",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::example::EnumTestComponent) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::example::EnumTestComponent) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::example::EnumTestComponent as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::example::EnumTestComponent as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::example::EnumTestComponentUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::example::EnumTestComponentUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::example::EnumTestComponentUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::example::EnumTestComponentUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::example::EnumTestComponentCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::example::EnumTestComponentCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -358,7 +408,7 @@ impl Component for EnumTestComponent {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::example::EnumTestComponentCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::example::EnumTestComponentCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -379,19 +429,20 @@ impl Component for EnumTestComponent {
     }
 }
 
+
 inventory::submit!(VTable::new::<EnumTestComponent>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Example {
     pub x: f32,
 }
 impl TypeConversion for Example {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             x: input.field::<SchemaFloat>(1).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaFloat>(1).add(input.x);
         Ok(())
     }
@@ -400,16 +451,29 @@ impl ComponentData<Example> for Example {
     fn merge(&mut self, update: ExampleUpdate) {
         if let Some(value) = update.x { self.x = value; }
     }
+    fn diff(&self, new: &Self) -> Option<ExampleUpdate> {
+        let mut update = ExampleUpdate::default();
+        let mut changed = false;
+        if self.x != new.x {
+            update.x = Some(new.x);
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ExampleUpdate {
     pub x: Option<f32>,
+    pub test_event: Vec<generated::example::CommandData>,
 }
 impl TypeConversion for ExampleUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             x: None,
+            test_event: Vec::new(),
         };
         let _field_x = input.field::<SchemaFloat>(1);
         if _field_x.count() > 0 {
@@ -418,7 +482,7 @@ impl TypeConversion for ExampleUpdate {
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(value) = input.x {
             output.field::<SchemaFloat>(1).add(value);
         }
@@ -428,15 +492,16 @@ impl TypeConversion for ExampleUpdate {
 impl ComponentUpdate<Example> for ExampleUpdate {
     fn merge(&mut self, update: ExampleUpdate) {
         if update.x.is_some() { self.x = update.x; }
+        self.test_event.extend(update.test_event);
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ExampleCommandRequest {
     TestCommand(generated::example::CommandData),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ExampleCommandResponse {
     TestCommand(generated::example::CommandData),
 }
@@ -448,62 +513,90 @@ impl Component for Example {
 
     const ID: ComponentId = 1000;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::example::Example, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::example::Example, spatialos_sdk::Error> {
         <generated::example::Example as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::ExampleUpdate, String> {
-        <generated::example::ExampleUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::ExampleUpdate, spatialos_sdk::Error> {
+        let mut output = <generated::example::ExampleUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        let events = update.events();
+        let _event_test_event = events.field::<SchemaObject>(1);
+        for i in 0.._event_test_event.count() {
+            output.test_event.push(<generated::example::CommandData as TypeConversion>::from_type(&_event_test_event.index(i))
+                .map_err(|e| e.in_component(Self::ID))?);
+        }
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::ExampleCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::ExampleCommandRequest, spatialos_sdk::Error> {
         match command_index {
             1 => {
                 let result = <generated::example::CommandData as TypeConversion>::from_type(&request.object());
-                result.and_then(|deserialized| Ok(ExampleCommandRequest::TestCommand(deserialized)))
+                result
+                    .map(ExampleCommandRequest::TestCommand)
+                    .map_err(|e| e.in_component(Self::ID))
             },
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component Example.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.Example",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::ExampleCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::ExampleCommandResponse, spatialos_sdk::Error> {
         match command_index {
             1 => {
                 let result = <generated::example::CommandData as TypeConversion>::from_type(&response.object());
-                result.and_then(|deserialized| Ok(ExampleCommandResponse::TestCommand(deserialized)))
+                result
+                    .map(ExampleCommandResponse::TestCommand)
+                    .map_err(|e| e.in_component(Self::ID))
             },
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component Example.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.Example",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::example::Example) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::example::Example) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::example::Example as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::example::Example as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::example::ExampleUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::example::ExampleUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::example::ExampleUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::example::ExampleUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
+        let mut events = serialized_update.events_mut();
+        for event in &update.test_event {
+            <generated::example::CommandData as TypeConversion>::to_type(event, &mut events.field::<SchemaObject>(1).add())
+                .map_err(|e| e.in_component(Self::ID))?;
+        }
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::example::ExampleCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::example::ExampleCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             ExampleCommandRequest::TestCommand(ref data) => {
-                <generated::example::CommandData as TypeConversion>::to_type(data, &mut serialized_request.object_mut())?;
+                <generated::example::CommandData as TypeConversion>::to_type(data, &mut serialized_request.object_mut())
+                    .map_err(|e| e.in_component(Self::ID))?;
             },
             _ => unreachable!()
         }
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::example::ExampleCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::example::ExampleCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             ExampleCommandResponse::TestCommand(ref data) => {
-                <generated::example::CommandData as TypeConversion>::to_type(data, &mut serialized_response.object_mut())?;
+                <generated::example::CommandData as TypeConversion>::to_type(data, &mut serialized_response.object_mut())
+                    .map_err(|e| e.in_component(Self::ID))?;
             },
             _ => unreachable!()
         }
@@ -525,23 +618,58 @@ impl Component for Example {
     }
 }
 
+
+#[derive(Debug)]
+pub struct ExampleTestCommandCommand;
+impl Command for ExampleTestCommandCommand {
+    type Component = generated::example::Example;
+    type Request = generated::example::CommandData;
+    type Response = generated::example::CommandData;
+
+    const COMMAND_INDEX: CommandIndex = 1;
+
+    fn into_request(request: Self::Request) -> generated::example::ExampleCommandRequest {
+        ExampleCommandRequest::TestCommand(request)
+    }
+
+    fn into_response(response: Self::Response) -> generated::example::ExampleCommandResponse {
+        ExampleCommandResponse::TestCommand(response)
+    }
+
+    #[allow(unreachable_patterns)]
+    fn request(request: &generated::example::ExampleCommandRequest) -> Option<&Self::Request> {
+        match request {
+            ExampleCommandRequest::TestCommand(request) => Some(request),
+            _ => None,
+        }
+    }
+
+    #[allow(unreachable_patterns)]
+    fn response(response: &generated::example::ExampleCommandResponse) -> Option<&Self::Response> {
+        match response {
+            ExampleCommandResponse::TestCommand(response) => Some(response),
+            _ => None,
+        }
+    }
+}
+
 inventory::submit!(VTable::new::<Example>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Rotate {
     pub angle: f64,
     pub center: generated::example::Vector3d,
     pub radius: f64,
 }
 impl TypeConversion for Rotate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             angle: input.field::<SchemaDouble>(1).get_or_default(),
-            center: <generated::example::Vector3d as TypeConversion>::from_type(&input.field::<SchemaObject>(2).get_or_default())?,
+            center: <generated::example::Vector3d as TypeConversion>::from_type(&input.field::<SchemaObject>(2).get_or_default()).map_err(|e| e.in_field(2))?,
             radius: input.field::<SchemaDouble>(3).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaDouble>(1).add(input.angle);
         <generated::example::Vector3d as TypeConversion>::to_type(&&input.center, &mut output.field::<SchemaObject>(2).add())?;
         output.field::<SchemaDouble>(3).add(input.radius);
@@ -554,16 +682,35 @@ impl ComponentData<Rotate> for Rotate {
         if let Some(value) = update.center { self.center = value; }
         if let Some(value) = update.radius { self.radius = value; }
     }
+    fn diff(&self, new: &Self) -> Option<RotateUpdate> {
+        let mut update = RotateUpdate::default();
+        let mut changed = false;
+        if self.angle != new.angle {
+            update.angle = Some(new.angle);
+            changed = true;
+        }
+        if self.center != new.center {
+            update.center = Some(new.center.clone());
+            changed = true;
+        }
+        if self.radius != new.radius {
+            update.radius = Some(new.radius);
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct RotateUpdate {
     pub angle: Option<f64>,
     pub center: Option<generated::example::Vector3d>,
     pub radius: Option<f64>,
 }
 impl TypeConversion for RotateUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             angle: None,
             center: None,
@@ -577,7 +724,7 @@ impl TypeConversion for RotateUpdate {
         let _field_center = input.field::<SchemaObject>(2);
         if _field_center.count() > 0 {
             let field = &_field_center;
-            output.center = Some(<generated::example::Vector3d as TypeConversion>::from_type(&field.get_or_default())?);
+            output.center = Some(<generated::example::Vector3d as TypeConversion>::from_type(&field.get_or_default()).map_err(|e| e.in_field(2))?);
         }
         let _field_radius = input.field::<SchemaDouble>(3);
         if _field_radius.count() > 0 {
@@ -586,7 +733,7 @@ impl TypeConversion for RotateUpdate {
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(value) = input.angle {
             output.field::<SchemaDouble>(1).add(value);
         }
@@ -607,11 +754,11 @@ impl ComponentUpdate<Rotate> for RotateUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RotateCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RotateCommandResponse {
 }
 
@@ -622,39 +769,50 @@ impl Component for Rotate {
 
     const ID: ComponentId = 1001;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::example::Rotate, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::example::Rotate, spatialos_sdk::Error> {
         <generated::example::Rotate as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::RotateUpdate, String> {
-        <generated::example::RotateUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::example::RotateUpdate, spatialos_sdk::Error> {
+        let output = <generated::example::RotateUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::RotateCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::example::RotateCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component Rotate.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.Rotate",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::RotateCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::example::RotateCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component Rotate.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "example.Rotate",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::example::Rotate) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::example::Rotate) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::example::Rotate as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::example::Rotate as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::example::RotateUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::example::RotateUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::example::RotateUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::example::RotateUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::example::RotateCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::example::RotateCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -662,7 +820,7 @@ impl Component for Rotate {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::example::RotateCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::example::RotateCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -683,6 +841,7 @@ impl Component for Rotate {
     }
 }
 
+
 inventory::submit!(VTable::new::<Rotate>());
 
 
@@ -691,67 +850,67 @@ inventory::submit!(VTable::new::<Rotate>());
 pub mod improbable {
 use spatialos_sdk::worker::internal::schema::*;
 use spatialos_sdk::worker::component::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::super::generated as generated;
 
 /* Enums. */
 /* Types. */
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest {
     pub queries: Vec<generated::improbable::ComponentInterest_Query>,
 }
 impl TypeConversion for ComponentInterest {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            queries: { let size = input.field::<SchemaObject>(1).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::ComponentInterest_Query as TypeConversion>::from_type(&input.field::<SchemaObject>(1).index(i))?); }; l },
+            queries: { let size = input.field::<SchemaObject>(1).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::ComponentInterest_Query as TypeConversion>::from_type(&input.field::<SchemaObject>(1).index(i)).map_err(|e| e.in_field(1))?); }; l },
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         for element in (&input.queries).iter() { <generated::improbable::ComponentInterest_Query as TypeConversion>::to_type(&element, &mut output.field::<SchemaObject>(1).add())?; };
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_BoxConstraint {
     pub center: generated::improbable::Coordinates,
     pub edge_length: generated::improbable::EdgeLength,
 }
 impl TypeConversion for ComponentInterest_BoxConstraint {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            center: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
-            edge_length: <generated::improbable::EdgeLength as TypeConversion>::from_type(&input.field::<SchemaObject>(2).get_or_default())?,
+            center: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
+            edge_length: <generated::improbable::EdgeLength as TypeConversion>::from_type(&input.field::<SchemaObject>(2).get_or_default()).map_err(|e| e.in_field(2))?,
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::Coordinates as TypeConversion>::to_type(&&input.center, &mut output.field::<SchemaObject>(1).add())?;
         <generated::improbable::EdgeLength as TypeConversion>::to_type(&&input.edge_length, &mut output.field::<SchemaObject>(2).add())?;
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_CylinderConstraint {
     pub center: generated::improbable::Coordinates,
     pub radius: f64,
 }
 impl TypeConversion for ComponentInterest_CylinderConstraint {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            center: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
+            center: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
             radius: input.field::<SchemaDouble>(2).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::Coordinates as TypeConversion>::to_type(&&input.center, &mut output.field::<SchemaObject>(1).add())?;
         output.field::<SchemaDouble>(2).add(input.radius);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_Query {
     pub constraint: generated::improbable::ComponentInterest_QueryConstraint,
     pub full_snapshot_result: Option<bool>,
@@ -759,15 +918,15 @@ pub struct ComponentInterest_Query {
     pub frequency: Option<f32>,
 }
 impl TypeConversion for ComponentInterest_Query {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            constraint: <generated::improbable::ComponentInterest_QueryConstraint as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
+            constraint: <generated::improbable::ComponentInterest_QueryConstraint as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
             full_snapshot_result: if let Some(data) = input.field::<SchemaBool>(2).get() { Some(data) } else { None },
             result_component_id: { let size = input.field::<SchemaUint32>(3).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(input.field::<SchemaUint32>(3).index(i)); }; l },
             frequency: if let Some(data) = input.field::<SchemaFloat>(4).get() { Some(data) } else { None },
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::ComponentInterest_QueryConstraint as TypeConversion>::to_type(&&input.constraint, &mut output.field::<SchemaObject>(1).add())?;
         if let Some(data) = input.full_snapshot_result { output.field::<SchemaBool>(2).add(data); };
         output.field::<SchemaUint32>(3).add_list(&&input.result_component_id[..]);
@@ -776,7 +935,7 @@ impl TypeConversion for ComponentInterest_Query {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_QueryConstraint {
     pub sphere_constraint: Option<generated::improbable::ComponentInterest_SphereConstraint>,
     pub cylinder_constraint: Option<generated::improbable::ComponentInterest_CylinderConstraint>,
@@ -790,21 +949,21 @@ pub struct ComponentInterest_QueryConstraint {
     pub or_constraint: Vec<generated::improbable::ComponentInterest_QueryConstraint>,
 }
 impl TypeConversion for ComponentInterest_QueryConstraint {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            sphere_constraint: if let Some(data) = input.field::<SchemaObject>(1).get() { Some(<generated::improbable::ComponentInterest_SphereConstraint as TypeConversion>::from_type(&data)?) } else { None },
-            cylinder_constraint: if let Some(data) = input.field::<SchemaObject>(2).get() { Some(<generated::improbable::ComponentInterest_CylinderConstraint as TypeConversion>::from_type(&data)?) } else { None },
-            box_constraint: if let Some(data) = input.field::<SchemaObject>(3).get() { Some(<generated::improbable::ComponentInterest_BoxConstraint as TypeConversion>::from_type(&data)?) } else { None },
-            relative_sphere_constraint: if let Some(data) = input.field::<SchemaObject>(4).get() { Some(<generated::improbable::ComponentInterest_RelativeSphereConstraint as TypeConversion>::from_type(&data)?) } else { None },
-            relative_cylinder_constraint: if let Some(data) = input.field::<SchemaObject>(5).get() { Some(<generated::improbable::ComponentInterest_RelativeCylinderConstraint as TypeConversion>::from_type(&data)?) } else { None },
-            relative_box_constraint: if let Some(data) = input.field::<SchemaObject>(6).get() { Some(<generated::improbable::ComponentInterest_RelativeBoxConstraint as TypeConversion>::from_type(&data)?) } else { None },
+            sphere_constraint: if let Some(data) = input.field::<SchemaObject>(1).get() { Some(<generated::improbable::ComponentInterest_SphereConstraint as TypeConversion>::from_type(&data).map_err(|e| e.in_field(1))?) } else { None },
+            cylinder_constraint: if let Some(data) = input.field::<SchemaObject>(2).get() { Some(<generated::improbable::ComponentInterest_CylinderConstraint as TypeConversion>::from_type(&data).map_err(|e| e.in_field(2))?) } else { None },
+            box_constraint: if let Some(data) = input.field::<SchemaObject>(3).get() { Some(<generated::improbable::ComponentInterest_BoxConstraint as TypeConversion>::from_type(&data).map_err(|e| e.in_field(3))?) } else { None },
+            relative_sphere_constraint: if let Some(data) = input.field::<SchemaObject>(4).get() { Some(<generated::improbable::ComponentInterest_RelativeSphereConstraint as TypeConversion>::from_type(&data).map_err(|e| e.in_field(4))?) } else { None },
+            relative_cylinder_constraint: if let Some(data) = input.field::<SchemaObject>(5).get() { Some(<generated::improbable::ComponentInterest_RelativeCylinderConstraint as TypeConversion>::from_type(&data).map_err(|e| e.in_field(5))?) } else { None },
+            relative_box_constraint: if let Some(data) = input.field::<SchemaObject>(6).get() { Some(<generated::improbable::ComponentInterest_RelativeBoxConstraint as TypeConversion>::from_type(&data).map_err(|e| e.in_field(6))?) } else { None },
             entity_id_constraint: if let Some(data) = input.field::<SchemaInt64>(7).get() { Some(data) } else { None },
             component_constraint: if let Some(data) = input.field::<SchemaUint32>(8).get() { Some(data) } else { None },
-            and_constraint: { let size = input.field::<SchemaObject>(9).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::ComponentInterest_QueryConstraint as TypeConversion>::from_type(&input.field::<SchemaObject>(9).index(i))?); }; l },
-            or_constraint: { let size = input.field::<SchemaObject>(10).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::ComponentInterest_QueryConstraint as TypeConversion>::from_type(&input.field::<SchemaObject>(10).index(i))?); }; l },
+            and_constraint: { let size = input.field::<SchemaObject>(9).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::ComponentInterest_QueryConstraint as TypeConversion>::from_type(&input.field::<SchemaObject>(9).index(i)).map_err(|e| e.in_field(9))?); }; l },
+            or_constraint: { let size = input.field::<SchemaObject>(10).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::ComponentInterest_QueryConstraint as TypeConversion>::from_type(&input.field::<SchemaObject>(10).index(i)).map_err(|e| e.in_field(10))?); }; l },
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(ref data) = &input.sphere_constraint { <generated::improbable::ComponentInterest_SphereConstraint as TypeConversion>::to_type(&data, &mut output.field::<SchemaObject>(1).add())?; };
         if let Some(ref data) = &input.cylinder_constraint { <generated::improbable::ComponentInterest_CylinderConstraint as TypeConversion>::to_type(&data, &mut output.field::<SchemaObject>(2).add())?; };
         if let Some(ref data) = &input.box_constraint { <generated::improbable::ComponentInterest_BoxConstraint as TypeConversion>::to_type(&data, &mut output.field::<SchemaObject>(3).add())?; };
@@ -819,88 +978,88 @@ impl TypeConversion for ComponentInterest_QueryConstraint {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_RelativeBoxConstraint {
     pub edge_length: generated::improbable::EdgeLength,
 }
 impl TypeConversion for ComponentInterest_RelativeBoxConstraint {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            edge_length: <generated::improbable::EdgeLength as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
+            edge_length: <generated::improbable::EdgeLength as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::EdgeLength as TypeConversion>::to_type(&&input.edge_length, &mut output.field::<SchemaObject>(1).add())?;
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_RelativeCylinderConstraint {
     pub radius: f64,
 }
 impl TypeConversion for ComponentInterest_RelativeCylinderConstraint {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             radius: input.field::<SchemaDouble>(1).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaDouble>(1).add(input.radius);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_RelativeSphereConstraint {
     pub radius: f64,
 }
 impl TypeConversion for ComponentInterest_RelativeSphereConstraint {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             radius: input.field::<SchemaDouble>(1).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaDouble>(1).add(input.radius);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentInterest_SphereConstraint {
     pub center: generated::improbable::Coordinates,
     pub radius: f64,
 }
 impl TypeConversion for ComponentInterest_SphereConstraint {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            center: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
+            center: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
             radius: input.field::<SchemaDouble>(2).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::Coordinates as TypeConversion>::to_type(&&input.center, &mut output.field::<SchemaObject>(1).add())?;
         output.field::<SchemaDouble>(2).add(input.radius);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Coordinates {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 impl TypeConversion for Coordinates {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             x: input.field::<SchemaDouble>(1).get_or_default(),
             y: input.field::<SchemaDouble>(2).get_or_default(),
             z: input.field::<SchemaDouble>(3).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaDouble>(1).add(input.x);
         output.field::<SchemaDouble>(2).add(input.y);
         output.field::<SchemaDouble>(3).add(input.z);
@@ -908,21 +1067,21 @@ impl TypeConversion for Coordinates {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EdgeLength {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 impl TypeConversion for EdgeLength {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             x: input.field::<SchemaDouble>(1).get_or_default(),
             y: input.field::<SchemaDouble>(2).get_or_default(),
             z: input.field::<SchemaDouble>(3).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaDouble>(1).add(input.x);
         output.field::<SchemaDouble>(2).add(input.y);
         output.field::<SchemaDouble>(3).add(input.z);
@@ -930,52 +1089,52 @@ impl TypeConversion for EdgeLength {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorkerAttributeSet {
     pub attribute: Vec<String>,
 }
 impl TypeConversion for WorkerAttributeSet {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             attribute: { let size = input.field::<SchemaString>(1).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(input.field::<SchemaString>(1).index(i)); }; l },
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaString>(1).add_list(&&input.attribute[..]);
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorkerRequirementSet {
     pub attribute_set: Vec<generated::improbable::WorkerAttributeSet>,
 }
 impl TypeConversion for WorkerRequirementSet {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            attribute_set: { let size = input.field::<SchemaObject>(1).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::WorkerAttributeSet as TypeConversion>::from_type(&input.field::<SchemaObject>(1).index(i))?); }; l },
+            attribute_set: { let size = input.field::<SchemaObject>(1).count(); let mut l = Vec::with_capacity(size); for i in 0..size { l.push(<generated::improbable::WorkerAttributeSet as TypeConversion>::from_type(&input.field::<SchemaObject>(1).index(i)).map_err(|e| e.in_field(1))?); }; l },
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         for element in (&input.attribute_set).iter() { <generated::improbable::WorkerAttributeSet as TypeConversion>::to_type(&element, &mut output.field::<SchemaObject>(1).add())?; };
         Ok(())
     }
 }
 
 /* Components. */ 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EntityAcl {
     pub read_acl: generated::improbable::WorkerRequirementSet,
     pub component_write_acl: BTreeMap<u32, generated::improbable::WorkerRequirementSet>,
 }
 impl TypeConversion for EntityAcl {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            read_acl: <generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
-            component_write_acl: { let size = input.field::<SchemaObject>(2).count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = input.field::<SchemaObject>(2).index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default())?); }; m },
+            read_acl: <generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
+            component_write_acl: { let size = input.field::<SchemaObject>(2).count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = input.field::<SchemaObject>(2).index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default()).map_err(|e| e.in_field(2))?); }; m },
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::WorkerRequirementSet as TypeConversion>::to_type(&&input.read_acl, &mut output.field::<SchemaObject>(1).add())?;
         for (k, v) in &input.component_write_acl { let object = output.field::<SchemaObject>(2).add(); object.field::<SchemaUint32>(1).add(*k); <generated::improbable::WorkerRequirementSet as TypeConversion>::to_type(&v, &mut object.field::<SchemaObject>(2).add())?; };
         Ok(())
@@ -986,15 +1145,30 @@ impl ComponentData<EntityAcl> for EntityAcl {
         if let Some(value) = update.read_acl { self.read_acl = value; }
         if let Some(value) = update.component_write_acl { self.component_write_acl = value; }
     }
+    fn diff(&self, new: &Self) -> Option<EntityAclUpdate> {
+        let mut update = EntityAclUpdate::default();
+        let mut changed = false;
+        if self.read_acl != new.read_acl {
+            update.read_acl = Some(new.read_acl.clone());
+            changed = true;
+        }
+        if self.component_write_acl != new.component_write_acl {
+            update.component_write_acl = Some(new.component_write_acl.clone());
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct EntityAclUpdate {
     pub read_acl: Option<generated::improbable::WorkerRequirementSet>,
     pub component_write_acl: Option<BTreeMap<u32, generated::improbable::WorkerRequirementSet>>,
 }
 impl TypeConversion for EntityAclUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             read_acl: None,
             component_write_acl: None,
@@ -1002,16 +1176,16 @@ impl TypeConversion for EntityAclUpdate {
         let _field_read_acl = input.field::<SchemaObject>(1);
         if _field_read_acl.count() > 0 {
             let field = &_field_read_acl;
-            output.read_acl = Some(<generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&field.get_or_default())?);
+            output.read_acl = Some(<generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&field.get_or_default()).map_err(|e| e.in_field(1))?);
         }
         let _field_component_write_acl = input.field::<SchemaObject>(2);
         if _field_component_write_acl.count() > 0 {
             let field = &_field_component_write_acl;
-            output.component_write_acl = Some({ let size = field.count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = field.index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default())?); }; m });
+            output.component_write_acl = Some({ let size = field.count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = field.index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::WorkerRequirementSet as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default()).map_err(|e| e.in_field(2))?); }; m });
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(ref value) = input.read_acl {
             <generated::improbable::WorkerRequirementSet as TypeConversion>::to_type(&value, &mut output.field::<SchemaObject>(1).add())?;
         }
@@ -1028,11 +1202,11 @@ impl ComponentUpdate<EntityAcl> for EntityAclUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EntityAclCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EntityAclCommandResponse {
 }
 
@@ -1043,39 +1217,58 @@ impl Component for EntityAcl {
 
     const ID: ComponentId = 50;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::EntityAcl, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::EntityAcl, spatialos_sdk::Error> {
         <generated::improbable::EntityAcl as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::EntityAclUpdate, String> {
-        <generated::improbable::EntityAclUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::EntityAclUpdate, spatialos_sdk::Error> {
+        let mut output = <generated::improbable::EntityAclUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        if update.is_field_cleared(2) {
+            output.component_write_acl = Some(Default::default());
+        }
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::EntityAclCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::EntityAclCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component EntityAcl.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.EntityAcl",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::EntityAclCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::EntityAclCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component EntityAcl.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.EntityAcl",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::EntityAcl) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::EntityAcl) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::EntityAcl as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::EntityAcl as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::EntityAclUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::EntityAclUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::EntityAclUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::EntityAclUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
+        if let Some(ref value) = update.component_write_acl {
+            if value.is_empty() {
+                serialized_update.add_cleared_field(2);
+            }
+        }
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::EntityAclCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::EntityAclCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -1083,7 +1276,7 @@ impl Component for EntityAcl {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::EntityAclCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::EntityAclCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -1104,19 +1297,20 @@ impl Component for EntityAcl {
     }
 }
 
+
 inventory::submit!(VTable::new::<EntityAcl>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Interest {
     pub component_interest: BTreeMap<u32, generated::improbable::ComponentInterest>,
 }
 impl TypeConversion for Interest {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            component_interest: { let size = input.field::<SchemaObject>(1).count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = input.field::<SchemaObject>(1).index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::ComponentInterest as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default())?); }; m },
+            component_interest: { let size = input.field::<SchemaObject>(1).count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = input.field::<SchemaObject>(1).index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::ComponentInterest as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default()).map_err(|e| e.in_field(1))?); }; m },
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         for (k, v) in &input.component_interest { let object = output.field::<SchemaObject>(1).add(); object.field::<SchemaUint32>(1).add(*k); <generated::improbable::ComponentInterest as TypeConversion>::to_type(&v, &mut object.field::<SchemaObject>(2).add())?; };
         Ok(())
     }
@@ -1125,25 +1319,36 @@ impl ComponentData<Interest> for Interest {
     fn merge(&mut self, update: InterestUpdate) {
         if let Some(value) = update.component_interest { self.component_interest = value; }
     }
+    fn diff(&self, new: &Self) -> Option<InterestUpdate> {
+        let mut update = InterestUpdate::default();
+        let mut changed = false;
+        if self.component_interest != new.component_interest {
+            update.component_interest = Some(new.component_interest.clone());
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct InterestUpdate {
     pub component_interest: Option<BTreeMap<u32, generated::improbable::ComponentInterest>>,
 }
 impl TypeConversion for InterestUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             component_interest: None,
         };
         let _field_component_interest = input.field::<SchemaObject>(1);
         if _field_component_interest.count() > 0 {
             let field = &_field_component_interest;
-            output.component_interest = Some({ let size = field.count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = field.index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::ComponentInterest as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default())?); }; m });
+            output.component_interest = Some({ let size = field.count(); let mut m = BTreeMap::new(); for i in 0..size { let kv = field.index(i); m.insert(kv.field::<SchemaUint32>(1).get_or_default(), <generated::improbable::ComponentInterest as TypeConversion>::from_type(&kv.field::<SchemaObject>(2).get_or_default()).map_err(|e| e.in_field(1))?); }; m });
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(ref value) = input.component_interest {
             for (k, v) in value { let object = output.field::<SchemaObject>(1).add(); object.field::<SchemaUint32>(1).add(*k); <generated::improbable::ComponentInterest as TypeConversion>::to_type(&v, &mut object.field::<SchemaObject>(2).add())?; };
         }
@@ -1156,11 +1361,11 @@ impl ComponentUpdate<Interest> for InterestUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum InterestCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum InterestCommandResponse {
 }
 
@@ -1171,39 +1376,58 @@ impl Component for Interest {
 
     const ID: ComponentId = 58;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Interest, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Interest, spatialos_sdk::Error> {
         <generated::improbable::Interest as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::InterestUpdate, String> {
-        <generated::improbable::InterestUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::InterestUpdate, spatialos_sdk::Error> {
+        let mut output = <generated::improbable::InterestUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        if update.is_field_cleared(1) {
+            output.component_interest = Some(Default::default());
+        }
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::InterestCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::InterestCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component Interest.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Interest",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::InterestCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::InterestCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component Interest.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Interest",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::Interest) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::Interest) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::Interest as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::Interest as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::InterestUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::InterestUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::InterestUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::InterestUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
+        if let Some(ref value) = update.component_interest {
+            if value.is_empty() {
+                serialized_update.add_cleared_field(1);
+            }
+        }
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::InterestCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::InterestCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -1211,7 +1435,7 @@ impl Component for Interest {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::InterestCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::InterestCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -1232,19 +1456,20 @@ impl Component for Interest {
     }
 }
 
+
 inventory::submit!(VTable::new::<Interest>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Metadata {
     pub entity_type: String,
 }
 impl TypeConversion for Metadata {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             entity_type: input.field::<SchemaString>(1).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaString>(1).add(&&input.entity_type);
         Ok(())
     }
@@ -1253,14 +1478,25 @@ impl ComponentData<Metadata> for Metadata {
     fn merge(&mut self, update: MetadataUpdate) {
         if let Some(value) = update.entity_type { self.entity_type = value; }
     }
+    fn diff(&self, new: &Self) -> Option<MetadataUpdate> {
+        let mut update = MetadataUpdate::default();
+        let mut changed = false;
+        if self.entity_type != new.entity_type {
+            update.entity_type = Some(new.entity_type.clone());
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct MetadataUpdate {
     pub entity_type: Option<String>,
 }
 impl TypeConversion for MetadataUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             entity_type: None,
         };
@@ -1271,7 +1507,7 @@ impl TypeConversion for MetadataUpdate {
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(ref value) = input.entity_type {
             output.field::<SchemaString>(1).add(&value);
         }
@@ -1284,11 +1520,11 @@ impl ComponentUpdate<Metadata> for MetadataUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MetadataCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MetadataCommandResponse {
 }
 
@@ -1299,39 +1535,50 @@ impl Component for Metadata {
 
     const ID: ComponentId = 53;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Metadata, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Metadata, spatialos_sdk::Error> {
         <generated::improbable::Metadata as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::MetadataUpdate, String> {
-        <generated::improbable::MetadataUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::MetadataUpdate, spatialos_sdk::Error> {
+        let output = <generated::improbable::MetadataUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::MetadataCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::MetadataCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component Metadata.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Metadata",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::MetadataCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::MetadataCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component Metadata.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Metadata",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::Metadata) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::Metadata) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::Metadata as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::Metadata as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::MetadataUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::MetadataUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::MetadataUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::MetadataUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::MetadataCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::MetadataCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -1339,7 +1586,7 @@ impl Component for Metadata {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::MetadataCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::MetadataCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -1360,35 +1607,41 @@ impl Component for Metadata {
     }
 }
 
+
 inventory::submit!(VTable::new::<Metadata>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Persistence {
 }
 impl TypeConversion for Persistence {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         Ok(())
     }
 }
 impl ComponentData<Persistence> for Persistence {
     fn merge(&mut self, update: PersistenceUpdate) {
     }
+    fn diff(&self, _new: &Self) -> Option<PersistenceUpdate> {
+        None
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct PersistenceUpdate {
 }
 impl TypeConversion for PersistenceUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
         };
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         Ok(())
     }
 }
@@ -1397,11 +1650,11 @@ impl ComponentUpdate<Persistence> for PersistenceUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PersistenceCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PersistenceCommandResponse {
 }
 
@@ -1412,39 +1665,50 @@ impl Component for Persistence {
 
     const ID: ComponentId = 55;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Persistence, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Persistence, spatialos_sdk::Error> {
         <generated::improbable::Persistence as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::PersistenceUpdate, String> {
-        <generated::improbable::PersistenceUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::PersistenceUpdate, spatialos_sdk::Error> {
+        let output = <generated::improbable::PersistenceUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::PersistenceCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::PersistenceCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component Persistence.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Persistence",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::PersistenceCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::PersistenceCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component Persistence.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Persistence",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::Persistence) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::Persistence) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::Persistence as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::Persistence as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::PersistenceUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::PersistenceUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::PersistenceUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::PersistenceUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::PersistenceCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::PersistenceCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -1452,7 +1716,7 @@ impl Component for Persistence {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::PersistenceCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::PersistenceCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -1473,19 +1737,20 @@ impl Component for Persistence {
     }
 }
 
+
 inventory::submit!(VTable::new::<Persistence>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Position {
     pub coords: generated::improbable::Coordinates,
 }
 impl TypeConversion for Position {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            coords: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
+            coords: <generated::improbable::Coordinates as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::Coordinates as TypeConversion>::to_type(&&input.coords, &mut output.field::<SchemaObject>(1).add())?;
         Ok(())
     }
@@ -1494,25 +1759,36 @@ impl ComponentData<Position> for Position {
     fn merge(&mut self, update: PositionUpdate) {
         if let Some(value) = update.coords { self.coords = value; }
     }
+    fn diff(&self, new: &Self) -> Option<PositionUpdate> {
+        let mut update = PositionUpdate::default();
+        let mut changed = false;
+        if self.coords != new.coords {
+            update.coords = Some(new.coords.clone());
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct PositionUpdate {
     pub coords: Option<generated::improbable::Coordinates>,
 }
 impl TypeConversion for PositionUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             coords: None,
         };
         let _field_coords = input.field::<SchemaObject>(1);
         if _field_coords.count() > 0 {
             let field = &_field_coords;
-            output.coords = Some(<generated::improbable::Coordinates as TypeConversion>::from_type(&field.get_or_default())?);
+            output.coords = Some(<generated::improbable::Coordinates as TypeConversion>::from_type(&field.get_or_default()).map_err(|e| e.in_field(1))?);
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(ref value) = input.coords {
             <generated::improbable::Coordinates as TypeConversion>::to_type(&value, &mut output.field::<SchemaObject>(1).add())?;
         }
@@ -1525,11 +1801,11 @@ impl ComponentUpdate<Position> for PositionUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PositionCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PositionCommandResponse {
 }
 
@@ -1540,39 +1816,50 @@ impl Component for Position {
 
     const ID: ComponentId = 54;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Position, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::Position, spatialos_sdk::Error> {
         <generated::improbable::Position as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::PositionUpdate, String> {
-        <generated::improbable::PositionUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::PositionUpdate, spatialos_sdk::Error> {
+        let output = <generated::improbable::PositionUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::PositionCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::PositionCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component Position.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Position",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::PositionCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::PositionCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component Position.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.Position",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::Position) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::Position) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::Position as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::Position as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::PositionUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::PositionUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::PositionUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::PositionUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::PositionCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::PositionCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -1580,7 +1867,7 @@ impl Component for Position {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::PositionCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::PositionCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -1601,6 +1888,7 @@ impl Component for Position {
     }
 }
 
+
 inventory::submit!(VTable::new::<Position>());
 
 
@@ -1608,7 +1896,7 @@ inventory::submit!(VTable::new::<Position>());
 pub mod restricted {
 use spatialos_sdk::worker::internal::schema::*;
 use spatialos_sdk::worker::component::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::super::super::generated as generated;
 
@@ -1622,15 +1910,20 @@ pub enum Connection_ConnectionStatus {
     DISCONNECTED,
 }
 
-impl From<u32> for Connection_ConnectionStatus {
-    fn from(value: u32) -> Self {
+impl std::convert::TryFrom<u32> for Connection_ConnectionStatus {
+    type Error = spatialos_sdk::Error;
+
+    fn try_from(value: u32) -> Result<Self, spatialos_sdk::Error> {
         match value {
 
-            0 => Connection_ConnectionStatus::UNKNOWN, 
-            1 => Connection_ConnectionStatus::AWAITING_WORKER_CONNECTION, 
-            2 => Connection_ConnectionStatus::CONNECTED, 
-            3 => Connection_ConnectionStatus::DISCONNECTED, 
-            _ => panic!(format!("Could not convert {} to enum Connection_ConnectionStatus.", value))
+            0 => Ok(Connection_ConnectionStatus::UNKNOWN), 
+            1 => Ok(Connection_ConnectionStatus::AWAITING_WORKER_CONNECTION), 
+            2 => Ok(Connection_ConnectionStatus::CONNECTED), 
+            3 => Ok(Connection_ConnectionStatus::DISCONNECTED), 
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.restricted.Connection.ConnectionStatus",
+                format!("Unknown enum value {}.", value),
+            )),
         }
     }
 }
@@ -1648,21 +1941,21 @@ impl Connection_ConnectionStatus {
 }
 
 /* Types. */
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Connection {
     pub status: generated::improbable::restricted::Connection_ConnectionStatus,
     pub data_latency_ms: u32,
     pub connected_since_utc: u64,
 }
 impl TypeConversion for Connection {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            status: generated::improbable::restricted::Connection_ConnectionStatus::from(input.field::<SchemaEnum>(1).get_or_default()),
+            status: <generated::improbable::restricted::Connection_ConnectionStatus as std::convert::TryFrom<u32>>::try_from(input.field::<SchemaEnum>(1).get_or_default()).map_err(|e| e.in_field(1))?,
             data_latency_ms: input.field::<SchemaUint32>(2).get_or_default(),
             connected_since_utc: input.field::<SchemaUint64>(3).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaEnum>(1).add(input.status.as_u32());
         output.field::<SchemaUint32>(2).add(input.data_latency_ms);
         output.field::<SchemaUint64>(3).add(input.connected_since_utc);
@@ -1670,47 +1963,47 @@ impl TypeConversion for Connection {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DisconnectRequest {
 }
 impl TypeConversion for DisconnectRequest {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DisconnectResponse {
 }
 impl TypeConversion for DisconnectResponse {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PlayerIdentity {
     pub player_identifier: String,
     pub provider: String,
     pub metadata: Vec<u8>,
 }
 impl TypeConversion for PlayerIdentity {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             player_identifier: input.field::<SchemaString>(1).get_or_default(),
             provider: input.field::<SchemaString>(2).get_or_default(),
             metadata: input.field::<SchemaBytes>(3).get_or_default(),
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaString>(1).add(&&input.player_identifier);
         output.field::<SchemaString>(2).add(&&input.provider);
         output.field::<SchemaBytes>(3).add(&&input.metadata);
@@ -1719,17 +2012,17 @@ impl TypeConversion for PlayerIdentity {
 }
 
 /* Components. */ 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PlayerClient {
     pub player_identity: generated::improbable::restricted::PlayerIdentity,
 }
 impl TypeConversion for PlayerClient {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
-            player_identity: <generated::improbable::restricted::PlayerIdentity as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default())?,
+            player_identity: <generated::improbable::restricted::PlayerIdentity as TypeConversion>::from_type(&input.field::<SchemaObject>(1).get_or_default()).map_err(|e| e.in_field(1))?,
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         <generated::improbable::restricted::PlayerIdentity as TypeConversion>::to_type(&&input.player_identity, &mut output.field::<SchemaObject>(1).add())?;
         Ok(())
     }
@@ -1738,25 +2031,36 @@ impl ComponentData<PlayerClient> for PlayerClient {
     fn merge(&mut self, update: PlayerClientUpdate) {
         if let Some(value) = update.player_identity { self.player_identity = value; }
     }
+    fn diff(&self, new: &Self) -> Option<PlayerClientUpdate> {
+        let mut update = PlayerClientUpdate::default();
+        let mut changed = false;
+        if self.player_identity != new.player_identity {
+            update.player_identity = Some(new.player_identity.clone());
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct PlayerClientUpdate {
     pub player_identity: Option<generated::improbable::restricted::PlayerIdentity>,
 }
 impl TypeConversion for PlayerClientUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             player_identity: None,
         };
         let _field_player_identity = input.field::<SchemaObject>(1);
         if _field_player_identity.count() > 0 {
             let field = &_field_player_identity;
-            output.player_identity = Some(<generated::improbable::restricted::PlayerIdentity as TypeConversion>::from_type(&field.get_or_default())?);
+            output.player_identity = Some(<generated::improbable::restricted::PlayerIdentity as TypeConversion>::from_type(&field.get_or_default()).map_err(|e| e.in_field(1))?);
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(ref value) = input.player_identity {
             <generated::improbable::restricted::PlayerIdentity as TypeConversion>::to_type(&value, &mut output.field::<SchemaObject>(1).add())?;
         }
@@ -1769,11 +2073,11 @@ impl ComponentUpdate<PlayerClient> for PlayerClientUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PlayerClientCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PlayerClientCommandResponse {
 }
 
@@ -1784,39 +2088,50 @@ impl Component for PlayerClient {
 
     const ID: ComponentId = 61;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::restricted::PlayerClient, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::restricted::PlayerClient, spatialos_sdk::Error> {
         <generated::improbable::restricted::PlayerClient as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::restricted::PlayerClientUpdate, String> {
-        <generated::improbable::restricted::PlayerClientUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::restricted::PlayerClientUpdate, spatialos_sdk::Error> {
+        let output = <generated::improbable::restricted::PlayerClientUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::restricted::PlayerClientCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::restricted::PlayerClientCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component PlayerClient.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.restricted.PlayerClient",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::restricted::PlayerClientCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::restricted::PlayerClientCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component PlayerClient.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.restricted.PlayerClient",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::restricted::PlayerClient) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::restricted::PlayerClient) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::restricted::PlayerClient as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::restricted::PlayerClient as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::restricted::PlayerClientUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::restricted::PlayerClientUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::restricted::PlayerClientUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::restricted::PlayerClientUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::restricted::PlayerClientCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::restricted::PlayerClientCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -1824,7 +2139,7 @@ impl Component for PlayerClient {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::restricted::PlayerClientCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::restricted::PlayerClientCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -1845,35 +2160,41 @@ impl Component for PlayerClient {
     }
 }
 
+
 inventory::submit!(VTable::new::<PlayerClient>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct System {
 }
 impl TypeConversion for System {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         Ok(())
     }
 }
 impl ComponentData<System> for System {
     fn merge(&mut self, update: SystemUpdate) {
     }
+    fn diff(&self, _new: &Self) -> Option<SystemUpdate> {
+        None
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SystemUpdate {
 }
 impl TypeConversion for SystemUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
         };
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         Ok(())
     }
 }
@@ -1882,11 +2203,11 @@ impl ComponentUpdate<System> for SystemUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SystemCommandRequest {
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SystemCommandResponse {
 }
 
@@ -1897,39 +2218,50 @@ impl Component for System {
 
     const ID: ComponentId = 59;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::restricted::System, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::restricted::System, spatialos_sdk::Error> {
         <generated::improbable::restricted::System as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::restricted::SystemUpdate, String> {
-        <generated::improbable::restricted::SystemUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::restricted::SystemUpdate, spatialos_sdk::Error> {
+        let output = <generated::improbable::restricted::SystemUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::restricted::SystemCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::restricted::SystemCommandRequest, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component System.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.restricted.System",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::restricted::SystemCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::restricted::SystemCommandResponse, spatialos_sdk::Error> {
         match command_index {
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component System.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.restricted.System",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::restricted::System) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::restricted::System) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::restricted::System as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::restricted::System as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::restricted::SystemUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::restricted::SystemUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::restricted::SystemUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::restricted::SystemUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::restricted::SystemCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::restricted::SystemCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             _ => unreachable!()
@@ -1937,7 +2269,7 @@ impl Component for System {
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::restricted::SystemCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::restricted::SystemCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             _ => unreachable!()
@@ -1958,23 +2290,24 @@ impl Component for System {
     }
 }
 
+
 inventory::submit!(VTable::new::<System>());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Worker {
     pub worker_id: String,
     pub worker_type: String,
     pub connection: generated::improbable::restricted::Connection,
 }
 impl TypeConversion for Worker {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {
             worker_id: input.field::<SchemaString>(1).get_or_default(),
             worker_type: input.field::<SchemaString>(2).get_or_default(),
-            connection: <generated::improbable::restricted::Connection as TypeConversion>::from_type(&input.field::<SchemaObject>(3).get_or_default())?,
+            connection: <generated::improbable::restricted::Connection as TypeConversion>::from_type(&input.field::<SchemaObject>(3).get_or_default()).map_err(|e| e.in_field(3))?,
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         output.field::<SchemaString>(1).add(&&input.worker_id);
         output.field::<SchemaString>(2).add(&&input.worker_type);
         <generated::improbable::restricted::Connection as TypeConversion>::to_type(&&input.connection, &mut output.field::<SchemaObject>(3).add())?;
@@ -1987,16 +2320,35 @@ impl ComponentData<Worker> for Worker {
         if let Some(value) = update.worker_type { self.worker_type = value; }
         if let Some(value) = update.connection { self.connection = value; }
     }
+    fn diff(&self, new: &Self) -> Option<WorkerUpdate> {
+        let mut update = WorkerUpdate::default();
+        let mut changed = false;
+        if self.worker_id != new.worker_id {
+            update.worker_id = Some(new.worker_id.clone());
+            changed = true;
+        }
+        if self.worker_type != new.worker_type {
+            update.worker_type = Some(new.worker_type.clone());
+            changed = true;
+        }
+        if self.connection != new.connection {
+            update.connection = Some(new.connection.clone());
+            changed = true;
+        }
+        if changed { Some(update) } else { None }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct WorkerUpdate {
     pub worker_id: Option<String>,
     pub worker_type: Option<String>,
     pub connection: Option<generated::improbable::restricted::Connection>,
 }
 impl TypeConversion for WorkerUpdate {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {
             worker_id: None,
             worker_type: None,
@@ -2015,11 +2367,11 @@ impl TypeConversion for WorkerUpdate {
         let _field_connection = input.field::<SchemaObject>(3);
         if _field_connection.count() > 0 {
             let field = &_field_connection;
-            output.connection = Some(<generated::improbable::restricted::Connection as TypeConversion>::from_type(&field.get_or_default())?);
+            output.connection = Some(<generated::improbable::restricted::Connection as TypeConversion>::from_type(&field.get_or_default()).map_err(|e| e.in_field(3))?);
         }
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {
         if let Some(ref value) = input.worker_id {
             output.field::<SchemaString>(1).add(&value);
         }
@@ -2040,12 +2392,12 @@ impl ComponentUpdate<Worker> for WorkerUpdate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum WorkerCommandRequest {
     Disconnect(generated::improbable::restricted::DisconnectRequest),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum WorkerCommandResponse {
     Disconnect(generated::improbable::restricted::DisconnectResponse),
 }
@@ -2057,62 +2409,79 @@ impl Component for Worker {
 
     const ID: ComponentId = 60;
 
-    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::restricted::Worker, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<generated::improbable::restricted::Worker, spatialos_sdk::Error> {
         <generated::improbable::restricted::Worker as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::restricted::WorkerUpdate, String> {
-        <generated::improbable::restricted::WorkerUpdate as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<generated::improbable::restricted::WorkerUpdate, spatialos_sdk::Error> {
+        let output = <generated::improbable::restricted::WorkerUpdate as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::restricted::WorkerCommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<generated::improbable::restricted::WorkerCommandRequest, spatialos_sdk::Error> {
         match command_index {
             1 => {
                 let result = <generated::improbable::restricted::DisconnectRequest as TypeConversion>::from_type(&request.object());
-                result.and_then(|deserialized| Ok(WorkerCommandRequest::Disconnect(deserialized)))
+                result
+                    .map(WorkerCommandRequest::Disconnect)
+                    .map_err(|e| e.in_component(Self::ID))
             },
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component Worker.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.restricted.Worker",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::restricted::WorkerCommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<generated::improbable::restricted::WorkerCommandResponse, spatialos_sdk::Error> {
         match command_index {
             1 => {
                 let result = <generated::improbable::restricted::DisconnectResponse as TypeConversion>::from_type(&response.object());
-                result.and_then(|deserialized| Ok(WorkerCommandResponse::Disconnect(deserialized)))
+                result
+                    .map(WorkerCommandResponse::Disconnect)
+                    .map_err(|e| e.in_component(Self::ID))
             },
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component Worker.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "improbable.restricted.Worker",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &generated::improbable::restricted::Worker) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &generated::improbable::restricted::Worker) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <generated::improbable::restricted::Worker as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <generated::improbable::restricted::Worker as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &generated::improbable::restricted::WorkerUpdate) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &generated::improbable::restricted::WorkerUpdate) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <generated::improbable::restricted::WorkerUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <generated::improbable::restricted::WorkerUpdate as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_update)
     }
 
-    fn to_request(request: &generated::improbable::restricted::WorkerCommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &generated::improbable::restricted::WorkerCommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {
             WorkerCommandRequest::Disconnect(ref data) => {
-                <generated::improbable::restricted::DisconnectRequest as TypeConversion>::to_type(data, &mut serialized_request.object_mut())?;
+                <generated::improbable::restricted::DisconnectRequest as TypeConversion>::to_type(data, &mut serialized_request.object_mut())
+                    .map_err(|e| e.in_component(Self::ID))?;
             },
             _ => unreachable!()
         }
         Ok(serialized_request)
     }
 
-    fn to_response(response: &generated::improbable::restricted::WorkerCommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &generated::improbable::restricted::WorkerCommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {
             WorkerCommandResponse::Disconnect(ref data) => {
-                <generated::improbable::restricted::DisconnectResponse as TypeConversion>::to_type(data, &mut serialized_response.object_mut())?;
+                <generated::improbable::restricted::DisconnectResponse as TypeConversion>::to_type(data, &mut serialized_response.object_mut())
+                    .map_err(|e| e.in_component(Self::ID))?;
             },
             _ => unreachable!()
         }
@@ -2134,6 +2503,41 @@ impl Component for Worker {
     }
 }
 
+
+#[derive(Debug)]
+pub struct WorkerDisconnectCommand;
+impl Command for WorkerDisconnectCommand {
+    type Component = generated::improbable::restricted::Worker;
+    type Request = generated::improbable::restricted::DisconnectRequest;
+    type Response = generated::improbable::restricted::DisconnectResponse;
+
+    const COMMAND_INDEX: CommandIndex = 1;
+
+    fn into_request(request: Self::Request) -> generated::improbable::restricted::WorkerCommandRequest {
+        WorkerCommandRequest::Disconnect(request)
+    }
+
+    fn into_response(response: Self::Response) -> generated::improbable::restricted::WorkerCommandResponse {
+        WorkerCommandResponse::Disconnect(response)
+    }
+
+    #[allow(unreachable_patterns)]
+    fn request(request: &generated::improbable::restricted::WorkerCommandRequest) -> Option<&Self::Request> {
+        match request {
+            WorkerCommandRequest::Disconnect(request) => Some(request),
+            _ => None,
+        }
+    }
+
+    #[allow(unreachable_patterns)]
+    fn response(response: &generated::improbable::restricted::WorkerCommandResponse) -> Option<&Self::Response> {
+        match response {
+            WorkerCommandResponse::Disconnect(response) => Some(response),
+            _ => None,
+        }
+    }
+}
+
 inventory::submit!(VTable::new::<Worker>());
 
 
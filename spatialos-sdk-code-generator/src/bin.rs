@@ -1,3 +1,4 @@
+extern crate serde_json;
 extern crate spatialos_sdk_code_generator;
 
 use spatialos_sdk_code_generator::generator;
@@ -10,6 +11,15 @@ use std::io::Write;
 pub fn main() {
     let input_filename = args().nth(1).unwrap();
     let output_filename = args().nth(2).unwrap();
+    // The codegen options can optionally be passed as a JSON file.
+    let options = match args().nth(3) {
+        Some(options_filename) => {
+            let options_file =
+                File::open(options_filename).expect("Unable to open the codegen options.");
+            serde_json::from_reader(options_file).expect("Unable to parse the codegen options.")
+        }
+        None => generator::CodegenOptions::default(),
+    };
 
     let mut input_file =
         File::open(input_filename).expect("Unable to open the test schema bundle.");
@@ -17,7 +27,8 @@ pub fn main() {
     input_file
         .read_to_string(&mut contents)
         .expect("Unable to read the test schema bundle");
-    let generated_file = generator::generate_code(schema_bundle::load_bundle(&contents).unwrap());
+    let generated_file =
+        generator::generate_code(schema_bundle::load_bundle(&contents).unwrap(), &options);
     let mut output_file = File::create(output_filename).unwrap();
     output_file.write_all(generated_file.as_bytes()).unwrap();
 }
@@ -0,0 +1,151 @@
+use generator::{self, CodegenOptions};
+use schema_bundle::{self, SchemaBundle};
+use schema_parser::SchemaParser;
+use std::env;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Generates code from a build script, writing it into `OUT_DIR`.
+///
+/// The code is generated either from a prebuilt `bundle.json`, or from schema files, which are
+/// parsed along with the standard schema library, so the SpatialOS SDK isn't needed. The
+/// generated file is pulled into the crate root with `spatialos_sdk::include_generated!()`.
+///
+/// ```no_run
+/// // build.rs
+/// spatialos_sdk_code_generator::Builder::new()
+///     .schema_path("schema")
+///     .generate()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    schema_paths: Vec<PathBuf>,
+    bundle: Option<PathBuf>,
+    out_dir: Option<PathBuf>,
+    file_name: String,
+    options: CodegenOptions,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Builder {
+            schema_paths: Vec::new(),
+            bundle: None,
+            out_dir: None,
+            file_name: "generated.rs".into(),
+            options: CodegenOptions::default(),
+        }
+    }
+
+    /// Adds a directory containing schema files to parse.
+    pub fn schema_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.schema_paths.push(path.into());
+        self
+    }
+
+    /// Generates code from a prebuilt `bundle.json` instead of parsing schema files.
+    pub fn bundle<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.bundle = Some(path.into());
+        self
+    }
+
+    /// Sets the directory the generated code is written to.
+    ///
+    /// Defaults to the `OUT_DIR` environment variable, which Cargo sets for build scripts.
+    pub fn out_dir<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.out_dir = Some(path.into());
+        self
+    }
+
+    /// Sets the name of the generated file. Defaults to `generated.rs`.
+    pub fn file_name<S: Into<String>>(mut self, file_name: S) -> Self {
+        self.file_name = file_name.into();
+        self
+    }
+
+    pub fn options(mut self, options: CodegenOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Generates the code, and returns the path of the generated file.
+    ///
+    /// Prints `cargo:rerun-if-changed` lines for the bundle or schema files, so that the build
+    /// script reruns when the schema changes.
+    pub fn generate(self) -> Result<PathBuf, Box<dyn Error>> {
+        let out_dir = match self.out_dir {
+            Some(ref out_dir) => out_dir.clone(),
+            None => env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or("OUT_DIR must be set, or an output directory must be given")?,
+        };
+
+        let bundle = match self.bundle {
+            Some(ref bundle) => {
+                println!("cargo:rerun-if-changed={}", bundle.display());
+                load_bundle(bundle)?
+            }
+            None => self.parse_schema()?,
+        };
+
+        let generated_file = generator::generate_items(bundle, &self.options);
+        fs::create_dir_all(&out_dir)
+            .map_err(|e| format!("Failed to create {}: {}", out_dir.display(), e))?;
+        let output_path = out_dir.join(&self.file_name);
+        fs::write(&output_path, generated_file)
+            .map_err(|e| format!("Failed to write {}: {}", output_path.display(), e))?;
+
+        Ok(output_path)
+    }
+
+    fn parse_schema(&self) -> Result<SchemaBundle, Box<dyn Error>> {
+        if self.schema_paths.is_empty() {
+            return Err("Either a schema path or a bundle must be given".into());
+        }
+
+        let mut parser = SchemaParser::new();
+        parser.add_standard_library();
+        for schema_path in &self.schema_paths {
+            rerun_if_schema_changed(schema_path)?;
+            parser
+                .add_schema_path(schema_path)
+                .map_err(|e| format!("Failed to read {}: {}", schema_path.display(), e))?;
+        }
+        Ok(parser.parse()?)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+fn load_bundle(path: &Path) -> Result<SchemaBundle, Box<dyn Error>> {
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let bundle = schema_bundle::load_bundle(&contents)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    Ok(bundle)
+}
+
+// Prints `cargo:rerun-if-changed` for a schema directory and every schema file within it, so that
+// both added and modified files trigger a rebuild.
+fn rerun_if_schema_changed(path: &Path) -> Result<(), Box<dyn Error>> {
+    println!("cargo:rerun-if-changed={}", path.display());
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let entry_path = entry?.path();
+            if entry_path.is_dir() || entry_path.extension() == Some(OsStr::new("schema")) {
+                rerun_if_schema_changed(&entry_path)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use spatialos_sdk::worker::internal::schema::*;
 use spatialos_sdk::worker::component::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use <#= vec!["super".to_string(); self.depth() + 1].join("::") #>::generated as generated;
 
@@ -8,18 +8,23 @@ use <#= vec!["super".to_string(); self.depth() + 1].join("::") #>::generated as
 let enum_def = self.get_enum_definition(enum_name);
 let enum_rust_name = self.rust_name(&enum_def.qualified_name);
 #>
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(<#= self.derives(&["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash"], &[], &[]) #>)]<#= self.serde_attributes() #>
 pub enum <#= enum_rust_name #> {
 <# for enum_value in &enum_def.values { #>
     <#= enum_value.name #>,<# } #>
 }
 
-impl From<u32> for <#= enum_rust_name #> {
-    fn from(value: u32) -> Self {
+impl std::convert::TryFrom<u32> for <#= enum_rust_name #> {
+    type Error = spatialos_sdk::Error;
+
+    fn try_from(value: u32) -> Result<Self, spatialos_sdk::Error> {
         match value {
 <# for enum_value in &enum_def.values { #>
-            <#= enum_value.value #> => <#= enum_rust_name #>::<#= enum_value.name #>, <# } #>
-            _ => panic!(format!("Could not convert {} to enum <#= enum_rust_name #>.", value))
+            <#= enum_value.value #> => Ok(<#= enum_rust_name #>::<#= enum_value.name #>), <# } #>
+            _ => Err(spatialos_sdk::Error::decode(
+                "<#= enum_def.qualified_name #>",
+                format!("Unknown enum value {}.", value),
+            )),
         }
     }
 }
@@ -34,22 +39,22 @@ impl <#= enum_rust_name #> {
 }
 <# } #>
 /* Types. */<# for type_name in &self.types { let type_def = self.get_type_definition(type_name); #>
-#[derive(Debug, Clone)]
+#[derive(<#= self.derives(&["Debug", "Clone", "PartialEq"], &type_def.fields, &[]) #>)]<#= self.serde_attributes() #>
 pub struct <#= self.rust_name(&type_def.qualified_name) #> {<#
     for field in &type_def.fields {
     #>
-    pub <#= field.name #>: <#= self.generate_field_type(field) #>,<# } #>
+    pub <#= field.name #>: <#= self.generate_field_type(&type_def.qualified_name, field) #>,<# } #>
 }
 impl TypeConversion for <#= self.rust_name(&type_def.qualified_name) #> {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {<#
             for field in &type_def.fields {
                 let field_expr = format!("input.field::<{}>({})", get_field_schema_type(field), field.field_id);
             #>
-            <#= field.name #>: <#= self.deserialize_field(field, &field_expr) #>,<# } #>
+            <#= field.name #>: <#= self.deserialize_field(&type_def.qualified_name, field, &field_expr) #>,<# } #>
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {<#
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {<#
         for field in &type_def.fields {
             let borrow = if self.field_needs_borrow(field) {
                 "&"
@@ -65,22 +70,22 @@ impl TypeConversion for <#= self.rust_name(&type_def.qualified_name) #> {
 /* Components. */ <# for component_name in &self.components {
     let component = self.get_component_definition(component_name);
     let component_fields = self.get_component_fields(&component); #>
-#[derive(Debug, Clone)]
+#[derive(<#= self.derives(&["Debug", "Clone", "PartialEq"], &component_fields, &[]) #>)]<#= self.serde_attributes() #>
 pub struct <#= self.rust_name(&component.qualified_name) #> {<#
     for field in &component_fields {
     #>
-    pub <#= field.name #>: <#= self.generate_field_type(field) #>,<# } #>
+    pub <#= field.name #>: <#= self.generate_field_type(&component.qualified_name, field) #>,<# } #>
 }
 impl TypeConversion for <#= self.rust_name(&component.qualified_name) #> {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         Ok(Self {<#
             for field in &component_fields {
                 let field_expr = format!("input.field::<{}>({})", get_field_schema_type(field), field.field_id);
             #>
-            <#= field.name #>: <#= self.deserialize_field(field, &field_expr) #>,<# } #>
+            <#= field.name #>: <#= self.deserialize_field(&component.qualified_name, field, &field_expr) #>,<# } #>
         })
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {<#
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {<#
         for field in &component_fields {
             let borrow = if self.field_needs_borrow(field) {
                 "&"
@@ -98,31 +103,58 @@ impl ComponentData<<#= self.rust_name(&component.qualified_name) #>> for <#= sel
         #>
         if let Some(value) = update.<#= field.name #> { self.<#= field.name #> = value; }<# } #>
     }
+    fn diff(&self, <# if component_fields.is_empty() { #>_<# } #>new: &Self) -> Option<<#= self.rust_name(&component.qualified_name) #>Update> {<#
+        if component_fields.is_empty() {
+        #>
+        None<# } else { #>
+        let mut update = <#= self.rust_name(&component.qualified_name) #>Update::default();
+        let mut changed = false;<#
+        for field in &component_fields {
+            let clone = if self.field_needs_borrow(field) {
+                ".clone()"
+            } else {
+                ""
+            };
+        #>
+        if self.<#= field.name #> != new.<#= field.name #> {
+            update.<#= field.name #> = Some(new.<#= field.name #><#= clone #>);
+            changed = true;
+        }<# } #>
+        if changed { Some(update) } else { None }<# } #>
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+// Fields that are `None` are left unchanged by the update. Setting an option, list or map field to
+// `Some` empty value (e.g. `Some(None)` or `Some(Vec::new())`) clears the field.
+#[derive(<#= self.derives(&["Debug", "Clone", "Default", "PartialEq"], &component_fields, &self.event_types(&component)) #>)]<#= self.serde_attributes() #>
 pub struct <#= self.rust_name(&component.qualified_name) #>Update {<#
     for field in &component_fields {
-    #>
-    pub <#= field.name #>: Option<<#= self.generate_field_type(field) #>>,<# } #>
+    #><#= self.update_field_serde_attributes() #>
+    pub <#= field.name #>: Option<<#= self.generate_field_type(&component.qualified_name, field) #>>,<# } #><#
+    for event in &component.events {
+    #><#= self.update_event_serde_attributes() #>
+    pub <#= event.name #>: Vec<<#= self.rust_fqname(&event.type_reference) #>>,<# } #>
 }
 impl TypeConversion for <#= self.rust_name(&component.qualified_name) #>Update {
-    fn from_type(input: &SchemaObject) -> Result<Self, String> {
+    fn from_type(input: &SchemaObject) -> Result<Self, spatialos_sdk::Error> {
         let mut output = Self {<#
             for field in &component_fields {
             #>
-            <#= field.name #>: None,<# } #>
+            <#= field.name #>: None,<# } #><#
+            for event in &component.events {
+            #>
+            <#= event.name #>: Vec::new(),<# } #>
         };<#
         for field in &component_fields {
         #>
         let _field_<#= field.name #> = input.field::<<#= get_field_schema_type(field) #>>(<#= field.field_id #>);
         if _field_<#= field.name #>.count() > 0 {
             let field = &_field_<#= field.name #>;
-            output.<#= field.name #> = Some(<#= self.deserialize_field(field, "field") #>);
+            output.<#= field.name #> = Some(<#= self.deserialize_field(&component.qualified_name, field, "field") #>);
         }<# } #>
         Ok(output)
     }
-    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), String> {<#
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), spatialos_sdk::Error> {<#
         for field in &component_fields {
             let ref_decorator = if self.field_needs_borrow(field) {
                 "ref "
@@ -140,18 +172,21 @@ impl ComponentUpdate<<#= self.rust_name(&component.qualified_name) #>> for <#= s
     fn merge(&mut self, update: <#= self.rust_name(&component.qualified_name) #>Update) {<#
         for field in &self.get_component_fields(&component) {
         #>
-        if update.<#= field.name #>.is_some() { self.<#= field.name #> = update.<#= field.name #>; }<# } #>
+        if update.<#= field.name #>.is_some() { self.<#= field.name #> = update.<#= field.name #>; }<# } #><#
+        for event in &component.events {
+        #>
+        self.<#= event.name #>.extend(update.<#= event.name #>);<# } #>
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(<#= self.derives(&["Debug", "Clone", "PartialEq"], &[], &self.request_types(&component)) #>)]<#= self.serde_attributes() #>
 pub enum <#= self.rust_name(&component.qualified_name) #>CommandRequest {<#
     for command in &component.commands {
     #>
     <#= command.name.to_camel_case() #>(<#= self.rust_fqname(&command.request_type) #>),<# } #>
 }
 
-#[derive(Debug, Clone)]
+#[derive(<#= self.derives(&["Debug", "Clone", "PartialEq"], &[], &self.response_types(&component)) #>)]<#= self.serde_attributes() #>
 pub enum <#= self.rust_name(&component.qualified_name) #>CommandResponse {<#
     for command in &component.commands {
     #>
@@ -165,70 +200,121 @@ impl Component for <#= self.rust_name(&component.qualified_name) #> {
 
     const ID: ComponentId = <#= component.component_id #>;
 
-    fn from_data(data: &SchemaComponentData) -> Result<<#= self.rust_fqname(&component.qualified_name) #>, String> {
+    fn from_data(data: &SchemaComponentData) -> Result<<#= self.rust_fqname(&component.qualified_name) #>, spatialos_sdk::Error> {
         <<#= self.rust_fqname(&component.qualified_name) #> as TypeConversion>::from_type(&data.fields())
+            .map_err(|e| e.in_component(Self::ID))
     }
 
-    fn from_update(update: &SchemaComponentUpdate) -> Result<<#= self.rust_fqname(&component.qualified_name) #>Update, String> {
-        <<#= self.rust_fqname(&component.qualified_name) #>Update as TypeConversion>::from_type(&update.fields())
+    fn from_update(update: &SchemaComponentUpdate) -> Result<<#= self.rust_fqname(&component.qualified_name) #>Update, spatialos_sdk::Error> {<#
+        let has_cleared_fields = component_fields.iter().any(|field| self.field_empty_check(field, "value").is_some());
+        let output_binding = if has_cleared_fields || !component.events.is_empty() { "mut output" } else { "output" };
+        #>
+        let <#= output_binding #> = <<#= self.rust_fqname(&component.qualified_name) #>Update as TypeConversion>::from_type(&update.fields())
+            .map_err(|e| e.in_component(Self::ID))?;<#
+        for field in &component_fields {
+            if self.field_empty_check(field, "value").is_some() {
+        #>
+        if update.is_field_cleared(<#= field.field_id #>) {
+            output.<#= field.name #> = Some(Default::default());
+        }<# } }
+        if !component.events.is_empty() { #>
+        let events = update.events();<# }
+        for event in &component.events {
+        #>
+        let _event_<#= event.name #> = events.field::<SchemaObject>(<#= event.event_index #>);
+        for i in 0.._event_<#= event.name #>.count() {
+            output.<#= event.name #>.push(<<#= self.rust_fqname(&event.type_reference) #> as TypeConversion>::from_type(&_event_<#= event.name #>.index(i))
+                .map_err(|e| e.in_component(Self::ID))?);
+        }<# } #>
+        Ok(output)
     }
 
-    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<<#= self.rust_fqname(&component.qualified_name) #>CommandRequest, String> {
+    fn from_request(command_index: CommandIndex, request: &SchemaCommandRequest) -> Result<<#= self.rust_fqname(&component.qualified_name) #>CommandRequest, spatialos_sdk::Error> {
         match command_index {<#
             for command in &component.commands {
             #>
             <#= command.command_index #> => {
                 let result = <<#= self.rust_fqname(&command.request_type) #> as TypeConversion>::from_type(&request.object());
-                result.and_then(|deserialized| Ok(<#= self.rust_name(&component.qualified_name) #>CommandRequest::<#= command.name.to_camel_case() #>(deserialized)))
+                result
+                    .map(<#= self.rust_name(&component.qualified_name) #>CommandRequest::<#= command.name.to_camel_case() #>)
+                    .map_err(|e| e.in_component(Self::ID))
             },<# } #>
-            _ => Err(format!("Attempted to deserialize an unrecognised command request with index {} in component <#= self.rust_name(&component.qualified_name) #>.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "<#= component.qualified_name #>",
+                format!("Attempted to deserialize an unrecognised command request with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<<#= self.rust_fqname(&component.qualified_name) #>CommandResponse, String> {
+    fn from_response(command_index: CommandIndex, response: &SchemaCommandResponse) -> Result<<#= self.rust_fqname(&component.qualified_name) #>CommandResponse, spatialos_sdk::Error> {
         match command_index {<#
             for command in &component.commands {
             #>
             <#= command.command_index #> => {
                 let result = <<#= self.rust_fqname(&command.response_type) #> as TypeConversion>::from_type(&response.object());
-                result.and_then(|deserialized| Ok(<#= self.rust_name(&component.qualified_name) #>CommandResponse::<#= command.name.to_camel_case() #>(deserialized)))
+                result
+                    .map(<#= self.rust_name(&component.qualified_name) #>CommandResponse::<#= command.name.to_camel_case() #>)
+                    .map_err(|e| e.in_component(Self::ID))
             },<# } #>
-            _ => Err(format!("Attempted to deserialize an unrecognised command response with index {} in component <#= self.rust_name(&component.qualified_name) #>.", command_index))
+            _ => Err(spatialos_sdk::Error::decode(
+                "<#= component.qualified_name #>",
+                format!("Attempted to deserialize an unrecognised command response with index {}.", command_index),
+            ).in_component(Self::ID))
         }
     }
 
-    fn to_data(data: &<#= self.rust_fqname(&component.qualified_name) #>) -> Result<SchemaComponentData, String> {
+    fn to_data(data: &<#= self.rust_fqname(&component.qualified_name) #>) -> Result<SchemaComponentData, spatialos_sdk::Error> {
         let mut serialized_data = SchemaComponentData::new();
-        <<#= self.rust_fqname(&component.qualified_name) #> as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())?;
+        <<#= self.rust_fqname(&component.qualified_name) #> as TypeConversion>::to_type(data, &mut serialized_data.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;
         Ok(serialized_data)
     }
 
-    fn to_update(update: &<#= self.rust_fqname(&component.qualified_name) #>Update) -> Result<SchemaComponentUpdate, String> {
+    fn to_update(update: &<#= self.rust_fqname(&component.qualified_name) #>Update) -> Result<SchemaComponentUpdate, spatialos_sdk::Error> {
         let mut serialized_update = SchemaComponentUpdate::new();
-        <<#= self.rust_fqname(&component.qualified_name) #>Update as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())?;
+        <<#= self.rust_fqname(&component.qualified_name) #>Update as TypeConversion>::to_type(update, &mut serialized_update.fields_mut())
+            .map_err(|e| e.in_component(Self::ID))?;<#
+        for field in &component_fields {
+            if let Some(is_empty) = self.field_empty_check(field, "value") {
+        #>
+        if let Some(ref value) = update.<#= field.name #> {
+            if <#= is_empty #> {
+                serialized_update.add_cleared_field(<#= field.field_id #>);
+            }
+        }<# } }
+        if !component.events.is_empty() { #>
+        let mut events = serialized_update.events_mut();<# }
+        for event in &component.events {
+        #>
+        for event in &update.<#= event.name #> {
+            <<#= self.rust_fqname(&event.type_reference) #> as TypeConversion>::to_type(event, &mut events.field::<SchemaObject>(<#= event.event_index #>).add())
+                .map_err(|e| e.in_component(Self::ID))?;
+        }<# } #>
         Ok(serialized_update)
     }
 
-    fn to_request(request: &<#= self.rust_fqname(&component.qualified_name) #>CommandRequest) -> Result<SchemaCommandRequest, String> {
+    fn to_request(request: &<#= self.rust_fqname(&component.qualified_name) #>CommandRequest) -> Result<SchemaCommandRequest, spatialos_sdk::Error> {
         let mut serialized_request = SchemaCommandRequest::new();
         match request {<#
             for command in &component.commands {
             #>
             <#= self.rust_name(&component.qualified_name) #>CommandRequest::<#= command.name.to_camel_case() #>(ref data) => {
-                <<#= self.rust_fqname(&command.request_type) #> as TypeConversion>::to_type(data, &mut serialized_request.object_mut())?;
+                <<#= self.rust_fqname(&command.request_type) #> as TypeConversion>::to_type(data, &mut serialized_request.object_mut())
+                    .map_err(|e| e.in_component(Self::ID))?;
             },<# } #>
             _ => unreachable!()
         }
         Ok(serialized_request)
     }
 
-    fn to_response(response: &<#= self.rust_fqname(&component.qualified_name) #>CommandResponse) -> Result<SchemaCommandResponse, String> {
+    fn to_response(response: &<#= self.rust_fqname(&component.qualified_name) #>CommandResponse) -> Result<SchemaCommandResponse, spatialos_sdk::Error> {
         let mut serialized_response = SchemaCommandResponse::new();
         match response {<#
             for command in &component.commands {
             #>
             <#= self.rust_name(&component.qualified_name) #>CommandResponse::<#= command.name.to_camel_case() #>(ref data) => {
-                <<#= self.rust_fqname(&command.response_type) #> as TypeConversion>::to_type(data, &mut serialized_response.object_mut())?;
+                <<#= self.rust_fqname(&command.response_type) #> as TypeConversion>::to_type(data, &mut serialized_response.object_mut())
+                    .map_err(|e| e.in_component(Self::ID))?;
             },<# } #>
             _ => unreachable!()
         }
@@ -254,5 +340,41 @@ impl Component for <#= self.rust_name(&component.qualified_name) #> {
     }
 }
 
+<# for command in &component.commands {
+    let command_type = format!("{}{}Command", self.rust_name(&component.qualified_name), command.name.to_camel_case()); #>
+#[derive(Debug)]
+pub struct <#= command_type #>;
+impl Command for <#= command_type #> {
+    type Component = <#= self.rust_fqname(&component.qualified_name) #>;
+    type Request = <#= self.rust_fqname(&command.request_type) #>;
+    type Response = <#= self.rust_fqname(&command.response_type) #>;
+
+    const COMMAND_INDEX: CommandIndex = <#= command.command_index #>;
+
+    fn into_request(request: Self::Request) -> <#= self.rust_fqname(&component.qualified_name) #>CommandRequest {
+        <#= self.rust_name(&component.qualified_name) #>CommandRequest::<#= command.name.to_camel_case() #>(request)
+    }
+
+    fn into_response(response: Self::Response) -> <#= self.rust_fqname(&component.qualified_name) #>CommandResponse {
+        <#= self.rust_name(&component.qualified_name) #>CommandResponse::<#= command.name.to_camel_case() #>(response)
+    }
+
+    #[allow(unreachable_patterns)]
+    fn request(request: &<#= self.rust_fqname(&component.qualified_name) #>CommandRequest) -> Option<&Self::Request> {
+        match request {
+            <#= self.rust_name(&component.qualified_name) #>CommandRequest::<#= command.name.to_camel_case() #>(request) => Some(request),
+            _ => None,
+        }
+    }
+
+    #[allow(unreachable_patterns)]
+    fn response(response: &<#= self.rust_fqname(&component.qualified_name) #>CommandResponse) -> Option<&Self::Response> {
+        match response {
+            <#= self.rust_name(&component.qualified_name) #>CommandResponse::<#= command.name.to_camel_case() #>(response) => Some(response),
+            _ => None,
+        }
+    }
+}
+<# } #>
 inventory::submit!(VTable::new::<<#= self.rust_name(&component.qualified_name) #>>());
 <# } #>
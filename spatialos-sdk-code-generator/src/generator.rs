@@ -1,7 +1,7 @@
 use heck::CamelCase;
 use schema_bundle::*;
 use std::cell::RefCell;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::rc::Rc;
 
 fn get_rust_primitive_type_tag(primitive_type: &PrimitiveType) -> &str {
@@ -22,11 +22,32 @@ fn get_rust_primitive_type_tag(primitive_type: &PrimitiveType) -> &str {
         PrimitiveType::Double => "SchemaDouble",
         PrimitiveType::String => "SchemaString",
         PrimitiveType::EntityId => "SchemaEntityId",
-        PrimitiveType::Entity => panic!("Entity serialization unimplemented."),
+        // Entities are stored as an object with a field per component.
+        PrimitiveType::Entity => "SchemaObject",
         PrimitiveType::Bytes => "SchemaBytes",
     }
 }
 
+fn is_entity(primitive_type: &PrimitiveType) -> bool {
+    matches!(primitive_type, PrimitiveType::Entity)
+}
+
+fn box_if(boxed: bool, rust_type: String) -> String {
+    if boxed {
+        format!("Box<{}>", rust_type)
+    } else {
+        rust_type
+    }
+}
+
+fn box_expr_if(boxed: bool, expression: String) -> String {
+    if boxed {
+        format!("Box::new({})", expression)
+    } else {
+        expression
+    }
+}
+
 fn get_schema_type(value_type: &TypeReference) -> &str {
     match value_type {
         TypeReference::Primitive(primitive) => get_rust_primitive_type_tag(&primitive),
@@ -46,6 +67,46 @@ fn get_field_schema_type(field: &FieldDefinition) -> &str {
     }
 }
 
+/// Options that control the code generated from a schema bundle.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodegenOptions {
+    /// Derive `serde::Serialize` and `serde::Deserialize` for all generated types, components,
+    /// updates and commands.
+    ///
+    /// The generated code uses the `serde` re-export from `spatialos-sdk`, so the SDK's `serde`
+    /// feature needs to be enabled. `Entity` fields are serialized as maps from component ID to
+    /// the component's schema data, as a base64 string in human-readable formats.
+    pub serde: bool,
+
+    /// Extra traits to derive for all generated types, components, updates and commands, e.g.
+    /// `["Eq", "Hash"]`.
+    ///
+    /// `Eq` and `Hash` are only derived for items whose fields all implement them, so they're
+    /// skipped for items that contain floats, entities or mapped types.
+    pub derives: Vec<String>,
+
+    /// The Rust type used for map fields.
+    pub map_type: MapType,
+
+    /// Replaces the generated Rust type for schema types, keyed by the qualified name of the
+    /// schema type, e.g. `"improbable.Coordinates" = "glam::DVec3"`.
+    ///
+    /// The Rust type is used for fields of the schema type. The generated type is still
+    /// emitted, and the Rust type must implement `Clone`, `PartialEq`, `From` the generated
+    /// type, and the generated type must implement `From` the Rust type.
+    pub type_mappings: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MapType {
+    #[default]
+    #[serde(rename = "btree_map")]
+    BTreeMap,
+    #[serde(rename = "hash_map")]
+    HashMap,
+}
+
 #[derive(Debug, Template)]
 #[TemplatePath = "./src/generated_code_mod.tt.rs"]
 struct Package {
@@ -80,6 +141,165 @@ impl Package {
         self.path.len()
     }
 
+    // Returns the traits to derive for an item, given the traits it always derives, and the fields
+    // and types that it contains. Extra derives from the options are appended.
+    fn derives(&self, base: &[&str], fields: &[FieldDefinition], type_names: &[String]) -> String {
+        let extra_derives = self.generated_code.borrow().options.derives.clone();
+        let mut derives: Vec<String> = base.iter().map(|derive| derive.to_string()).collect();
+        for derive in extra_derives {
+            if derives.contains(&derive) {
+                continue;
+            }
+            if derive == "Eq" || derive == "Hash" {
+                let mut visiting = BTreeSet::new();
+                let implemented = fields
+                    .iter()
+                    .all(|field| self.field_implements(field, &derive, &mut visiting))
+                    && type_names
+                        .iter()
+                        .all(|type_name| self.fields_implement(type_name, &derive, &mut visiting));
+                if !implemented {
+                    continue;
+                }
+            }
+            derives.push(derive);
+        }
+        derives.join(", ")
+    }
+
+    // Returns whether values of the field implement `derive`, which is either `Eq` or `Hash`.
+    // `visiting` holds the types that are already being checked, so that recursive types
+    // terminate.
+    fn field_implements(
+        &self,
+        field: &FieldDefinition,
+        derive: &str,
+        visiting: &mut BTreeSet<String>,
+    ) -> bool {
+        match field.field_type {
+            FieldDefinition_FieldType::Singular {
+                type_reference: ref value_type,
+            }
+            | FieldDefinition_FieldType::Option {
+                inner_type: ref value_type,
+            }
+            | FieldDefinition_FieldType::List {
+                inner_type: ref value_type,
+            } => self.type_implements(value_type, derive, visiting),
+            FieldDefinition_FieldType::Map {
+                ref key_type,
+                ref value_type,
+            } => {
+                // `HashMap` doesn't implement `Hash`.
+                let map_type = self.generated_code.borrow().options.map_type;
+                !(derive == "Hash" && map_type == MapType::HashMap)
+                    && self.type_implements(key_type, derive, visiting)
+                    && self.type_implements(value_type, derive, visiting)
+            }
+        }
+    }
+
+    fn type_implements(
+        &self,
+        value_type: &TypeReference,
+        derive: &str,
+        visiting: &mut BTreeSet<String>,
+    ) -> bool {
+        match value_type {
+            TypeReference::Primitive(ref primitive) => !matches!(
+                primitive,
+                PrimitiveType::Float | PrimitiveType::Double | PrimitiveType::Entity
+            ),
+            TypeReference::Enum(_) => true,
+            // The traits implemented by mapped types aren't known.
+            TypeReference::Type(ref type_ref) if self.mapped_type(type_ref).is_some() => false,
+            TypeReference::Type(ref type_ref) => self.fields_implement(type_ref, derive, visiting),
+        }
+    }
+
+    fn fields_implement(
+        &self,
+        type_name: &str,
+        derive: &str,
+        visiting: &mut BTreeSet<String>,
+    ) -> bool {
+        if !visiting.insert(type_name.to_string()) {
+            return true;
+        }
+        self.get_type_definition(type_name)
+            .fields
+            .iter()
+            .all(|field| self.field_implements(field, derive, visiting))
+    }
+
+    fn event_types(&self, component: &ComponentDefinition) -> Vec<String> {
+        component
+            .events
+            .iter()
+            .map(|event| event.type_reference.clone())
+            .collect()
+    }
+
+    fn request_types(&self, component: &ComponentDefinition) -> Vec<String> {
+        component
+            .commands
+            .iter()
+            .map(|command| command.request_type.clone())
+            .collect()
+    }
+
+    fn response_types(&self, component: &ComponentDefinition) -> Vec<String> {
+        component
+            .commands
+            .iter()
+            .map(|command| command.response_type.clone())
+            .collect()
+    }
+
+    fn map_type(&self) -> &'static str {
+        match self.generated_code.borrow().options.map_type {
+            MapType::BTreeMap => "BTreeMap",
+            MapType::HashMap => "HashMap",
+        }
+    }
+
+    // Returns the Rust type that replaces the schema type `qualified_name`, if it's mapped.
+    fn mapped_type(&self, qualified_name: &str) -> Option<String> {
+        self.generated_code
+            .borrow()
+            .options
+            .type_mappings
+            .get(qualified_name)
+            .cloned()
+    }
+
+    // Attributes added to every generated struct and enum that holds schema data.
+    fn serde_attributes(&self) -> &'static str {
+        if self.generated_code.borrow().options.serde {
+            "\n#[derive(spatialos_sdk::serde::Serialize, spatialos_sdk::serde::Deserialize)]\n#[serde(crate = \"spatialos_sdk::serde\")]"
+        } else {
+            ""
+        }
+    }
+
+    // Fields of an update that are `None` are omitted, so that a field which is present but
+    // `null` can be deserialized as `Some(None)`, which clears an option field.
+    fn update_field_serde_attributes(&self) -> &'static str {
+        if self.generated_code.borrow().options.serde {
+            "\n    #[serde(default, skip_serializing_if = \"Option::is_none\", deserialize_with = \"spatialos_sdk::worker::component::deserialize_update_field\")]"
+        } else {
+            ""
+        }
+    }
+
+    fn update_event_serde_attributes(&self) -> &'static str {
+        if self.generated_code.borrow().options.serde {
+            "\n    #[serde(default, skip_serializing_if = \"Vec::is_empty\")]"
+        } else {
+            ""
+        }
+    }
+
     fn rust_name(&self, qualified_name: &str) -> String {
         let tokens: Vec<&str> = qualified_name.split('.').collect();
         tokens[self.path.len()..].join("_")
@@ -160,27 +380,39 @@ impl Package {
                 PrimitiveType::Double => "f64",
                 PrimitiveType::String => "String",
                 PrimitiveType::EntityId => "spatialos_sdk::worker::EntityId",
-                PrimitiveType::Entity => panic!("Entity serialization unimplemented."),
+                PrimitiveType::Entity => "spatialos_sdk::worker::entity::Entity",
                 PrimitiveType::Bytes => "Vec<u8>",
             }
             .to_string(),
             TypeReference::Enum(ref enum_ref) => {
                 self.rust_fqname(&self.resolve_enum_reference(&enum_ref).qualified_name)
             }
-            TypeReference::Type(ref type_ref) => {
-                self.rust_fqname(&self.resolve_type_reference(&type_ref).qualified_name)
-            }
+            TypeReference::Type(ref type_ref) => match self.mapped_type(type_ref) {
+                Some(rust_type) => rust_type,
+                None => self.rust_fqname(&self.resolve_type_reference(&type_ref).qualified_name),
+            },
         }
     }
 
-    fn generate_field_type(&self, field: &FieldDefinition) -> String {
+    // Returns true if the field of the type or component 'owner' is part of a recursive type cycle,
+    // and so is boxed in the generated struct.
+    fn is_boxed(&self, owner: &str, field: &FieldDefinition) -> bool {
+        self.generated_code
+            .borrow()
+            .boxed_fields
+            .contains(&(owner.to_string(), field.field_id))
+    }
+
+    fn generate_field_type(&self, owner: &str, field: &FieldDefinition) -> String {
+        let boxed = self.is_boxed(owner, field);
         match field.field_type {
             FieldDefinition_FieldType::Singular { ref type_reference } => {
-                self.generate_rust_type_name(type_reference)
-            }
-            FieldDefinition_FieldType::Option { ref inner_type } => {
-                format!("Option<{}>", self.generate_rust_type_name(inner_type))
+                box_if(boxed, self.generate_rust_type_name(type_reference))
             }
+            FieldDefinition_FieldType::Option { ref inner_type } => format!(
+                "Option<{}>",
+                box_if(boxed, self.generate_rust_type_name(inner_type))
+            ),
             FieldDefinition_FieldType::List { ref inner_type } => {
                 format!("Vec<{}>", self.generate_rust_type_name(inner_type))
             }
@@ -188,13 +420,27 @@ impl Package {
                 ref key_type,
                 ref value_type,
             } => format!(
-                "BTreeMap<{}, {}>",
+                "{}<{}, {}>",
+                self.map_type(),
                 self.generate_rust_type_name(key_type),
                 self.generate_rust_type_name(value_type)
             ),
         }
     }
 
+    // Option, list and map fields can be set to their empty value by a component update. This helper
+    // function returns an expression which checks whether the field in 'expression' is empty, or None if
+    // the field can't be cleared.
+    fn field_empty_check(&self, field: &FieldDefinition, expression: &str) -> Option<String> {
+        match field.field_type {
+            FieldDefinition_FieldType::Singular { .. } => None,
+            FieldDefinition_FieldType::Option { .. } => Some(format!("{}.is_none()", expression)),
+            FieldDefinition_FieldType::List { .. } | FieldDefinition_FieldType::Map { .. } => {
+                Some(format!("{}.is_empty()", expression))
+            }
+        }
+    }
+
     // Some fields need to be borrowed when serializing (such as strings or objects). This helper function returns true
     // if this is required.
     fn field_needs_borrow(&self, field: &FieldDefinition) -> bool {
@@ -254,7 +500,7 @@ impl Package {
             FieldDefinition_FieldType::List { ref inner_type } => {
                 // If we have a list of primitives, we can just pass a slice directly to add_list.
                 match inner_type {
-                    TypeReference::Primitive(ref primitive) => format!(
+                    TypeReference::Primitive(ref primitive) if !is_entity(primitive) => format!(
                         "{}.field::<{}>({}).add_list(&{}[..])",
                         schema_object,
                         get_rust_primitive_type_tag(primitive),
@@ -318,6 +564,10 @@ impl Package {
         schema_object: &str,
     ) -> String {
         match value_type {
+            TypeReference::Primitive(ref primitive) if is_entity(primitive) => format!(
+                "<spatialos_sdk::worker::entity::Entity as TypeConversion>::to_type(&{}, &mut {}.field::<SchemaObject>({}).add())?",
+                expression, schema_object, field_id
+            ),
             TypeReference::Primitive(ref primitive) => {
                 let borrow = if self.type_needs_borrow(value_type) {
                     "&"
@@ -340,6 +590,14 @@ impl Package {
             TypeReference::Type(ref type_ref) => {
                 let type_definition =
                     self.rust_fqname(&self.get_type_definition(type_ref).qualified_name);
+                // Mapped types are converted to the generated type before being serialized.
+                let expression = match self.mapped_type(type_ref) {
+                    Some(rust_type) => format!(
+                        "<{} as From<{}>>::from(<{} as Clone>::clone(&{}))",
+                        type_definition, rust_type, rust_type, expression
+                    ),
+                    None => expression.to_string(),
+                };
                 format!(
                     "<{} as TypeConversion>::to_type(&{}, &mut {}.field::<SchemaObject>({}).add())?",
                     type_definition, expression, schema_object, field_id
@@ -349,24 +607,37 @@ impl Package {
     }
 
     // Generates an expression which deserializes a field from a schema field 'schema_field'.
-    fn deserialize_field(&self, field: &FieldDefinition, schema_field: &str) -> String {
+    fn deserialize_field(
+        &self,
+        owner: &str,
+        field: &FieldDefinition,
+        schema_field: &str,
+    ) -> String {
+        let boxed = self.is_boxed(owner, field);
         match field.field_type {
             FieldDefinition_FieldType::Singular { ref type_reference } => {
                 let schema_expr = format!("{}.get_or_default()", schema_field);
-                self.deserialize_type_unwrapped(type_reference, &schema_expr)
+                let deserialize_expr =
+                    self.deserialize_type_unwrapped(type_reference, &schema_expr, field.field_id);
+                box_expr_if(boxed, deserialize_expr)
             }
             FieldDefinition_FieldType::Option { ref inner_type } => {
                 let schema_expr = format!("{}.get()", schema_field);
+                let deserialize_expr =
+                    self.deserialize_type_unwrapped(inner_type, "data", field.field_id);
                 format!(
                     "if let Some(data) = {} {{ Some({}) }} else {{ None }}",
                     schema_expr,
-                    self.deserialize_type_unwrapped(inner_type, "data")
+                    box_expr_if(boxed, deserialize_expr)
                 )
             }
             FieldDefinition_FieldType::List { ref inner_type } => {
                 let capacity = format!("{}.count()", schema_field);
-                let deserialize_element = self
-                    .deserialize_type_unwrapped(inner_type, &format!("{}.index(i)", schema_field));
+                let deserialize_element = self.deserialize_type_unwrapped(
+                    inner_type,
+                    &format!("{}.index(i)", schema_field),
+                    field.field_id,
+                );
                 format!("{{ let size = {}; let mut l = Vec::with_capacity(size); for i in 0..size {{ l.push({}); }}; l }}", capacity, deserialize_element)
             }
             FieldDefinition_FieldType::Map {
@@ -380,6 +651,7 @@ impl Package {
                         "kv.field::<{}>(1).get_or_default()",
                         get_schema_type(key_type)
                     ),
+                    field.field_id,
                 );
                 let deserialize_value = self.deserialize_type_unwrapped(
                     value_type,
@@ -387,39 +659,68 @@ impl Package {
                         "kv.field::<{}>(2).get_or_default()",
                         get_schema_type(value_type)
                     ),
+                    field.field_id,
                 );
-                format!("{{ let size = {}; let mut m = BTreeMap::new(); for i in 0..size {{ let kv = {}.index(i); m.insert({}, {}); }}; m }}", capacity, schema_field, deserialize_key, deserialize_value)
+                format!("{{ let size = {}; let mut m = {}::new(); for i in 0..size {{ let kv = {}.index(i); m.insert({}, {}); }}; m }}", capacity, self.map_type(), schema_field, deserialize_key, deserialize_value)
             }
         }
     }
 
     // Generates an expression which deserializes a value from a schema type in 'schema_expr'. In the non primitive
-    // case, this expression is of type Result<GeneratedType, String>, otherwise it is just T (where T is the primitive type).
+    // case, this expression is of type Result<GeneratedType, Error>, otherwise it is just T (where T is the primitive type).
+    // Enums are fallible too, as the data may contain a value that isn't known to the generated enum.
     fn deserialize_type(&self, value_type: &TypeReference, schema_expr: &str) -> String {
         match value_type {
+            TypeReference::Primitive(ref primitive) if is_entity(primitive) => format!(
+                "<spatialos_sdk::worker::entity::Entity as TypeConversion>::from_type(&{})",
+                schema_expr
+            ),
             TypeReference::Primitive(_) => schema_expr.to_string(),
             TypeReference::Enum(ref enum_ref) => {
                 let enum_name =
                     self.rust_fqname(&self.get_enum_definition(enum_ref).qualified_name);
-                format!("{}::from({})", enum_name, schema_expr)
+                format!(
+                    "<{} as std::convert::TryFrom<u32>>::try_from({})",
+                    enum_name, schema_expr
+                )
             }
             TypeReference::Type(ref type_ref) => {
                 let type_name =
                     self.rust_fqname(&self.get_type_definition(type_ref).qualified_name);
-                format!(
+                let deserialize_expr = format!(
                     "<{} as TypeConversion>::from_type(&{})",
                     type_name, schema_expr
-                )
+                );
+                match self.mapped_type(type_ref) {
+                    Some(rust_type) => format!(
+                        "{}.map(<{} as From<{}>>::from)",
+                        deserialize_expr, rust_type, type_name
+                    ),
+                    None => deserialize_expr,
+                }
             }
         }
     }
 
     // Generates an expression which deserializes a value from a schema type in 'schema_expr'. Also unwraps the result
-    // using ? operator if the deserialize expression results in a Result<_, String> type.
-    fn deserialize_type_unwrapped(&self, value_type: &TypeReference, schema_expr: &str) -> String {
+    // using ? operator if the deserialize expression results in a Result<_, Error> type, recording the ID of the field
+    // being deserialized in the error.
+    fn deserialize_type_unwrapped(
+        &self,
+        value_type: &TypeReference,
+        schema_expr: &str,
+        field_id: u32,
+    ) -> String {
         let deserialize_expr = self.deserialize_type(value_type, schema_expr);
-        if let TypeReference::Type(_) = value_type {
-            format!("{}?", deserialize_expr)
+        let is_fallible = match value_type {
+            TypeReference::Type(_) | TypeReference::Enum(_) => true,
+            TypeReference::Primitive(ref primitive) => is_entity(primitive),
+        };
+        if is_fallible {
+            format!(
+                "{}.map_err(|e| e.in_field({}))?",
+                deserialize_expr, field_id
+            )
         } else {
             deserialize_expr
         }
@@ -433,6 +734,9 @@ struct GeneratedCode {
     enums: BTreeMap<String, EnumDefinition>,
     types: BTreeMap<String, TypeDefinition>,
     components: BTreeMap<String, ComponentDefinition>,
+    // The (type name, field ID) pairs of fields which are boxed in the generated code.
+    boxed_fields: HashSet<(String, u32)>,
+    options: CodegenOptions,
 }
 
 impl GeneratedCode {
@@ -462,6 +766,63 @@ impl GeneratedCode {
     }
 }
 
+// Returns the type referenced by a field if the field stores it inline, i.e. it's a singular or
+// option field. List and map fields store their elements on the heap, so never form a cycle of
+// inline types.
+fn inline_type_reference(field: &FieldDefinition) -> Option<&str> {
+    match field.field_type {
+        FieldDefinition_FieldType::Singular {
+            type_reference: TypeReference::Type(ref type_ref),
+        }
+        | FieldDefinition_FieldType::Option {
+            inner_type: TypeReference::Type(ref type_ref),
+        } => Some(type_ref.as_str()),
+        _ => None,
+    }
+}
+
+// Returns true if the type 'from' stores the type 'target' inline, either directly or through
+// other types.
+fn type_contains_inline(
+    types: &BTreeMap<String, TypeDefinition>,
+    from: &str,
+    target: &str,
+    visited: &mut BTreeSet<String>,
+) -> bool {
+    if from == target {
+        return true;
+    }
+    if !visited.insert(from.to_string()) {
+        return false;
+    }
+
+    match types.get(from) {
+        Some(type_def) => type_def
+            .fields
+            .iter()
+            .filter_map(inline_type_reference)
+            .any(|field_type| type_contains_inline(types, field_type, target, visited)),
+        None => false,
+    }
+}
+
+// Finds the fields which would make a type contain itself, such as `option<Node> child` in a type
+// `Node`, so that they are boxed in the generated code. Otherwise the generated struct would have
+// an infinite size.
+fn find_recursive_fields(types: &BTreeMap<String, TypeDefinition>) -> HashSet<(String, u32)> {
+    let mut recursive_fields = HashSet::new();
+    for (type_name, type_def) in types {
+        for field in &type_def.fields {
+            if let Some(field_type) = inline_type_reference(field) {
+                if type_contains_inline(types, field_type, type_name, &mut BTreeSet::new()) {
+                    recursive_fields.insert((type_name.clone(), field.field_id));
+                }
+            }
+        }
+    }
+    recursive_fields
+}
+
 // This function ensures that given a path ["example", "foo"] and the root package, it will create
 // 2 packages with the following structure:
 //   Package("root", [Package("example", [Package("foo", [])])])
@@ -506,23 +867,31 @@ fn generate_module(package: &Package) -> String {
     let module_contents = format!("{}\n{}", package, submodules);
     // The only package with a depth of 0 is the root package.
     if package.depth() == 0 {
-        let allow_warnings = vec![
-            "#![allow(unused_imports)]",
-            "#![allow(unreachable_code)]",
-            "#![allow(unreachable_patterns)]",
-            "#![allow(unused_variables)]",
-            "#![allow(dead_code)]",
-            "#![allow(non_camel_case_types)]",
-            "#![allow(unused_mut)]",
-        ]
-        .join("\n");
-        format!("{}\n\n{}", allow_warnings, module_contents)
+        module_contents
     } else {
         format!("pub mod {} {{\n{}}}\n", package.name, module_contents)
     }
 }
 
-pub fn generate_code(bundle: SchemaBundle) -> String {
+/// Generates the contents of the `generated` module, which is expected to be declared at the
+/// root of the crate.
+pub fn generate_code(bundle: SchemaBundle, options: &CodegenOptions) -> String {
+    let allow_warnings = [
+        "#![allow(unused_imports)]",
+        "#![allow(unreachable_code)]",
+        "#![allow(unreachable_patterns)]",
+        "#![allow(unused_variables)]",
+        "#![allow(dead_code)]",
+        "#![allow(non_camel_case_types)]",
+        "#![allow(unused_mut)]",
+    ]
+    .join("\n");
+    format!("{}\n\n{}", allow_warnings, generate_items(bundle, options))
+}
+
+// Generates the items of the `generated` module without any inner attributes, so that they can be
+// pulled into a module with `include!`.
+pub(crate) fn generate_items(bundle: SchemaBundle, options: &CodegenOptions) -> String {
     // Set up the root package.
     let generated_code = Rc::new(RefCell::new(GeneratedCode {
         root_package: None,
@@ -530,6 +899,8 @@ pub fn generate_code(bundle: SchemaBundle) -> String {
         enums: BTreeMap::new(),
         types: BTreeMap::new(),
         components: BTreeMap::new(),
+        boxed_fields: HashSet::new(),
+        options: options.clone(),
     }));
     let mut root_package = Package::new(Rc::clone(&generated_code), "", vec![]);
     for file in bundle.schema_files {
@@ -565,6 +936,8 @@ pub fn generate_code(bundle: SchemaBundle) -> String {
                 .insert(component_def.qualified_name.clone(), component_def);
         }
     }
+    let boxed_fields = find_recursive_fields(&generated_code.borrow().types);
+    generated_code.borrow_mut().boxed_fields = boxed_fields;
     generated_code.borrow_mut().root_package = Some(root_package);
     //println!("{:#?}", generated_code.borrow_mut().root_package);
     let generated_code_ref = generated_code.borrow();
@@ -9,26 +9,38 @@ extern crate t4rust_derive;
 
 extern crate heck;
 
+mod builder;
 pub mod generator;
 #[allow(non_camel_case_types)]
 pub mod schema_bundle;
+pub mod schema_diff;
+pub mod schema_parser;
+
+pub use builder::Builder;
 
 #[cfg(test)]
 mod tests {
     use generator;
     use schema_bundle;
+    use schema_diff::{self, Addition, BreakingChange};
+    use schema_parser::SchemaParser;
     use std::fs::File;
     use std::io::Read;
+    use Builder;
 
-    #[test]
-    fn deserialize_bundle() {
+    fn load_test_bundle() -> Result<schema_bundle::SchemaBundle, serde_json::Error> {
         let mut file =
             File::open("data/test.sb.json").expect("Unable to open the test schema bundle.");
         let mut contents = String::new();
         file.read_to_string(&mut contents)
             .expect("Unable to read the test schema bundle");
 
-        let bundle = schema_bundle::load_bundle(&contents);
+        schema_bundle::load_bundle(&contents)
+    }
+
+    #[test]
+    fn deserialize_bundle() {
+        let bundle = load_test_bundle();
         assert!(
             bundle.is_ok(),
             "Schema bundle contains an error: {:?}",
@@ -37,7 +49,296 @@ mod tests {
         println!("Bundle contents: {:#?}", bundle);
         println!(
             "Generated code: {}",
-            generator::generate_code(bundle.unwrap())
+            generator::generate_code(bundle.unwrap(), &generator::CodegenOptions::default())
+        );
+    }
+
+    #[test]
+    fn builder_writes_generated_code_to_out_dir() {
+        let out_dir = std::env::temp_dir().join("spatialos-sdk-code-generator-builder-test");
+        let output_path = Builder::new()
+            .bundle("data/test.sb.json")
+            .out_dir(&out_dir)
+            .generate()
+            .unwrap();
+
+        assert_eq!(out_dir.join("generated.rs"), output_path);
+        let mut contents = String::new();
+        File::open(&output_path)
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        // Inner attributes can't be used with `include!`.
+        assert!(!contents.contains("#!["));
+        assert!(contents.contains("pub mod example {"));
+    }
+
+    #[test]
+    fn extra_derives_are_skipped_where_invalid() {
+        let options = generator::CodegenOptions {
+            derives: vec![
+                "Eq".to_string(),
+                "Hash".to_string(),
+                "PartialOrd".to_string(),
+            ],
+            ..Default::default()
+        };
+        let code = generator::generate_code(load_test_bundle().unwrap(), &options);
+
+        assert!(code.contains(
+            "#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd)]\npub struct CommandData"
+        ));
+        assert!(
+            code.contains("#[derive(Debug, Clone, PartialEq, PartialOrd)]\npub struct Vector3d")
+        );
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd)]\npub enum TestEnum"
+        ));
+    }
+
+    #[test]
+    fn mapped_types_replace_generated_field_types() {
+        let mut options = generator::CodegenOptions::default();
+        options
+            .type_mappings
+            .insert("example.Vector3d".to_string(), "glam::DVec3".to_string());
+        let code = generator::generate_code(load_test_bundle().unwrap(), &options);
+
+        assert!(code.contains("pub center: glam::DVec3,"));
+        assert!(code.contains("pub struct Vector3d"));
+    }
+
+    #[test]
+    fn parsed_schema_generates_the_same_code_as_the_compiled_bundle() {
+        let mut parser = SchemaParser::new();
+        parser.add_schema_path("../project-example/schema").unwrap();
+        let bundle = parser.parse().unwrap();
+
+        let options = generator::CodegenOptions::default();
+        assert_eq!(
+            generator::generate_code(load_test_bundle().unwrap(), &options),
+            generator::generate_code(bundle, &options)
+        );
+    }
+
+    #[test]
+    fn parsed_annotations_are_resolved() {
+        let mut parser = SchemaParser::new();
+        parser.add_schema_path("../project-example/schema").unwrap();
+        let bundle = parser.parse().unwrap();
+
+        let test_type = bundle.schema_files[0]
+            .types
+            .iter()
+            .find(|definition| definition.qualified_name == "example.TestType")
+            .unwrap();
+        let annotation = &test_type.fields[0].annotations[0];
+        assert_eq!(
+            "example.TestType.Inner",
+            annotation.type_value.type_reference
+        );
+        assert_eq!("number", annotation.type_value.fields[0].name);
+        match annotation.type_value.fields[0].value.value {
+            schema_bundle::Value_Value::FloatValue(value) => assert_eq!(2.0, value),
+            ref value => panic!("Expected a float value, got {:?}", value),
+        }
+    }
+
+    #[test]
+    fn standard_library_is_parsed() {
+        let mut parser = SchemaParser::new();
+        parser.add_standard_library();
+        parser.add_file(
+            "game/player.schema",
+            "package game;\nimport \"improbable/standard_library.schema\";\n\
+             component Player {\n  id = 100;\n  improbable.Coordinates spawn = 1;\n}\n",
+        );
+        let bundle = parser.parse().unwrap();
+
+        let code = generator::generate_code(bundle, &generator::CodegenOptions::default());
+        assert!(code.contains("pub spawn: generated::improbable::Coordinates,"));
+    }
+
+    #[test]
+    fn parse_errors_report_the_file_and_line() {
+        let mut parser = SchemaParser::new();
+        parser.add_file("a.schema", "package a;\n\ntype A {\n  int32 x = 1\n}\n");
+        let diagnostics = parser.parse().unwrap_err();
+        assert_eq!(
+            "a.schema:5:1: Expected ';'. Found '}'.",
+            diagnostics.to_string()
+        );
+    }
+
+    #[test]
+    fn resolve_errors_report_every_file_and_line() {
+        let mut parser = SchemaParser::new();
+        parser.add_file(
+            "a.schema",
+            "package a;\ncomponent A {\n  id = 1;\n  Missing x = 1;\n}\n",
+        );
+        parser.add_file(
+            "b.schema",
+            "package b;\ncomponent B {\n  id = 1;\n  a.A y = 1;\n}\n",
+        );
+        let diagnostics = parser.parse().unwrap_err();
+        assert_eq!(
+            "a.schema:4:3: Unknown type Missing.\n\
+             b.schema:4:3: a.A is defined in \"a.schema\", which isn't imported.\n\
+             b.schema:2:1: Component ID 1 is already used by a.A.",
+            diagnostics.to_string()
+        );
+    }
+
+    fn parse_schema(contents: &str) -> schema_bundle::SchemaBundle {
+        let mut parser = SchemaParser::new();
+        parser.add_file("game.schema", contents);
+        parser.parse().unwrap()
+    }
+
+    #[test]
+    fn schema_diff_reports_breaking_changes() {
+        let old = parse_schema(
+            "package game;\n\
+             type Request {}\n\
+             type Damage { int32 amount = 1; }\n\
+             enum State { IDLE = 0; RUNNING = 1; }\n\
+             component Health {\n\
+               id = 100;\n\
+               float current = 1;\n\
+               event Damage hit;\n\
+               command Request heal(Request);\n\
+             }\n\
+             component Mover { id = 101; State state = 1; }\n",
+        );
+        let new = parse_schema(
+            "package game;\n\
+             type Request {}\n\
+             type Damage { int32 amount = 1; }\n\
+             enum State { IDLE = 0; RUNNING = 2; }\n\
+             component Health {\n\
+               id = 100;\n\
+               int32 current = 1;\n\
+               event Request hit;\n\
+             }\n\
+             component Stamina { id = 101; State state = 1; }\n",
+        );
+
+        let diff = schema_diff::diff(&old, &new);
+        assert_eq!(
+            vec![
+                BreakingChange::FieldTypeChanged {
+                    owner: "game.Health".to_string(),
+                    field_id: 1,
+                    old_field: "float current".to_string(),
+                    new_field: "int32 current".to_string(),
+                },
+                BreakingChange::EventTypeChanged {
+                    component: "game.Health".to_string(),
+                    event: "hit".to_string(),
+                    old_type: "game.Damage".to_string(),
+                    new_type: "game.Request".to_string(),
+                },
+                BreakingChange::CommandRemoved {
+                    component: "game.Health".to_string(),
+                    command: "heal".to_string(),
+                },
+                BreakingChange::ComponentIdReused {
+                    component_id: 101,
+                    old_component: "game.Mover".to_string(),
+                    new_component: "game.Stamina".to_string(),
+                },
+                BreakingChange::EnumValueRenumbered {
+                    enum_name: "game.State".to_string(),
+                    value: "RUNNING".to_string(),
+                    old_number: 1,
+                    new_number: 2,
+                },
+            ],
+            diff.breaking_changes
+        );
+        assert!(diff.additions.is_empty());
+    }
+
+    #[test]
+    fn schema_diff_lists_safe_additions() {
+        let old = parse_schema(
+            "package game;\n\
+             type Damage { int32 amount = 1; }\n\
+             component Health { id = 100; float current = 1; }\n",
+        );
+        let new = parse_schema(
+            "package game;\n\
+             type Damage { int32 amount = 1; }\n\
+             type Heal { int32 amount = 1; }\n\
+             component Health {\n\
+               id = 100;\n\
+               float current = 1;\n\
+               float max = 2;\n\
+               event Damage hit;\n\
+             }\n\
+             component Stamina { id = 101; float current = 1; }\n",
+        );
+
+        let diff = schema_diff::diff(&old, &new);
+        assert!(!diff.is_breaking());
+        assert_eq!(
+            vec![
+                Addition::Field {
+                    owner: "game.Health".to_string(),
+                    field: "max".to_string(),
+                    field_id: 2,
+                },
+                Addition::Event {
+                    component: "game.Health".to_string(),
+                    event: "hit".to_string(),
+                },
+                Addition::Component {
+                    component: "game.Stamina".to_string(),
+                    component_id: 101,
+                },
+                Addition::Type {
+                    name: "game.Heal".to_string(),
+                },
+            ],
+            diff.additions
+        );
+        assert_eq!(
+            "Field game.Health.max (ID 2)",
+            diff.additions[0].to_string()
+        );
+    }
+
+    #[test]
+    fn schema_diff_reports_data_type_changes_once() {
+        let old = parse_schema(
+            "package game;\n\
+             type HealthData { float current = 1; }\n\
+             component Health { id = 100; data HealthData; }\n",
+        );
+        let new = parse_schema(
+            "package game;\n\
+             type HealthData { int32 current = 1; float max = 2; }\n\
+             component Health { id = 100; data HealthData; }\n",
+        );
+
+        let diff = schema_diff::diff(&old, &new);
+        assert_eq!(
+            vec![BreakingChange::FieldTypeChanged {
+                owner: "game.HealthData".to_string(),
+                field_id: 1,
+                old_field: "float current".to_string(),
+                new_field: "int32 current".to_string(),
+            }],
+            diff.breaking_changes
+        );
+        assert_eq!(
+            vec![Addition::Field {
+                owner: "game.HealthData".to_string(),
+                field: "max".to_string(),
+                field_id: 2,
+            }],
+            diff.additions
         );
     }
 }
@@ -8,7 +8,7 @@ where
     Ok(o.filter(|s| !s.is_empty()))
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SourceReference {
     pub line: u32,
@@ -59,8 +59,8 @@ pub struct Value_TypeValue_FieldValue {
 #[serde(rename_all = "camelCase")]
 pub struct Value_TypeValue {
     #[serde(rename = "type")]
-    type_reference: String,
-    fields: Vec<Value_TypeValue_FieldValue>,
+    pub type_reference: String,
+    pub fields: Vec<Value_TypeValue_FieldValue>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -0,0 +1,560 @@
+//! Compatibility checks between two versions of a schema.
+//!
+//! Components are matched by ID, types and enums by qualified name, fields by field ID, and
+//! events and commands by index, as that's how they're identified in serialized data. A breaking
+//! change is one that makes existing data, such as a persistent snapshot, or workers built
+//! against the old schema incompatible with the new schema.
+
+use schema_bundle::*;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakingChange {
+    ComponentIdReused {
+        component_id: u32,
+        old_component: String,
+        new_component: String,
+    },
+    ComponentRemoved {
+        component: String,
+        component_id: u32,
+    },
+    /// A field ID is used by a field of a different type. The fields are described as they're
+    /// declared, e.g. `list<int32> values`.
+    FieldTypeChanged {
+        owner: String,
+        field_id: u32,
+        old_field: String,
+        new_field: String,
+    },
+    FieldRemoved {
+        owner: String,
+        field: String,
+        field_id: u32,
+    },
+    EventTypeChanged {
+        component: String,
+        event: String,
+        old_type: String,
+        new_type: String,
+    },
+    EventRemoved {
+        component: String,
+        event: String,
+    },
+    CommandTypeChanged {
+        component: String,
+        command: String,
+        old_signature: String,
+        new_signature: String,
+    },
+    CommandRemoved {
+        component: String,
+        command: String,
+    },
+    EnumValueRenumbered {
+        enum_name: String,
+        value: String,
+        old_number: u32,
+        new_number: u32,
+    },
+    EnumValueRemoved {
+        enum_name: String,
+        value: String,
+        number: u32,
+    },
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BreakingChange::ComponentIdReused {
+                component_id,
+                old_component,
+                new_component,
+            } => write!(
+                f,
+                "Component ID {} was used by {}, and is now used by {}",
+                component_id, old_component, new_component
+            ),
+            BreakingChange::ComponentRemoved {
+                component,
+                component_id,
+            } => write!(
+                f,
+                "Component {} (ID {}) was removed",
+                component, component_id
+            ),
+            BreakingChange::FieldTypeChanged {
+                owner,
+                field_id,
+                old_field,
+                new_field,
+            } => write!(
+                f,
+                "Field {} of {} changed from `{}` to `{}`",
+                field_id, owner, old_field, new_field
+            ),
+            BreakingChange::FieldRemoved {
+                owner,
+                field,
+                field_id,
+            } => write!(f, "Field {}.{} (ID {}) was removed", owner, field, field_id),
+            BreakingChange::EventTypeChanged {
+                component,
+                event,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Event {}.{} changed type from {} to {}",
+                component, event, old_type, new_type
+            ),
+            BreakingChange::EventRemoved { component, event } => {
+                write!(f, "Event {}.{} was removed", component, event)
+            }
+            BreakingChange::CommandTypeChanged {
+                component,
+                command,
+                old_signature,
+                new_signature,
+            } => write!(
+                f,
+                "Command {}.{} changed from `{}` to `{}`",
+                component, command, old_signature, new_signature
+            ),
+            BreakingChange::CommandRemoved { component, command } => {
+                write!(f, "Command {}.{} was removed", component, command)
+            }
+            BreakingChange::EnumValueRenumbered {
+                enum_name,
+                value,
+                old_number,
+                new_number,
+            } => write!(
+                f,
+                "Enum value {}.{} changed from {} to {}",
+                enum_name, value, old_number, new_number
+            ),
+            BreakingChange::EnumValueRemoved {
+                enum_name,
+                value,
+                number,
+            } => write!(
+                f,
+                "Enum value {}.{} ({}) was removed",
+                enum_name, value, number
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Addition {
+    Component {
+        component: String,
+        component_id: u32,
+    },
+    Type {
+        name: String,
+    },
+    Enum {
+        name: String,
+    },
+    Field {
+        owner: String,
+        field: String,
+        field_id: u32,
+    },
+    Event {
+        component: String,
+        event: String,
+    },
+    Command {
+        component: String,
+        command: String,
+    },
+    EnumValue {
+        enum_name: String,
+        value: String,
+        number: u32,
+    },
+}
+
+impl fmt::Display for Addition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Addition::Component {
+                component,
+                component_id,
+            } => write!(f, "Component {} (ID {})", component, component_id),
+            Addition::Type { name } => write!(f, "Type {}", name),
+            Addition::Enum { name } => write!(f, "Enum {}", name),
+            Addition::Field {
+                owner,
+                field,
+                field_id,
+            } => write!(f, "Field {}.{} (ID {})", owner, field, field_id),
+            Addition::Event { component, event } => write!(f, "Event {}.{}", component, event),
+            Addition::Command { component, command } => {
+                write!(f, "Command {}.{}", component, command)
+            }
+            Addition::EnumValue {
+                enum_name,
+                value,
+                number,
+            } => write!(f, "Enum value {}.{} ({})", enum_name, value, number),
+        }
+    }
+}
+
+/// The differences between two versions of a schema.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDiff {
+    pub breaking_changes: Vec<BreakingChange>,
+    pub additions: Vec<Addition>,
+}
+
+impl SchemaDiff {
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking_changes.is_empty()
+    }
+}
+
+/// Compares the schema in `old` with the schema in `new`.
+pub fn diff(old: &SchemaBundle, new: &SchemaBundle) -> SchemaDiff {
+    let old = Definitions::new(old);
+    let new = Definitions::new(new);
+    let mut diff = SchemaDiff::default();
+
+    for (component_id, old_component) in &old.components {
+        let new_component = match new.components.get(component_id) {
+            Some(new_component) => new_component,
+            None => {
+                diff.breaking_changes
+                    .push(BreakingChange::ComponentRemoved {
+                        component: old_component.qualified_name.clone(),
+                        component_id: *component_id,
+                    });
+                continue;
+            }
+        };
+
+        if old_component.qualified_name != new_component.qualified_name {
+            diff.breaking_changes
+                .push(BreakingChange::ComponentIdReused {
+                    component_id: *component_id,
+                    old_component: old_component.qualified_name.clone(),
+                    new_component: new_component.qualified_name.clone(),
+                });
+            continue;
+        }
+
+        // A data type that's shared by both versions is diffed with the other types below.
+        let same_data_type = old_component.data_definition.is_some()
+            && old_component.data_definition == new_component.data_definition;
+        if !same_data_type {
+            diff_fields(
+                &old_component.qualified_name,
+                old.component_fields(old_component),
+                new.component_fields(new_component),
+                &mut diff,
+            );
+        }
+        diff_events(old_component, new_component, &mut diff);
+        diff_commands(old_component, new_component, &mut diff);
+    }
+    for (component_id, new_component) in &new.components {
+        if !old.components.contains_key(component_id) {
+            diff.additions.push(Addition::Component {
+                component: new_component.qualified_name.clone(),
+                component_id: *component_id,
+            });
+        }
+    }
+
+    // Removing a type or enum only matters where it's used, which shows up as a changed field.
+    for (name, new_type) in &new.types {
+        match old.types.get(name) {
+            Some(old_type) => diff_fields(name, &old_type.fields, &new_type.fields, &mut diff),
+            None => diff.additions.push(Addition::Type {
+                name: name.to_string(),
+            }),
+        }
+    }
+    for (name, new_enum) in &new.enums {
+        match old.enums.get(name) {
+            Some(old_enum) => diff_enum_values(old_enum, new_enum, &mut diff),
+            None => diff.additions.push(Addition::Enum {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    diff
+}
+
+// The definitions in a bundle, keyed by how they're matched between versions.
+struct Definitions<'a> {
+    components: BTreeMap<u32, &'a ComponentDefinition>,
+    types: BTreeMap<&'a str, &'a TypeDefinition>,
+    enums: BTreeMap<&'a str, &'a EnumDefinition>,
+}
+
+impl<'a> Definitions<'a> {
+    fn new(bundle: &'a SchemaBundle) -> Self {
+        let mut definitions = Definitions {
+            components: BTreeMap::new(),
+            types: BTreeMap::new(),
+            enums: BTreeMap::new(),
+        };
+        for file in &bundle.schema_files {
+            for component in &file.components {
+                definitions
+                    .components
+                    .insert(component.component_id, component);
+            }
+            for type_definition in &file.types {
+                definitions
+                    .types
+                    .insert(&type_definition.qualified_name, type_definition);
+            }
+            for enum_definition in &file.enums {
+                definitions
+                    .enums
+                    .insert(&enum_definition.qualified_name, enum_definition);
+            }
+        }
+        definitions
+    }
+
+    // The fields of a component, which come from its data definition if it has one.
+    fn component_fields(&self, component: &'a ComponentDefinition) -> &'a [FieldDefinition] {
+        match component.data_definition {
+            Some(ref data_definition) => self
+                .types
+                .get(data_definition.as_str())
+                .map_or(&[][..], |data_type| &data_type.fields[..]),
+            None => &component.fields,
+        }
+    }
+}
+
+fn diff_fields(
+    owner: &str,
+    old_fields: &[FieldDefinition],
+    new_fields: &[FieldDefinition],
+    diff: &mut SchemaDiff,
+) {
+    for old_field in old_fields {
+        match new_fields
+            .iter()
+            .find(|field| field.field_id == old_field.field_id)
+        {
+            Some(new_field) => {
+                let old_type = describe_field_type(&old_field.field_type);
+                let new_type = describe_field_type(&new_field.field_type);
+                if old_type != new_type {
+                    diff.breaking_changes
+                        .push(BreakingChange::FieldTypeChanged {
+                            owner: owner.to_string(),
+                            field_id: old_field.field_id,
+                            old_field: format!("{} {}", old_type, old_field.name),
+                            new_field: format!("{} {}", new_type, new_field.name),
+                        });
+                }
+            }
+            None => diff.breaking_changes.push(BreakingChange::FieldRemoved {
+                owner: owner.to_string(),
+                field: old_field.name.clone(),
+                field_id: old_field.field_id,
+            }),
+        }
+    }
+
+    for new_field in new_fields {
+        if !old_fields
+            .iter()
+            .any(|field| field.field_id == new_field.field_id)
+        {
+            diff.additions.push(Addition::Field {
+                owner: owner.to_string(),
+                field: new_field.name.clone(),
+                field_id: new_field.field_id,
+            });
+        }
+    }
+}
+
+fn diff_events(old: &ComponentDefinition, new: &ComponentDefinition, diff: &mut SchemaDiff) {
+    for old_event in &old.events {
+        match new
+            .events
+            .iter()
+            .find(|event| event.event_index == old_event.event_index)
+        {
+            Some(new_event) => {
+                if old_event.type_reference != new_event.type_reference {
+                    diff.breaking_changes
+                        .push(BreakingChange::EventTypeChanged {
+                            component: old.qualified_name.clone(),
+                            event: old_event.name.clone(),
+                            old_type: old_event.type_reference.clone(),
+                            new_type: new_event.type_reference.clone(),
+                        });
+                }
+            }
+            None => diff.breaking_changes.push(BreakingChange::EventRemoved {
+                component: old.qualified_name.clone(),
+                event: old_event.name.clone(),
+            }),
+        }
+    }
+
+    for new_event in &new.events {
+        if !old
+            .events
+            .iter()
+            .any(|event| event.event_index == new_event.event_index)
+        {
+            diff.additions.push(Addition::Event {
+                component: new.qualified_name.clone(),
+                event: new_event.name.clone(),
+            });
+        }
+    }
+}
+
+fn diff_commands(old: &ComponentDefinition, new: &ComponentDefinition, diff: &mut SchemaDiff) {
+    for old_command in &old.commands {
+        match new
+            .commands
+            .iter()
+            .find(|command| command.command_index == old_command.command_index)
+        {
+            Some(new_command) => {
+                if old_command.request_type != new_command.request_type
+                    || old_command.response_type != new_command.response_type
+                {
+                    diff.breaking_changes
+                        .push(BreakingChange::CommandTypeChanged {
+                            component: old.qualified_name.clone(),
+                            command: old_command.name.clone(),
+                            old_signature: describe_command(old_command),
+                            new_signature: describe_command(new_command),
+                        });
+                }
+            }
+            None => diff.breaking_changes.push(BreakingChange::CommandRemoved {
+                component: old.qualified_name.clone(),
+                command: old_command.name.clone(),
+            }),
+        }
+    }
+
+    for new_command in &new.commands {
+        if !old
+            .commands
+            .iter()
+            .any(|command| command.command_index == new_command.command_index)
+        {
+            diff.additions.push(Addition::Command {
+                component: new.qualified_name.clone(),
+                command: new_command.name.clone(),
+            });
+        }
+    }
+}
+
+fn diff_enum_values(old: &EnumDefinition, new: &EnumDefinition, diff: &mut SchemaDiff) {
+    for old_value in &old.values {
+        match new.values.iter().find(|value| value.name == old_value.name) {
+            Some(new_value) => {
+                if old_value.value != new_value.value {
+                    diff.breaking_changes
+                        .push(BreakingChange::EnumValueRenumbered {
+                            enum_name: old.qualified_name.clone(),
+                            value: old_value.name.clone(),
+                            old_number: old_value.value,
+                            new_number: new_value.value,
+                        });
+                }
+            }
+            None => diff
+                .breaking_changes
+                .push(BreakingChange::EnumValueRemoved {
+                    enum_name: old.qualified_name.clone(),
+                    value: old_value.name.clone(),
+                    number: old_value.value,
+                }),
+        }
+    }
+
+    for new_value in &new.values {
+        if !old.values.iter().any(|value| value.name == new_value.name) {
+            diff.additions.push(Addition::EnumValue {
+                enum_name: new.qualified_name.clone(),
+                value: new_value.name.clone(),
+                number: new_value.value,
+            });
+        }
+    }
+}
+
+fn describe_command(command: &ComponentDefinition_CommandDefinition) -> String {
+    format!(
+        "{} {}({})",
+        command.response_type, command.name, command.request_type
+    )
+}
+
+// Describes a field type as it's written in schema, e.g. `map<string, example.Vector3d>`.
+fn describe_field_type(field_type: &FieldDefinition_FieldType) -> String {
+    match field_type {
+        FieldDefinition_FieldType::Singular { type_reference } => {
+            describe_type_reference(type_reference)
+        }
+        FieldDefinition_FieldType::Option { inner_type } => {
+            format!("option<{}>", describe_type_reference(inner_type))
+        }
+        FieldDefinition_FieldType::List { inner_type } => {
+            format!("list<{}>", describe_type_reference(inner_type))
+        }
+        FieldDefinition_FieldType::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "map<{}, {}>",
+            describe_type_reference(key_type),
+            describe_type_reference(value_type)
+        ),
+    }
+}
+
+fn describe_type_reference(type_reference: &TypeReference) -> String {
+    match type_reference {
+        TypeReference::Primitive(primitive_type) => match primitive_type {
+            PrimitiveType::Invalid => "invalid",
+            PrimitiveType::Int32 => "int32",
+            PrimitiveType::Int64 => "int64",
+            PrimitiveType::Uint32 => "uint32",
+            PrimitiveType::Uint64 => "uint64",
+            PrimitiveType::Sint32 => "sint32",
+            PrimitiveType::Sint64 => "sint64",
+            PrimitiveType::Fixed32 => "fixed32",
+            PrimitiveType::Fixed64 => "fixed64",
+            PrimitiveType::Sfixed32 => "sfixed32",
+            PrimitiveType::Sfixed64 => "sfixed64",
+            PrimitiveType::Bool => "bool",
+            PrimitiveType::Float => "float",
+            PrimitiveType::Double => "double",
+            PrimitiveType::String => "string",
+            PrimitiveType::EntityId => "EntityId",
+            PrimitiveType::Bytes => "bytes",
+            PrimitiveType::Entity => "Entity",
+        }
+        .to_string(),
+        TypeReference::Enum(name) | TypeReference::Type(name) => name.clone(),
+    }
+}
@@ -0,0 +1,141 @@
+use schema_bundle::SourceReference;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Identifier(String),
+    // Numbers are kept as text, as their type depends on where they're used.
+    Number(String),
+    String(String),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub source_reference: SourceReference,
+}
+
+// Splits a schema file into tokens, skipping whitespace and comments. Returns the position and a
+// message for the first invalid character or unterminated literal.
+pub fn tokenize(contents: &str) -> Result<Vec<Token>, (SourceReference, String)> {
+    let chars: Vec<char> = contents.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut column = 1;
+
+    // Advances past `count` characters, keeping track of the line and column.
+    macro_rules! advance {
+        ($count:expr) => {
+            for _ in 0..$count {
+                if chars[i] == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += 1;
+                }
+                i += 1;
+            }
+        };
+    }
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).cloned();
+        let source_reference = SourceReference { line, column };
+
+        if c.is_whitespace() {
+            advance!(1);
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                advance!(1);
+            }
+        } else if c == '/' && next == Some('*') {
+            advance!(2);
+            loop {
+                if i + 1 >= chars.len() {
+                    return Err((source_reference, "Unterminated comment.".to_string()));
+                }
+                if chars[i] == '*' && chars[i + 1] == '/' {
+                    advance!(2);
+                    break;
+                }
+                advance!(1);
+            }
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                advance!(1);
+            }
+            tokens.push(Token {
+                kind: TokenKind::Identifier(chars[start..i].iter().collect()),
+                source_reference,
+            });
+        } else if c.is_ascii_digit() || (c == '-' && next.filter(char::is_ascii_digit).is_some()) {
+            let start = i;
+            advance!(1);
+            while i < chars.len() {
+                let c = chars[i];
+                let is_exponent_sign =
+                    (c == '-' || c == '+') && (chars[i - 1] == 'e' || chars[i - 1] == 'E');
+                if c.is_ascii_alphanumeric() || c == '.' || is_exponent_sign {
+                    advance!(1);
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Number(chars[start..i].iter().collect()),
+                source_reference,
+            });
+        } else if c == '"' {
+            advance!(1);
+            let mut value = String::new();
+            loop {
+                match chars.get(i).cloned() {
+                    None | Some('\n') => {
+                        return Err((source_reference, "Unterminated string.".to_string()))
+                    }
+                    Some('"') => {
+                        advance!(1);
+                        break;
+                    }
+                    Some('\\') => {
+                        let escaped = match chars.get(i + 1).cloned() {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some('r') => '\r',
+                            Some(c @ '"') | Some(c @ '\\') | Some(c @ '\'') => c,
+                            _ => {
+                                return Err((
+                                    SourceReference { line, column },
+                                    "Invalid escape sequence.".to_string(),
+                                ))
+                            }
+                        };
+                        value.push(escaped);
+                        advance!(2);
+                    }
+                    Some(c) => {
+                        value.push(c);
+                        advance!(1);
+                    }
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::String(value),
+                source_reference,
+            });
+        } else if "{}()[]<>;=,:.".contains(c) {
+            tokens.push(Token {
+                kind: TokenKind::Symbol(c),
+                source_reference,
+            });
+            advance!(1);
+        } else {
+            return Err((source_reference, format!("Unexpected character '{}'.", c)));
+        }
+    }
+
+    Ok(tokens)
+}
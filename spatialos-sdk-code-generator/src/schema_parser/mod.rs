@@ -0,0 +1,148 @@
+//! A parser for the SpatialOS schema language, which builds a `SchemaBundle` from schema files
+//! without the schema compiler from the SpatialOS SDK.
+//!
+//! The bundle matches the one written by the schema compiler's `--bundle_json_out`, so it can be
+//! used for code generation and reflection. Deployments still need the schema descriptor, which
+//! only the schema compiler produces.
+
+mod lexer;
+mod parser;
+mod resolver;
+
+use schema_bundle::{SchemaBundle, SourceReference};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The standard schema library shipped with the SpatialOS SDK, keyed by canonical path.
+const STANDARD_LIBRARY: &[(&str, &str)] = &[
+    (
+        "improbable/standard_library.schema",
+        include_str!("../../std-lib/improbable/standard_library.schema"),
+    ),
+    (
+        "improbable/restricted/system_components.schema",
+        include_str!("../../std-lib/improbable/restricted/system_components.schema"),
+    ),
+];
+
+/// Parses a set of schema files into a `SchemaBundle`.
+///
+/// ```no_run
+/// # use spatialos_sdk_code_generator::schema_parser::SchemaParser;
+/// let mut parser = SchemaParser::new();
+/// parser.add_standard_library();
+/// parser.add_schema_path("schema").unwrap();
+/// let bundle = parser.parse().unwrap_or_else(|diagnostics| panic!("{}", diagnostics));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaParser {
+    // Schema file contents, keyed by canonical path.
+    files: BTreeMap<String, String>,
+}
+
+impl SchemaParser {
+    pub fn new() -> Self {
+        SchemaParser::default()
+    }
+
+    /// Adds a schema file. The canonical path is the path relative to the schema root, which is
+    /// used by imports, e.g. `improbable/standard_library.schema`. Adding a file with the same
+    /// canonical path again replaces it.
+    pub fn add_file<P: Into<String>, C: Into<String>>(&mut self, canonical_path: P, contents: C) {
+        self.files.insert(canonical_path.into(), contents.into());
+    }
+
+    /// Adds every `.schema` file within a directory, recursively.
+    pub fn add_schema_path<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.add_directory(path.as_ref(), path.as_ref())
+    }
+
+    /// Adds the standard schema library, which defines the `improbable` package.
+    pub fn add_standard_library(&mut self) {
+        for (canonical_path, contents) in STANDARD_LIBRARY {
+            self.add_file(*canonical_path, *contents);
+        }
+    }
+
+    /// Parses every file added, and resolves them into a bundle.
+    pub fn parse(&self) -> Result<SchemaBundle, Diagnostics> {
+        let mut files = Vec::new();
+        let mut diagnostics = Vec::new();
+        for (canonical_path, contents) in &self.files {
+            let parsed = lexer::tokenize(contents).and_then(|tokens| parser::parse(&tokens));
+            match parsed {
+                Ok(file) => files.push((canonical_path.clone(), file)),
+                Err((source_reference, message)) => diagnostics.push(Diagnostic {
+                    path: canonical_path.clone(),
+                    source_reference,
+                    message,
+                }),
+            }
+        }
+
+        if !diagnostics.is_empty() {
+            return Err(Diagnostics(diagnostics));
+        }
+        resolver::resolve(&files).map_err(Diagnostics)
+    }
+
+    fn add_directory(&mut self, root: &Path, directory: &Path) -> io::Result<()> {
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.add_directory(root, &path)?;
+            } else if path.extension() == Some(OsStr::new("schema")) {
+                let relative_path = path.strip_prefix(root).unwrap();
+                let canonical_path = relative_path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                self.add_file(canonical_path, fs::read_to_string(&path)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error in a schema file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The canonical path of the file.
+    pub path: String,
+    pub source_reference: SourceReference,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.path, self.source_reference.line, self.source_reference.column, self.message
+        )
+    }
+}
+
+/// Every error found while parsing a set of schema files.
+#[derive(Debug, Clone)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, diagnostic) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Diagnostics {}
@@ -0,0 +1,578 @@
+use super::lexer::{Token, TokenKind};
+use schema_bundle::SourceReference;
+
+// The syntax tree of a schema file. Names are kept as written, and are resolved to qualified
+// names when the bundle is built.
+
+#[derive(Debug, Clone)]
+pub struct File {
+    // The position of the `package` keyword.
+    pub source_reference: SourceReference,
+    pub package: Name,
+    pub imports: Vec<Import>,
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Import {
+    pub source_reference: SourceReference,
+    pub path: String,
+}
+
+// A possibly qualified name, e.g. `improbable.Coordinates`.
+#[derive(Debug, Clone)]
+pub struct Name {
+    pub source_reference: SourceReference,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Definition {
+    Type(Type),
+    Enum(Enum),
+    Component(Component),
+}
+
+#[derive(Debug, Clone)]
+pub struct Type {
+    pub source_reference: SourceReference,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub fields: Vec<Field>,
+    // Nested types and enums.
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Enum {
+    pub source_reference: SourceReference,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub values: Vec<EnumValue>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumValue {
+    pub source_reference: SourceReference,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub value: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub source_reference: SourceReference,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub id: Option<u32>,
+    pub data_definition: Option<Name>,
+    pub fields: Vec<Field>,
+    pub events: Vec<Event>,
+    pub commands: Vec<Command>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub source_reference: SourceReference,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub field_id: u32,
+    pub field_type: FieldType,
+}
+
+#[derive(Debug, Clone)]
+pub enum FieldType {
+    Singular(Name),
+    Option(Name),
+    List(Name),
+    Map(Name, Name),
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub source_reference: SourceReference,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub type_name: Name,
+}
+
+#[derive(Debug, Clone)]
+pub struct Command {
+    pub source_reference: SourceReference,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub request_type: Name,
+    pub response_type: Name,
+}
+
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub source_reference: SourceReference,
+    pub type_name: Name,
+    pub arguments: Vec<Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Value {
+    pub source_reference: SourceReference,
+    pub kind: ValueKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ValueKind {
+    Bool(bool),
+    Number(String),
+    String(String),
+    // An enum value, either on its own or qualified with the name of the enum.
+    Name(String),
+    List(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Type(Name, Vec<Value>),
+}
+
+pub type ParseError = (SourceReference, String);
+
+pub fn parse(tokens: &[Token]) -> Result<File, ParseError> {
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+    parser.file()
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn file(&mut self) -> Result<File, ParseError> {
+        let source_reference = self.expect_keyword("package")?;
+        let package = self.name()?;
+        self.expect_symbol(';')?;
+
+        let mut imports = Vec::new();
+        while self.peek_keyword("import") {
+            let source_reference = self.next_token()?.source_reference;
+            let path = match self.next_token()?.kind {
+                TokenKind::String(path) => path,
+                _ => return Err(self.error_at_previous("Expected an import path.")),
+            };
+            self.expect_symbol(';')?;
+            imports.push(Import {
+                source_reference,
+                path,
+            });
+        }
+
+        let mut definitions = Vec::new();
+        while !self.at_end() {
+            let annotations = self.annotations()?;
+            if self.peek_keyword("type") {
+                definitions.push(Definition::Type(self.type_definition(annotations)?));
+            } else if self.peek_keyword("enum") {
+                definitions.push(Definition::Enum(self.enum_definition(annotations)?));
+            } else if self.peek_keyword("component") {
+                definitions.push(Definition::Component(
+                    self.component_definition(annotations)?,
+                ));
+            } else if self.peek_keyword("import") {
+                return Err(self.error("Imports must come before any definitions."));
+            } else {
+                return Err(self.error("Expected a type, enum or component definition."));
+            }
+        }
+
+        Ok(File {
+            source_reference,
+            package,
+            imports,
+            definitions,
+        })
+    }
+
+    fn type_definition(&mut self, annotations: Vec<Annotation>) -> Result<Type, ParseError> {
+        let source_reference = self.expect_keyword("type")?;
+        let name = self.identifier()?;
+        self.expect_symbol('{')?;
+
+        let mut fields = Vec::new();
+        let mut definitions = Vec::new();
+        while !self.peek_symbol('}') {
+            let annotations = self.annotations()?;
+            if self.peek_keyword("type") {
+                definitions.push(Definition::Type(self.type_definition(annotations)?));
+            } else if self.peek_keyword("enum") {
+                definitions.push(Definition::Enum(self.enum_definition(annotations)?));
+            } else {
+                fields.push(self.field(annotations)?);
+            }
+        }
+        self.expect_symbol('}')?;
+
+        Ok(Type {
+            source_reference,
+            annotations,
+            name,
+            fields,
+            definitions,
+        })
+    }
+
+    fn enum_definition(&mut self, annotations: Vec<Annotation>) -> Result<Enum, ParseError> {
+        let source_reference = self.expect_keyword("enum")?;
+        let name = self.identifier()?;
+        self.expect_symbol('{')?;
+
+        let mut values = Vec::new();
+        while !self.peek_symbol('}') {
+            let annotations = self.annotations()?;
+            let source_reference = self.current_source_reference();
+            let name = self.identifier()?;
+            self.expect_symbol('=')?;
+            let value = self.integer()?;
+            self.expect_symbol(';')?;
+            values.push(EnumValue {
+                source_reference,
+                annotations,
+                name,
+                value,
+            });
+        }
+        self.expect_symbol('}')?;
+
+        Ok(Enum {
+            source_reference,
+            annotations,
+            name,
+            values,
+        })
+    }
+
+    fn component_definition(
+        &mut self,
+        annotations: Vec<Annotation>,
+    ) -> Result<Component, ParseError> {
+        let source_reference = self.expect_keyword("component")?;
+        let mut component = Component {
+            source_reference,
+            annotations,
+            name: self.identifier()?,
+            id: None,
+            data_definition: None,
+            fields: Vec::new(),
+            events: Vec::new(),
+            commands: Vec::new(),
+        };
+        self.expect_symbol('{')?;
+
+        while !self.peek_symbol('}') {
+            let annotations = self.annotations()?;
+            let source_reference = self.current_source_reference();
+            if self.peek_keyword("id") && self.peek_symbol_at(1, '=') {
+                self.next_token()?;
+                self.expect_symbol('=')?;
+                component.id = Some(self.integer()?);
+                self.expect_symbol(';')?;
+            } else if self.peek_keyword("data") {
+                self.next_token()?;
+                component.data_definition = Some(self.name()?);
+                self.expect_symbol(';')?;
+            } else if self.peek_keyword("event") {
+                self.next_token()?;
+                let type_name = self.name()?;
+                let name = self.identifier()?;
+                self.expect_symbol(';')?;
+                component.events.push(Event {
+                    source_reference,
+                    annotations,
+                    name,
+                    type_name,
+                });
+            } else if self.peek_keyword("command") {
+                self.next_token()?;
+                let response_type = self.name()?;
+                let name = self.identifier()?;
+                self.expect_symbol('(')?;
+                let request_type = self.name()?;
+                self.expect_symbol(')')?;
+                self.expect_symbol(';')?;
+                component.commands.push(Command {
+                    source_reference,
+                    annotations,
+                    name,
+                    request_type,
+                    response_type,
+                });
+            } else {
+                component.fields.push(self.field(annotations)?);
+            }
+        }
+        self.expect_symbol('}')?;
+
+        Ok(component)
+    }
+
+    fn field(&mut self, annotations: Vec<Annotation>) -> Result<Field, ParseError> {
+        let source_reference = self.current_source_reference();
+        let field_type = if self.peek_keyword("option") && self.peek_symbol_at(1, '<') {
+            self.next_token()?;
+            self.expect_symbol('<')?;
+            let inner_type = self.name()?;
+            self.expect_symbol('>')?;
+            FieldType::Option(inner_type)
+        } else if self.peek_keyword("list") && self.peek_symbol_at(1, '<') {
+            self.next_token()?;
+            self.expect_symbol('<')?;
+            let inner_type = self.name()?;
+            self.expect_symbol('>')?;
+            FieldType::List(inner_type)
+        } else if self.peek_keyword("map") && self.peek_symbol_at(1, '<') {
+            self.next_token()?;
+            self.expect_symbol('<')?;
+            let key_type = self.name()?;
+            self.expect_symbol(',')?;
+            let value_type = self.name()?;
+            self.expect_symbol('>')?;
+            FieldType::Map(key_type, value_type)
+        } else {
+            FieldType::Singular(self.name()?)
+        };
+
+        let name = self.identifier()?;
+        self.expect_symbol('=')?;
+        let field_id = self.integer()?;
+        self.expect_symbol(';')?;
+
+        Ok(Field {
+            source_reference,
+            annotations,
+            name,
+            field_id,
+            field_type,
+        })
+    }
+
+    // Parses any number of annotations, e.g. `[Deprecated("Use x instead.")]`.
+    fn annotations(&mut self) -> Result<Vec<Annotation>, ParseError> {
+        let mut annotations = Vec::new();
+        while self.peek_symbol('[') {
+            let source_reference = self.next_token()?.source_reference;
+            let type_name = self.name()?;
+            let arguments = if self.peek_symbol('(') {
+                self.arguments()?
+            } else {
+                Vec::new()
+            };
+            self.expect_symbol(']')?;
+            annotations.push(Annotation {
+                source_reference,
+                type_name,
+                arguments,
+            });
+        }
+        Ok(annotations)
+    }
+
+    fn arguments(&mut self) -> Result<Vec<Value>, ParseError> {
+        self.expect_symbol('(')?;
+        let arguments = self.values_until(')')?;
+        self.expect_symbol(')')?;
+        Ok(arguments)
+    }
+
+    // Parses a comma separated list of values, ending before the `end` symbol.
+    fn values_until(&mut self, end: char) -> Result<Vec<Value>, ParseError> {
+        let mut values = Vec::new();
+        while !self.peek_symbol(end) {
+            values.push(self.value()?);
+            if !self.peek_symbol(end) {
+                self.expect_symbol(',')?;
+            }
+        }
+        Ok(values)
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        let source_reference = self.current_source_reference();
+        let kind = if self.peek_symbol('[') {
+            self.next_token()?;
+            let values = self.values_until(']')?;
+            self.expect_symbol(']')?;
+            ValueKind::List(values)
+        } else if self.peek_symbol('{') {
+            self.next_token()?;
+            let mut entries = Vec::new();
+            while !self.peek_symbol('}') {
+                let key = self.value()?;
+                self.expect_symbol(':')?;
+                let value = self.value()?;
+                entries.push((key, value));
+                if !self.peek_symbol('}') {
+                    self.expect_symbol(',')?;
+                }
+            }
+            self.expect_symbol('}')?;
+            ValueKind::Map(entries)
+        } else {
+            match self.peek().map(|token| token.kind.clone()) {
+                Some(TokenKind::Number(number)) => {
+                    self.next_token()?;
+                    ValueKind::Number(number)
+                }
+                Some(TokenKind::String(value)) => {
+                    self.next_token()?;
+                    ValueKind::String(value)
+                }
+                Some(TokenKind::Identifier(ref keyword)) if keyword == "true" => {
+                    self.next_token()?;
+                    ValueKind::Bool(true)
+                }
+                Some(TokenKind::Identifier(ref keyword)) if keyword == "false" => {
+                    self.next_token()?;
+                    ValueKind::Bool(false)
+                }
+                Some(TokenKind::Identifier(_)) => {
+                    let name = self.name()?;
+                    if self.peek_symbol('(') {
+                        ValueKind::Type(name, self.arguments()?)
+                    } else {
+                        ValueKind::Name(name.name)
+                    }
+                }
+                _ => return Err(self.error("Expected a value.")),
+            }
+        };
+
+        Ok(Value {
+            source_reference,
+            kind,
+        })
+    }
+
+    fn name(&mut self) -> Result<Name, ParseError> {
+        let source_reference = self.current_source_reference();
+        let mut name = self.identifier()?;
+        while self.peek_symbol('.') {
+            self.next_token()?;
+            name.push('.');
+            name.push_str(&self.identifier()?);
+        }
+        Ok(Name {
+            source_reference,
+            name,
+        })
+    }
+
+    fn identifier(&mut self) -> Result<String, ParseError> {
+        match self.peek().map(|token| token.kind.clone()) {
+            Some(TokenKind::Identifier(identifier)) => {
+                self.next_token()?;
+                Ok(identifier)
+            }
+            _ => Err(self.error("Expected an identifier.")),
+        }
+    }
+
+    fn integer(&mut self) -> Result<u32, ParseError> {
+        match self.peek().map(|token| token.kind.clone()) {
+            Some(TokenKind::Number(number)) => match number.parse() {
+                Ok(value) => {
+                    self.next_token()?;
+                    Ok(value)
+                }
+                Err(_) => Err(self.error(&format!("Invalid integer {}.", number))),
+            },
+            _ => Err(self.error("Expected an integer.")),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<SourceReference, ParseError> {
+        if self.peek_keyword(keyword) {
+            Ok(self.next_token()?.source_reference)
+        } else {
+            Err(self.error(&format!("Expected '{}'.", keyword)))
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), ParseError> {
+        if self.peek_symbol(symbol) {
+            self.next_token()?;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{}'.", symbol)))
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Identifier(ref identifier),
+                ..
+            }) => identifier == keyword,
+            _ => false,
+        }
+    }
+
+    fn peek_symbol(&self, symbol: char) -> bool {
+        self.peek_symbol_at(0, symbol)
+    }
+
+    fn peek_symbol_at(&self, offset: usize, symbol: char) -> bool {
+        match self.tokens.get(self.position + offset) {
+            Some(token) => token.kind == TokenKind::Symbol(symbol),
+            None => false,
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    fn next_token(&mut self) -> Result<Token, ParseError> {
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                self.position += 1;
+                Ok(token.clone())
+            }
+            None => Err(self.error("Unexpected end of file.")),
+        }
+    }
+
+    // The position of the next token, or of the end of the last token at the end of the file.
+    fn current_source_reference(&self) -> SourceReference {
+        match self.peek().or_else(|| self.tokens.last()) {
+            Some(token) => token.source_reference.clone(),
+            None => SourceReference { line: 1, column: 1 },
+        }
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        let message = match self.peek() {
+            Some(token) => format!("{} Found {}.", message, describe(&token.kind)),
+            None => format!("{} Found the end of the file.", message),
+        };
+        (self.current_source_reference(), message)
+    }
+
+    fn error_at_previous(&self, message: &str) -> ParseError {
+        let token = &self.tokens[self.position - 1];
+        (
+            token.source_reference.clone(),
+            format!("{} Found {}.", message, describe(&token.kind)),
+        )
+    }
+}
+
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Identifier(identifier) => format!("'{}'", identifier),
+        TokenKind::Number(number) => format!("'{}'", number),
+        TokenKind::String(value) => format!("\"{}\"", value),
+        TokenKind::Symbol(symbol) => format!("'{}'", symbol),
+    }
+}
@@ -0,0 +1,779 @@
+use super::parser::{self, Definition, FieldType, Name, ValueKind};
+use super::Diagnostic;
+use schema_bundle::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+// A definition that a name can resolve to, along with the index of the file it's defined in.
+#[derive(Clone, Copy)]
+enum Symbol<'a> {
+    Type(usize, &'a parser::Type),
+    Enum(usize, &'a parser::Enum),
+    Component(usize, &'a parser::Component),
+}
+
+impl<'a> Symbol<'a> {
+    fn file(&self) -> usize {
+        match *self {
+            Symbol::Type(file, _) | Symbol::Enum(file, _) | Symbol::Component(file, _) => file,
+        }
+    }
+
+    fn source_reference(&self) -> &'a SourceReference {
+        match *self {
+            Symbol::Type(_, definition) => &definition.source_reference,
+            Symbol::Enum(_, definition) => &definition.source_reference,
+            Symbol::Component(_, definition) => &definition.source_reference,
+        }
+    }
+}
+
+// Resolves the names in parsed schema files, and builds a bundle from them. Every error found is
+// returned, rather than only the first one.
+pub fn resolve(files: &[(String, parser::File)]) -> Result<SchemaBundle, Vec<Diagnostic>> {
+    let mut resolver = Resolver {
+        files,
+        symbols: BTreeMap::new(),
+        diagnostics: Vec::new(),
+    };
+
+    for (index, (_, file)) in files.iter().enumerate() {
+        resolver.collect(index, &file.package.name, &file.definitions);
+    }
+
+    let mut schema_files = Vec::new();
+    let mut component_ids = BTreeMap::new();
+    for (index, (canonical_path, file)) in files.iter().enumerate() {
+        let schema_file = resolver.schema_file(index, canonical_path, file);
+        for component in &schema_file.components {
+            if let Some(existing) =
+                component_ids.insert(component.component_id, component.qualified_name.clone())
+            {
+                resolver.error(
+                    index,
+                    &component.source_reference,
+                    format!(
+                        "Component ID {} is already used by {}.",
+                        component.component_id, existing
+                    ),
+                );
+            }
+        }
+        schema_files.push(schema_file);
+    }
+
+    if resolver.diagnostics.is_empty() {
+        Ok(SchemaBundle { schema_files })
+    } else {
+        Err(resolver.diagnostics)
+    }
+}
+
+struct Resolver<'a> {
+    files: &'a [(String, parser::File)],
+    symbols: BTreeMap<String, Symbol<'a>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Resolver<'a> {
+    // Registers the qualified name of every definition in `definitions`, including nested ones.
+    fn collect(&mut self, file: usize, scope: &str, definitions: &'a [Definition]) {
+        for definition in definitions {
+            let (name, symbol) = match definition {
+                Definition::Type(definition) => (&definition.name, Symbol::Type(file, definition)),
+                Definition::Enum(definition) => (&definition.name, Symbol::Enum(file, definition)),
+                Definition::Component(definition) => {
+                    (&definition.name, Symbol::Component(file, definition))
+                }
+            };
+            let qualified_name = qualify(scope, name);
+
+            if let Some(existing) = self.symbols.get(&qualified_name).cloned() {
+                let message = format!(
+                    "{} is already defined at {}:{}:{}.",
+                    qualified_name,
+                    self.files[existing.file()].0,
+                    existing.source_reference().line,
+                    existing.source_reference().column
+                );
+                self.error(file, symbol.source_reference(), message);
+                continue;
+            }
+            self.symbols.insert(qualified_name.clone(), symbol);
+
+            if let Definition::Type(definition) = definition {
+                self.collect(file, &qualified_name, &definition.definitions);
+            }
+        }
+    }
+
+    fn schema_file(
+        &mut self,
+        file: usize,
+        canonical_path: &str,
+        definition: &'a parser::File,
+    ) -> SchemaFile {
+        for import in &definition.imports {
+            if !self.files.iter().any(|(path, _)| *path == import.path) {
+                self.error(
+                    file,
+                    &import.source_reference,
+                    format!("Unknown import \"{}\".", import.path),
+                );
+            }
+        }
+
+        let mut schema_file = SchemaFile {
+            canonical_path: canonical_path.to_string(),
+            package: SchemaFile_Package {
+                source_reference: definition.source_reference.clone(),
+                name: definition.package.name.clone(),
+            },
+            imports: definition
+                .imports
+                .iter()
+                .map(|import| SchemaFile_Import {
+                    source_reference: import.source_reference.clone(),
+                    path: import.path.clone(),
+                })
+                .collect(),
+            enums: Vec::new(),
+            types: Vec::new(),
+            components: Vec::new(),
+        };
+        self.definitions(
+            file,
+            &definition.package.name,
+            None,
+            &definition.definitions,
+            &mut schema_file,
+        );
+        schema_file
+    }
+
+    fn definitions(
+        &mut self,
+        file: usize,
+        scope: &str,
+        outer_type: Option<&str>,
+        definitions: &'a [Definition],
+        schema_file: &mut SchemaFile,
+    ) {
+        for definition in definitions {
+            match definition {
+                Definition::Type(definition) => {
+                    let qualified_name = qualify(scope, &definition.name);
+                    let type_definition = TypeDefinition {
+                        source_reference: definition.source_reference.clone(),
+                        annotations: self.annotations(file, scope, &definition.annotations),
+                        qualified_name: qualified_name.clone(),
+                        name: definition.name.clone(),
+                        outer_type: outer_type.map(str::to_string),
+                        fields: self.fields(file, &qualified_name, &definition.fields),
+                    };
+                    schema_file.types.push(type_definition);
+                    self.definitions(
+                        file,
+                        &qualified_name,
+                        Some(&qualified_name),
+                        &definition.definitions,
+                        schema_file,
+                    );
+                }
+                Definition::Enum(definition) => {
+                    let enum_definition = self.enum_definition(file, scope, outer_type, definition);
+                    schema_file.enums.push(enum_definition);
+                }
+                Definition::Component(definition) => {
+                    if let Some(component) = self.component(file, scope, definition) {
+                        schema_file.components.push(component);
+                    }
+                }
+            }
+        }
+    }
+
+    fn enum_definition(
+        &mut self,
+        file: usize,
+        scope: &str,
+        outer_type: Option<&str>,
+        definition: &'a parser::Enum,
+    ) -> EnumDefinition {
+        let mut names = BTreeSet::new();
+        let mut numbers = BTreeSet::new();
+        let mut values = Vec::new();
+        for value in &definition.values {
+            if !names.insert(&value.name) {
+                self.error(
+                    file,
+                    &value.source_reference,
+                    format!("Duplicate enum value name {}.", value.name),
+                );
+            }
+            if !numbers.insert(value.value) {
+                self.error(
+                    file,
+                    &value.source_reference,
+                    format!("Duplicate enum value {}.", value.value),
+                );
+            }
+            values.push(EnumDefinition_EnumValueDefinition {
+                source_reference: value.source_reference.clone(),
+                annotations: self.annotations(file, scope, &value.annotations),
+                name: value.name.clone(),
+                value: value.value,
+            });
+        }
+
+        EnumDefinition {
+            source_reference: definition.source_reference.clone(),
+            annotations: self.annotations(file, scope, &definition.annotations),
+            qualified_name: qualify(scope, &definition.name),
+            name: definition.name.clone(),
+            outer_type: outer_type.map(str::to_string),
+            values,
+        }
+    }
+
+    fn component(
+        &mut self,
+        file: usize,
+        scope: &str,
+        definition: &'a parser::Component,
+    ) -> Option<ComponentDefinition> {
+        let qualified_name = qualify(scope, &definition.name);
+        let component_id = match definition.id {
+            Some(id) => id,
+            None => {
+                self.error(
+                    file,
+                    &definition.source_reference,
+                    format!("Component {} is missing an ID.", qualified_name),
+                );
+                return None;
+            }
+        };
+
+        let data_definition = match definition.data_definition {
+            Some(ref data_definition) => {
+                if !definition.fields.is_empty() {
+                    self.error(
+                        file,
+                        &data_definition.source_reference,
+                        "A component with a data definition can't have fields.".to_string(),
+                    );
+                }
+                self.user_type(file, scope, data_definition)
+            }
+            None => None,
+        };
+
+        // Events and commands share a namespace with fields.
+        let mut names: BTreeSet<&str> = definition
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect();
+
+        let mut events = Vec::new();
+        for event in &definition.events {
+            if !names.insert(&event.name) {
+                self.error(
+                    file,
+                    &event.source_reference,
+                    format!("Duplicate name {}.", event.name),
+                );
+            }
+            let type_reference = self.user_type(file, scope, &event.type_name);
+            events.push(ComponentDefinition_EventDefinition {
+                source_reference: event.source_reference.clone(),
+                annotations: self.annotations(file, scope, &event.annotations),
+                name: event.name.clone(),
+                type_reference: type_reference.unwrap_or_default(),
+                event_index: events.len() as u32 + 1,
+            });
+        }
+
+        let mut commands = Vec::new();
+        for command in &definition.commands {
+            if !names.insert(&command.name) {
+                self.error(
+                    file,
+                    &command.source_reference,
+                    format!("Duplicate name {}.", command.name),
+                );
+            }
+            let request_type = self.user_type(file, scope, &command.request_type);
+            let response_type = self.user_type(file, scope, &command.response_type);
+            commands.push(ComponentDefinition_CommandDefinition {
+                source_reference: command.source_reference.clone(),
+                annotations: self.annotations(file, scope, &command.annotations),
+                name: command.name.clone(),
+                request_type: request_type.unwrap_or_default(),
+                response_type: response_type.unwrap_or_default(),
+                command_index: commands.len() as u32 + 1,
+            });
+        }
+
+        Some(ComponentDefinition {
+            source_reference: definition.source_reference.clone(),
+            annotations: self.annotations(file, scope, &definition.annotations),
+            qualified_name: qualified_name.clone(),
+            name: definition.name.clone(),
+            component_id,
+            data_definition,
+            fields: self.fields(file, &qualified_name, &definition.fields),
+            events,
+            commands,
+        })
+    }
+
+    // Resolves the fields of the type or component `scope`. Fields with an unknown type are left
+    // out, after reporting the error.
+    fn fields(
+        &mut self,
+        file: usize,
+        scope: &str,
+        fields: &'a [parser::Field],
+    ) -> Vec<FieldDefinition> {
+        let mut names = BTreeSet::new();
+        let mut field_ids = BTreeSet::new();
+        let mut definitions = Vec::new();
+        for field in fields {
+            if !names.insert(&field.name) {
+                self.error(
+                    file,
+                    &field.source_reference,
+                    format!("Duplicate field name {}.", field.name),
+                );
+            }
+            if !field_ids.insert(field.field_id) {
+                self.error(
+                    file,
+                    &field.source_reference,
+                    format!("Duplicate field ID {}.", field.field_id),
+                );
+            }
+
+            let field_type = self.field_type(file, scope, &field.field_type);
+            let annotations = self.annotations(file, scope, &field.annotations);
+            if let Some(field_type) = field_type {
+                definitions.push(FieldDefinition {
+                    source_reference: field.source_reference.clone(),
+                    annotations,
+                    name: field.name.clone(),
+                    field_id: field.field_id,
+                    transient: false,
+                    field_type,
+                });
+            }
+        }
+        definitions
+    }
+
+    fn field_type(
+        &mut self,
+        file: usize,
+        scope: &str,
+        field_type: &FieldType,
+    ) -> Option<FieldDefinition_FieldType> {
+        Some(match field_type {
+            FieldType::Singular(name) => FieldDefinition_FieldType::Singular {
+                type_reference: self.type_reference(file, scope, name)?,
+            },
+            FieldType::Option(name) => FieldDefinition_FieldType::Option {
+                inner_type: self.type_reference(file, scope, name)?,
+            },
+            FieldType::List(name) => FieldDefinition_FieldType::List {
+                inner_type: self.type_reference(file, scope, name)?,
+            },
+            FieldType::Map(key, value) => {
+                let key_type = self.type_reference(file, scope, key);
+                let value_type = self.type_reference(file, scope, value);
+                FieldDefinition_FieldType::Map {
+                    key_type: key_type?,
+                    value_type: value_type?,
+                }
+            }
+        })
+    }
+
+    fn type_reference(&mut self, file: usize, scope: &str, name: &Name) -> Option<TypeReference> {
+        if let Some(primitive_type) = primitive_type(&name.name) {
+            return Some(TypeReference::Primitive(primitive_type));
+        }
+        match self.resolve(file, scope, name)? {
+            (qualified_name, Symbol::Type(..)) => Some(TypeReference::Type(qualified_name)),
+            (qualified_name, Symbol::Enum(..)) => Some(TypeReference::Enum(qualified_name)),
+            (qualified_name, Symbol::Component(..)) => {
+                self.error(
+                    file,
+                    &name.source_reference,
+                    format!("Component {} can't be used as a type.", qualified_name),
+                );
+                None
+            }
+        }
+    }
+
+    // Resolves the name of a type used as event, command, data or annotation type, which can't be
+    // an enum or a primitive type.
+    fn user_type(&mut self, file: usize, scope: &str, name: &Name) -> Option<String> {
+        match self.type_reference(file, scope, name)? {
+            TypeReference::Type(qualified_name) => Some(qualified_name),
+            _ => {
+                self.error(
+                    file,
+                    &name.source_reference,
+                    format!("{} must be a type.", name.name),
+                );
+                None
+            }
+        }
+    }
+
+    // Resolves a name relative to `scope`, trying each enclosing scope from the innermost out.
+    // Definitions from other files are only visible if that file is imported.
+    fn resolve(&mut self, file: usize, scope: &str, name: &Name) -> Option<(String, Symbol<'a>)> {
+        let mut scope = Some(scope);
+        while let Some(current) = scope {
+            let qualified_name = qualify(current, &name.name);
+            if let Some(symbol) = self.symbols.get(&qualified_name).cloned() {
+                let defining_file = &self.files[symbol.file()].0;
+                let visible = symbol.file() == file
+                    || self.files[file]
+                        .1
+                        .imports
+                        .iter()
+                        .any(|import| import.path == *defining_file);
+                if !visible {
+                    let message = format!(
+                        "{} is defined in \"{}\", which isn't imported.",
+                        qualified_name, defining_file
+                    );
+                    self.error(file, &name.source_reference, message);
+                    return None;
+                }
+                return Some((qualified_name, symbol));
+            }
+            scope = if current.is_empty() {
+                None
+            } else {
+                Some(current.rfind('.').map_or("", |index| &current[..index]))
+            };
+        }
+
+        self.error(
+            file,
+            &name.source_reference,
+            format!("Unknown type {}.", name.name),
+        );
+        None
+    }
+
+    fn annotations(
+        &mut self,
+        file: usize,
+        scope: &str,
+        annotations: &'a [parser::Annotation],
+    ) -> Vec<Annotation> {
+        annotations
+            .iter()
+            .filter_map(|annotation| {
+                let type_value = self.type_value(
+                    file,
+                    scope,
+                    &annotation.source_reference,
+                    &annotation.type_name,
+                    &annotation.arguments,
+                )?;
+                Some(Annotation {
+                    source_reference: annotation.source_reference.clone(),
+                    type_value,
+                })
+            })
+            .collect()
+    }
+
+    // Builds a value of the type `type_name` from positional arguments, one for each field.
+    fn type_value(
+        &mut self,
+        file: usize,
+        scope: &str,
+        source_reference: &SourceReference,
+        type_name: &Name,
+        arguments: &'a [parser::Value],
+    ) -> Option<Value_TypeValue> {
+        let qualified_name = self.user_type(file, scope, type_name)?;
+        let (type_file, definition) = match self.symbols[&qualified_name] {
+            Symbol::Type(type_file, definition) => (type_file, definition),
+            _ => unreachable!(),
+        };
+
+        if arguments.len() != definition.fields.len() {
+            self.error(
+                file,
+                source_reference,
+                format!(
+                    "{} has {} fields, but {} values were given.",
+                    qualified_name,
+                    definition.fields.len(),
+                    arguments.len()
+                ),
+            );
+            return None;
+        }
+
+        let mut fields = Vec::new();
+        for (field, argument) in definition.fields.iter().zip(arguments) {
+            let field_type = self.field_type(type_file, &qualified_name, &field.field_type)?;
+            fields.push(Value_TypeValue_FieldValue {
+                source_reference: argument.source_reference.clone(),
+                name: field.name.clone(),
+                value: self.field_value(file, scope, &field_type, argument)?,
+            });
+        }
+
+        Some(Value_TypeValue {
+            type_reference: qualified_name,
+            fields,
+        })
+    }
+
+    fn field_value(
+        &mut self,
+        file: usize,
+        scope: &str,
+        field_type: &FieldDefinition_FieldType,
+        value: &'a parser::Value,
+    ) -> Option<Value> {
+        let converted = match (field_type, &value.kind) {
+            (FieldDefinition_FieldType::Singular { type_reference }, _) => {
+                return self.value(file, scope, type_reference, value);
+            }
+            (FieldDefinition_FieldType::Option { inner_type }, _) => Value_Value::OptionValue {
+                value: Box::new(self.value(file, scope, inner_type, value)?),
+            },
+            (FieldDefinition_FieldType::List { inner_type }, ValueKind::List(values)) => {
+                let values = values
+                    .iter()
+                    .map(|value| self.value(file, scope, inner_type, value))
+                    .collect::<Vec<_>>();
+                Value_Value::ListValue {
+                    values: values.into_iter().collect::<Option<_>>()?,
+                }
+            }
+            (
+                FieldDefinition_FieldType::Map {
+                    key_type,
+                    value_type,
+                },
+                ValueKind::Map(entries),
+            ) => {
+                let values = entries
+                    .iter()
+                    .map(|(key, value)| {
+                        let key = self.value(file, scope, key_type, key);
+                        let value = self.value(file, scope, value_type, value);
+                        Some(Value_MapValue_KeyValuePair {
+                            key: key?,
+                            value: value?,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                Value_Value::MapValue {
+                    values: values.into_iter().collect::<Option<_>>()?,
+                }
+            }
+            (FieldDefinition_FieldType::List { .. }, _) => {
+                self.error(
+                    file,
+                    &value.source_reference,
+                    "Expected a list.".to_string(),
+                );
+                return None;
+            }
+            (FieldDefinition_FieldType::Map { .. }, _) => {
+                self.error(file, &value.source_reference, "Expected a map.".to_string());
+                return None;
+            }
+        };
+
+        Some(Value {
+            source_reference: value.source_reference.clone(),
+            value: converted,
+        })
+    }
+
+    fn value(
+        &mut self,
+        file: usize,
+        scope: &str,
+        type_reference: &TypeReference,
+        value: &'a parser::Value,
+    ) -> Option<Value> {
+        let converted = match (type_reference, &value.kind) {
+            (TypeReference::Primitive(primitive_type), _) => {
+                self.primitive_value(file, primitive_type, value)?
+            }
+            (TypeReference::Enum(qualified_name), ValueKind::Name(name)) => {
+                // The value may be qualified with the name of the enum.
+                let value_name = name.rsplit('.').next().unwrap_or(name);
+                let known = match self.symbols.get(qualified_name) {
+                    Some(Symbol::Enum(_, definition)) => definition
+                        .values
+                        .iter()
+                        .any(|value| value.name == value_name),
+                    _ => false,
+                };
+                if !known {
+                    self.error(
+                        file,
+                        &value.source_reference,
+                        format!("{} is not a value of {}.", name, qualified_name),
+                    );
+                    return None;
+                }
+                Value_Value::EnumValue {
+                    enum_reference: qualified_name.clone(),
+                    value: value_name.to_string(),
+                }
+            }
+            (TypeReference::Type(qualified_name), ValueKind::Type(type_name, arguments)) => {
+                let type_value =
+                    self.type_value(file, scope, &value.source_reference, type_name, arguments)?;
+                if type_value.type_reference != *qualified_name {
+                    self.error(
+                        file,
+                        &value.source_reference,
+                        format!(
+                            "Expected a value of type {}, but found {}.",
+                            qualified_name, type_value.type_reference
+                        ),
+                    );
+                    return None;
+                }
+                Value_Value::TypeValue {
+                    type_reference: type_value.type_reference,
+                    fields: type_value.fields,
+                }
+            }
+            (TypeReference::Enum(qualified_name), _) | (TypeReference::Type(qualified_name), _) => {
+                self.error(
+                    file,
+                    &value.source_reference,
+                    format!("Expected a value of type {}.", qualified_name),
+                );
+                return None;
+            }
+        };
+
+        Some(Value {
+            source_reference: value.source_reference.clone(),
+            value: converted,
+        })
+    }
+
+    fn primitive_value(
+        &mut self,
+        file: usize,
+        primitive_type: &PrimitiveType,
+        value: &parser::Value,
+    ) -> Option<Value_Value> {
+        let converted = match (primitive_type, &value.kind) {
+            (PrimitiveType::Int32, ValueKind::Number(number))
+            | (PrimitiveType::Sint32, ValueKind::Number(number))
+            | (PrimitiveType::Sfixed32, ValueKind::Number(number)) => {
+                parse_number(number).map(Value_Value::Int32Value)
+            }
+            (PrimitiveType::Int64, ValueKind::Number(number))
+            | (PrimitiveType::Sint64, ValueKind::Number(number))
+            | (PrimitiveType::Sfixed64, ValueKind::Number(number)) => {
+                parse_number(number).map(Value_Value::Int64Value)
+            }
+            (PrimitiveType::Uint32, ValueKind::Number(number))
+            | (PrimitiveType::Fixed32, ValueKind::Number(number)) => {
+                parse_number(number).map(Value_Value::Uint32Value)
+            }
+            (PrimitiveType::Uint64, ValueKind::Number(number))
+            | (PrimitiveType::Fixed64, ValueKind::Number(number)) => {
+                parse_number(number).map(Value_Value::Uint64Value)
+            }
+            (PrimitiveType::Float, ValueKind::Number(number)) => {
+                parse_number(number).map(Value_Value::FloatValue)
+            }
+            (PrimitiveType::Double, ValueKind::Number(number)) => {
+                parse_number(number).map(Value_Value::DoubleValue)
+            }
+            (PrimitiveType::EntityId, ValueKind::Number(number)) => {
+                parse_number(number).map(Value_Value::EntityIdValue)
+            }
+            (PrimitiveType::Bool, ValueKind::Bool(value)) => Some(Value_Value::BoolValue(*value)),
+            (PrimitiveType::String, ValueKind::String(value)) => {
+                Some(Value_Value::StringValue(value.clone()))
+            }
+            (PrimitiveType::Bytes, ValueKind::String(value)) => {
+                Some(Value_Value::BytesValue(value.clone()))
+            }
+            _ => None,
+        };
+
+        if converted.is_none() {
+            self.error(
+                file,
+                &value.source_reference,
+                format!("Expected a value of type {:?}.", primitive_type),
+            );
+        }
+        converted
+    }
+
+    fn error(&mut self, file: usize, source_reference: &SourceReference, message: String) {
+        let diagnostic = Diagnostic {
+            path: self.files[file].0.clone(),
+            source_reference: source_reference.clone(),
+            message,
+        };
+        // The fields of annotation types are resolved for every use, which can report the same
+        // error more than once.
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+fn parse_number<T: FromStr>(number: &str) -> Option<T> {
+    number.parse().ok()
+}
+
+fn primitive_type(name: &str) -> Option<PrimitiveType> {
+    Some(match name {
+        "int32" => PrimitiveType::Int32,
+        "int64" => PrimitiveType::Int64,
+        "uint32" => PrimitiveType::Uint32,
+        "uint64" => PrimitiveType::Uint64,
+        "sint32" => PrimitiveType::Sint32,
+        "sint64" => PrimitiveType::Sint64,
+        "fixed32" => PrimitiveType::Fixed32,
+        "fixed64" => PrimitiveType::Fixed64,
+        "sfixed32" => PrimitiveType::Sfixed32,
+        "sfixed64" => PrimitiveType::Sfixed64,
+        "bool" => PrimitiveType::Bool,
+        "float" => PrimitiveType::Float,
+        "double" => PrimitiveType::Double,
+        "string" => PrimitiveType::String,
+        "EntityId" => PrimitiveType::EntityId,
+        "bytes" => PrimitiveType::Bytes,
+        "Entity" => PrimitiveType::Entity,
+        _ => return None,
+    })
+}
@@ -0,0 +1,165 @@
+use crate::worker::{
+    component::ComponentId, connection::ConnectionStatusCode, internal::schema::FieldId,
+    snapshot::SnapshotError,
+};
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The errors that can be returned by the SDK and by generated code.
+///
+/// Errors that occur while converting to or from schema data are annotated with the
+/// component and field that was being converted, where they are known.
+#[derive(Debug)]
+pub enum Error {
+    /// Schema data couldn't be decoded as the expected type.
+    SchemaDecode {
+        component_id: Option<ComponentId>,
+        field_id: Option<FieldId>,
+        /// The fully qualified schema name of the type that was being decoded.
+        expected_type: String,
+        message: String,
+    },
+
+    /// A value couldn't be encoded as schema data.
+    SchemaEncode {
+        component_id: Option<ComponentId>,
+        message: String,
+    },
+
+    /// No vtable has been registered for the component, which usually means that the
+    /// generated code for its schema hasn't been linked into the worker.
+    MissingVtable(ComponentId),
+
+    /// The component has already been added to the entity.
+    DuplicateComponent(ComponentId),
+
+    /// The connection to SpatialOS failed.
+    Connection {
+        code: ConnectionStatusCode,
+        detail: String,
+    },
+
+    /// A future was polled after it had already completed.
+    FutureConsumed,
+
+    /// A value received from the C API doesn't correspond to any variant of the SDK type,
+    /// which usually means that the runtime is newer than this SDK.
+    UnknownValue { type_name: &'static str, value: u32 },
+
+    /// Reading or writing a snapshot failed.
+    Snapshot(SnapshotError),
+
+    /// A schema bundle couldn't be loaded.
+    SchemaBundle(String),
+}
+
+impl Error {
+    /// Creates a decode error for a value of the schema type `expected_type`.
+    pub fn decode<T: Into<String>, M: Into<String>>(expected_type: T, message: M) -> Self {
+        Error::SchemaDecode {
+            component_id: None,
+            field_id: None,
+            expected_type: expected_type.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an encode error.
+    pub fn encode<M: Into<String>>(message: M) -> Self {
+        Error::SchemaEncode {
+            component_id: None,
+            message: message.into(),
+        }
+    }
+
+    /// Records the component that was being converted when a schema error occurred.
+    ///
+    /// Errors that already have a component ID, such as those from a component nested
+    /// inside an `Entity` field, keep the innermost component.
+    pub fn in_component(mut self, id: ComponentId) -> Self {
+        match &mut self {
+            Error::SchemaDecode { component_id, .. } | Error::SchemaEncode { component_id, .. } => {
+                component_id.get_or_insert(id);
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Records the field that was being decoded when a decode error occurred.
+    ///
+    /// Errors that already have a field ID keep the innermost field, which is the
+    /// field of `expected_type` that failed to decode.
+    pub fn in_field(mut self, id: FieldId) -> Self {
+        if let Error::SchemaDecode { field_id, .. } = &mut self {
+            field_id.get_or_insert(id);
+        }
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::SchemaDecode {
+                component_id,
+                field_id,
+                expected_type,
+                message,
+            } => {
+                write!(f, "Failed to decode {}", expected_type)?;
+                if let Some(field_id) = field_id {
+                    write!(f, " field {}", field_id)?;
+                }
+                if let Some(component_id) = component_id {
+                    write!(f, " in component {}", component_id)?;
+                }
+                write!(f, ": {}", message)
+            }
+            Error::SchemaEncode {
+                component_id,
+                message,
+            } => {
+                write!(f, "Failed to encode schema data")?;
+                if let Some(component_id) = component_id {
+                    write!(f, " for component {}", component_id)?;
+                }
+                write!(f, ": {}", message)
+            }
+            Error::MissingVtable(id) => write!(
+                f,
+                "Could not find a vtable implementation for component {}",
+                id
+            ),
+            Error::DuplicateComponent(id) => {
+                write!(f, "Duplicate component with ID {} added to `Entity`", id)
+            }
+            Error::Connection { code, detail } => {
+                write!(f, "Connection failed with {:?}: {}", code, detail)
+            }
+            Error::FutureConsumed => write!(f, "The future has already been consumed"),
+            Error::UnknownValue { type_name, value } => {
+                write!(f, "Unknown value {} for {}", value, type_name)
+            }
+            Error::Snapshot(error) => error.fmt(f),
+            Error::SchemaBundle(message) => write!(f, "Failed to load schema bundle: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Snapshot(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<SnapshotError> for Error {
+    fn from(error: SnapshotError) -> Self {
+        Error::Snapshot(error)
+    }
+}
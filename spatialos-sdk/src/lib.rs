@@ -2,5 +2,37 @@
 
 extern crate spatialos_sdk_sys;
 
+mod error;
 pub(crate) mod ptr;
 pub mod worker;
+
+pub use crate::error::Error;
+
+// Re-export serde so generated code with serde support doesn't require the user to add serde to
+// their Cargo.toml.
+#[cfg(feature = "serde")]
+pub use serde;
+
+/// Declares the `generated` module from code written into `OUT_DIR` by
+/// `spatialos_sdk_code_generator::Builder` in a build script.
+///
+/// This must be invoked at the root of the crate, as the generated code refers to its own types
+/// through `crate::generated`. The file name defaults to `generated.rs`.
+#[macro_export]
+macro_rules! include_generated {
+    () => {
+        $crate::include_generated!("generated.rs");
+    };
+    ($file_name:expr) => {
+        #[allow(unused_imports)]
+        #[allow(unreachable_code)]
+        #[allow(unreachable_patterns)]
+        #[allow(unused_variables)]
+        #[allow(dead_code)]
+        #[allow(non_camel_case_types)]
+        #[allow(unused_mut)]
+        pub mod generated {
+            include!(concat!(env!("OUT_DIR"), "/", $file_name));
+        }
+    };
+}
@@ -0,0 +1,196 @@
+use crate::worker::{
+    component::{Command, CommandIndex, Component, ComponentId},
+    connection::Connection,
+    op::{CommandRequestOp, OpList, WorkerOp},
+    EntityId,
+};
+use std::collections::HashMap;
+
+type Handler<T> = Box<dyn FnMut(&mut T, &CommandRequestOp)>;
+
+/// Routes incoming command requests to handlers registered per component or per command.
+///
+/// Each handler receives the typed command request and returns either the response to
+/// send back to the caller, or an error message which is sent as a command failure.
+/// Handlers registered for a specific command take priority over a handler registered
+/// for the whole component. Command requests without a matching handler are failed
+/// immediately, rather than being left to time out on the caller's side.
+///
+/// # Examples
+///
+/// ```no_run
+/// use spatialos_sdk::worker::{
+///     command_handlers::CommandHandlers,
+///     connection::{Connection, WorkerConnection},
+/// };
+/// # use spatialos_sdk::worker::component::Component;
+/// # fn get_connection() -> WorkerConnection { unimplemented!() }
+/// # fn register<C: Component>(handlers: &mut CommandHandlers<WorkerConnection>)
+/// # where C::CommandResponse: Default {
+///
+/// let mut connection = get_connection();
+/// let mut handlers = CommandHandlers::new();
+///
+/// handlers.register::<C, _>(|entity_id, request| {
+///     // Handle the request.
+///     Ok(Default::default())
+/// });
+///
+/// loop {
+///     let ops = connection.get_op_list(0);
+///     handlers.process_op_list(&mut connection, &ops);
+/// }
+/// # }
+/// ```
+pub struct CommandHandlers<T: Connection> {
+    handlers: HashMap<ComponentId, Handler<T>>,
+    command_handlers: HashMap<(ComponentId, CommandIndex), Handler<T>>,
+}
+
+impl<T: Connection> CommandHandlers<T> {
+    pub fn new() -> Self {
+        CommandHandlers {
+            handlers: HashMap::new(),
+            command_handlers: HashMap::new(),
+        }
+    }
+
+    /// Registers the handler for command requests on component `C`, replacing any
+    /// previously registered handler for that component.
+    pub fn register<C, F>(&mut self, mut handler: F)
+    where
+        C: Component,
+        F: FnMut(EntityId, &C::CommandRequest) -> Result<C::CommandResponse, String> + 'static,
+    {
+        let handler = move |connection: &mut T, op: &CommandRequestOp| {
+            let request = match op.get::<C>() {
+                Some(request) => request,
+                None => {
+                    send_failure(
+                        connection,
+                        op,
+                        &format!("Failed to read command request for component {}", C::ID),
+                    );
+                    return;
+                }
+            };
+
+            match handler(op.entity_id, request) {
+                Ok(response) => connection.send_command_response::<C>(op.request_id, response),
+                Err(message) => send_failure(connection, op, &message),
+            }
+        };
+
+        self.handlers.insert(C::ID, Box::new(handler));
+    }
+
+    /// Registers the handler for requests for command `Cmd`, replacing any previously
+    /// registered handler for that command.
+    pub fn register_command<Cmd, F>(&mut self, mut handler: F)
+    where
+        Cmd: Command,
+        F: FnMut(EntityId, &Cmd::Request) -> Result<Cmd::Response, String> + 'static,
+    {
+        let handler = move |connection: &mut T, op: &CommandRequestOp| {
+            let request = match op.get_command::<Cmd>() {
+                Some(request) => request,
+                None => {
+                    send_failure(
+                        connection,
+                        op,
+                        &format!(
+                            "Failed to read request for command {} of component {}",
+                            Cmd::COMMAND_INDEX,
+                            <Cmd::Component as Component>::ID
+                        ),
+                    );
+                    return;
+                }
+            };
+
+            match handler(op.entity_id, request) {
+                Ok(response) => connection.send_response::<Cmd>(op.request_id, response),
+                Err(message) => send_failure(connection, op, &message),
+            }
+        };
+
+        let key = (<Cmd::Component as Component>::ID, Cmd::COMMAND_INDEX);
+        self.command_handlers.insert(key, Box::new(handler));
+    }
+
+    /// Removes the handler for command requests on component `C`, returning `true` if
+    /// one was registered.
+    pub fn unregister<C: Component>(&mut self) -> bool {
+        self.handlers.remove(&C::ID).is_some()
+    }
+
+    /// Removes the handler for requests for command `Cmd`, returning `true` if one was
+    /// registered.
+    pub fn unregister_command<Cmd: Command>(&mut self) -> bool {
+        let key = (<Cmd::Component as Component>::ID, Cmd::COMMAND_INDEX);
+        self.command_handlers.remove(&key).is_some()
+    }
+
+    /// Returns `true` if there is a handler for any command requests on the component.
+    pub fn is_registered(&self, component_id: ComponentId) -> bool {
+        self.handlers.contains_key(&component_id)
+            || self
+                .command_handlers
+                .keys()
+                .any(|&(id, _)| id == component_id)
+    }
+
+    /// Handles all of the command requests in the op list.
+    pub fn process_op_list(&mut self, connection: &mut T, op_list: &OpList) {
+        for op in op_list {
+            self.process_op(connection, &op);
+        }
+    }
+
+    /// Handles the op if it is a command request, sending either the response or a
+    /// command failure through the connection.
+    ///
+    /// Returns `true` if the op was a command request.
+    pub fn process_op(&mut self, connection: &mut T, op: &WorkerOp) -> bool {
+        let op = match op {
+            WorkerOp::CommandRequest(op) => op,
+            _ => return false,
+        };
+
+        let key = (op.component_id, op.command_index());
+        let handler = match self.command_handlers.get_mut(&key) {
+            Some(handler) => Some(handler),
+            None => self.handlers.get_mut(&op.component_id),
+        };
+
+        match handler {
+            Some(handler) => handler(connection, op),
+            None => send_failure(
+                connection,
+                op,
+                &format!(
+                    "No command handler registered for command {} of component {}",
+                    op.command_index(),
+                    op.component_id
+                ),
+            ),
+        }
+
+        true
+    }
+}
+
+impl<T: Connection> Default for CommandHandlers<T> {
+    fn default() -> Self {
+        CommandHandlers::new()
+    }
+}
+
+fn send_failure<T: Connection>(connection: &mut T, op: &CommandRequestOp, message: &str) {
+    // Nul bytes can't be passed to the C API, so strip them rather than dropping the
+    // failure entirely and leaving the caller to time out.
+    let message = message.replace('\0', "");
+    connection
+        .send_command_failure(op.request_id, &message)
+        .expect("Command failure message contains no nul bytes");
+}
@@ -1,6 +1,6 @@
-use crate::worker::internal::schema;
+use crate::{worker::internal::schema, Error};
 use spatialos_sdk_sys::worker::*;
-use std::{collections::hash_map::HashMap, mem, os::raw, ptr, sync::Arc};
+use std::{any::Any, collections::hash_map::HashMap, mem, os::raw, ptr, sync::Arc};
 
 // Re-export inventory so generated code doesn't require the user to add inventory to their
 // Cargo.toml
@@ -15,6 +15,24 @@ pub trait ComponentUpdate<C: Component> {
 
 pub trait ComponentData<C: Component> {
     fn merge(&mut self, update: C::Update);
+
+    /// Returns an update that sets each field that differs between `self` and `new` to its
+    /// value in `new`, or `None` if the two are equal. Merging the update into `self` makes
+    /// it equal to `new`.
+    fn diff(&self, new: &Self) -> Option<C::Update>;
+}
+
+/// Deserializes a field of a generated component update.
+///
+/// Any value that's present, including `null`, sets the field, so that e.g. an option field
+/// can be cleared. Missing fields are left as `None` through `#[serde(default)]`.
+#[cfg(feature = "serde")]
+pub fn deserialize_update_field<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 // A trait that's implemented by a type to convert to/from schema objects.
@@ -22,47 +40,70 @@ pub trait TypeConversion
 where
     Self: std::marker::Sized,
 {
-    fn from_type(input: &schema::SchemaObject) -> Result<Self, String>;
-    fn to_type(input: &Self, output: &mut schema::SchemaObject) -> Result<(), String>;
+    fn from_type(input: &schema::SchemaObject) -> Result<Self, Error>;
+    fn to_type(input: &Self, output: &mut schema::SchemaObject) -> Result<(), Error>;
 }
 
 // A trait that's implemented by a component to convert to/from schema handle types.
 pub trait Component
 where
-    Self: std::marker::Sized,
+    Self: std::marker::Sized + Send + Sync + 'static,
 {
-    type Update;
-    type CommandRequest;
-    type CommandResponse;
+    type Update: Send + Sync + 'static;
+    type CommandRequest: Send + Sync + 'static;
+    type CommandResponse: Send + Sync + 'static;
 
     const ID: ComponentId;
 
-    fn from_data(data: &schema::SchemaComponentData) -> Result<Self, String>;
-    fn from_update(update: &schema::SchemaComponentUpdate) -> Result<Self::Update, String>;
+    fn from_data(data: &schema::SchemaComponentData) -> Result<Self, Error>;
+    fn from_update(update: &schema::SchemaComponentUpdate) -> Result<Self::Update, Error>;
     fn from_request(
         command_index: CommandIndex,
         request: &schema::SchemaCommandRequest,
-    ) -> Result<Self::CommandRequest, String>;
+    ) -> Result<Self::CommandRequest, Error>;
     fn from_response(
         command_index: CommandIndex,
         response: &schema::SchemaCommandResponse,
-    ) -> Result<Self::CommandResponse, String>;
+    ) -> Result<Self::CommandResponse, Error>;
 
-    fn to_data(data: &Self) -> Result<schema::SchemaComponentData, String>;
-    fn to_update(update: &Self::Update) -> Result<schema::SchemaComponentUpdate, String>;
-    fn to_request(request: &Self::CommandRequest) -> Result<schema::SchemaCommandRequest, String>;
+    fn to_data(data: &Self) -> Result<schema::SchemaComponentData, Error>;
+    fn to_update(update: &Self::Update) -> Result<schema::SchemaComponentUpdate, Error>;
+    fn to_request(request: &Self::CommandRequest) -> Result<schema::SchemaCommandRequest, Error>;
     fn to_response(
         response: &Self::CommandResponse,
-    ) -> Result<schema::SchemaCommandResponse, String>;
+    ) -> Result<schema::SchemaCommandResponse, Error>;
 
     fn get_request_command_index(request: &Self::CommandRequest) -> u32;
     fn get_response_command_index(response: &Self::CommandResponse) -> u32;
 }
 
+// A trait that's implemented by a marker type generated for each command of a component. It ties
+// the command's request and response types to the component's command enums, so that requests
+// and responses for different commands can't be mixed up.
+pub trait Command
+where
+    Self: std::marker::Sized + 'static,
+{
+    type Component: Component;
+    type Request: Send + Sync + 'static;
+    type Response: Send + Sync + 'static;
+
+    const COMMAND_INDEX: CommandIndex;
+
+    fn into_request(request: Self::Request) -> <Self::Component as Component>::CommandRequest;
+    fn into_response(response: Self::Response) -> <Self::Component as Component>::CommandResponse;
+
+    fn request(request: &<Self::Component as Component>::CommandRequest) -> Option<&Self::Request>;
+    fn response(
+        response: &<Self::Component as Component>::CommandResponse,
+    ) -> Option<&Self::Response>;
+}
+
 /// Additional parameters for sending component updates.
 ///
-/// Additional parameters passed to [`Connection::send_component_update`]. Note that
-/// all parameters are kept private and the struct can only be initialized with
+/// Additional parameters passed to [`Connection::send_component_update`],
+/// [`Connection::send_add_component`] and [`Connection::send_remove_component`]. Note
+/// that all parameters are kept private and the struct can only be initialized with
 /// default values in order to make it possible to add new parameters without a
 /// breaking change.
 ///
@@ -125,113 +166,195 @@ impl UpdateParameters {
 pub(crate) mod internal {
     use crate::worker::internal::schema::*;
     use spatialos_sdk_sys::worker::*;
-    use std::marker::PhantomData;
-
-    use crate::worker::component::ComponentId;
-
-    #[derive(Debug)]
-    pub struct ComponentData<'a> {
-        pub component_id: ComponentId,
-        pub schema_type: SchemaComponentData,
-        pub user_handle: *const Worker_ComponentDataHandle,
-
-        // NOTE: `user_handle` is borrowing data owned by the parent object, but it's a
-        // type-erased pointer that may be null, so we just mark that we're borrowing
-        // *something*.
-        pub _marker: PhantomData<&'a ()>,
-    }
-
-    impl<'a> From<&'a Worker_ComponentData> for ComponentData<'a> {
-        fn from(data: &Worker_ComponentData) -> Self {
-            ComponentData {
-                component_id: data.component_id,
-                schema_type: SchemaComponentData {
-                    internal: data.schema_type,
-                },
-                user_handle: data.user_handle,
-                _marker: PhantomData,
-            }
+    use std::{marker::PhantomData, os::raw, ptr};
+
+    use crate::worker::component::{serialize_handle, ComponentId, HandleKind, OwnedHandle};
+    use crate::Error;
+    use once_cell::unsync::OnceCell;
+
+    // Deserializes the schema data of an op through the component's vtable if the op has
+    // no user data, which is the case when the connection doesn't use internal
+    // serialization. Returns `None` if the op has user data or the component has no
+    // vtable, in which case only the schema data is available.
+    //
+    // This is only called when the user data is first accessed, so that ops which are
+    // never read aren't deserialized.
+    fn deserialize_schema_type(
+        component_id: ComponentId,
+        kind: HandleKind,
+        schema_type: *mut raw::c_void,
+        user_handle: *const raw::c_void,
+    ) -> Option<OwnedHandle> {
+        if !user_handle.is_null() || schema_type.is_null() {
+            return None;
         }
-    }
-
-    #[derive(Debug)]
-    pub struct ComponentUpdate<'a> {
-        pub component_id: ComponentId,
-        pub schema_type: SchemaComponentUpdate,
-        pub user_handle: *const Worker_ComponentUpdateHandle,
 
-        // NOTE: `user_handle` is borrowing data owned by the parent object, but it's a
-        // type-erased pointer that may be null, so we just mark that we're borrowing
-        // *something*.
-        pub _marker: PhantomData<&'a ()>,
+        unsafe { OwnedHandle::deserialize(component_id, kind, schema_type).ok() }
     }
 
-    impl<'a> From<&'a Worker_ComponentUpdate> for ComponentUpdate<'a> {
-        fn from(update: &Worker_ComponentUpdate) -> Self {
-            ComponentUpdate {
-                component_id: update.component_id,
-                schema_type: SchemaComponentUpdate {
-                    internal: update.schema_type,
-                },
-                user_handle: update.user_handle,
-                _marker: PhantomData,
+    // Defines the untyped payload of an op, which has schema data, user data or both. Command
+    // payloads also record the index of the command within the component.
+    macro_rules! impl_payload {
+        (
+            $name:ident,
+            $schema_type:ident,
+            $handle_type:ident,
+            $worker_type:ident,
+            $kind:path
+            $(, $command_index:ident)?
+        ) => {
+            #[derive(Debug)]
+            pub struct $name<'a> {
+                pub component_id: ComponentId,
+                $(pub $command_index: FieldId,)?
+                pub schema_type: $schema_type,
+                pub user_handle: *const $handle_type,
+
+                // The user data deserialized from `schema_type` when it's first needed, if the
+                // op only has schema data and the component has a vtable.
+                deserialized: OnceCell<Option<OwnedHandle>>,
+
+                // NOTE: `user_handle` is borrowing data owned by the parent object, but it's a
+                // type-erased pointer that may be null, so we just mark that we're borrowing
+                // *something*.
+                pub _marker: PhantomData<&'a ()>,
             }
-        }
-    }
 
-    #[derive(Debug)]
-    pub struct CommandRequest<'a> {
-        pub component_id: ComponentId,
-        pub command_index: FieldId,
-        pub schema_type: SchemaCommandRequest,
-        pub user_handle: *const Worker_CommandRequestHandle,
-
-        // NOTE: `user_handle` is borrowing data owned by the parent object, but it's a
-        // type-erased pointer that may be null, so we just mark that we're borrowing
-        // *something*.
-        pub _marker: PhantomData<&'a ()>,
-    }
-
-    impl<'a> From<&'a Worker_CommandRequest> for CommandRequest<'a> {
-        fn from(request: &Worker_CommandRequest) -> Self {
-            CommandRequest {
-                component_id: request.component_id,
-                command_index: request.command_index,
-                schema_type: SchemaCommandRequest {
-                    internal: request.schema_type,
-                },
-                user_handle: request.user_handle,
-                _marker: PhantomData,
+            impl<'a> $name<'a> {
+                fn kind(&self) -> HandleKind {
+                    $kind$((self.$command_index))?
+                }
+
+                fn deserialized(&self) -> Option<&OwnedHandle> {
+                    self.deserialized
+                        .get_or_init(|| {
+                            deserialize_schema_type(
+                                self.component_id,
+                                self.kind(),
+                                self.schema_type.internal as *mut _,
+                                self.user_handle as *const _,
+                            )
+                        })
+                        .as_ref()
+                }
+
+                /// Returns the user data, which may have been deserialized from the schema
+                /// data, or null if there is none.
+                pub(crate) fn handle(&self) -> *const raw::c_void {
+                    match self.deserialized() {
+                        Some(handle) => handle.as_ptr(),
+                        None => self.user_handle as *const _,
+                    }
+                }
+
+                /// Copies the user data into an owned handle, deserializing it first if there
+                /// is no user handle.
+                pub(crate) fn to_owned_handle(&self) -> Result<OwnedHandle, Error> {
+                    if let Some(handle) = self.deserialized.get().and_then(Option::as_ref) {
+                        return Ok(handle.clone());
+                    }
+
+                    unsafe {
+                        if self.user_handle.is_null() {
+                            OwnedHandle::deserialize(
+                                self.component_id,
+                                self.kind(),
+                                self.schema_type.internal as *mut _,
+                            )
+                        } else {
+                            OwnedHandle::copy_from(
+                                self.component_id,
+                                self.kind(),
+                                self.user_handle as *const _,
+                            )
+                        }
+                    }
+                }
+
+                /// Returns the schema data, serializing the user data if the op has no schema
+                /// data.
+                pub(crate) fn schema(&self) -> Result<SchemaRef<'a, $schema_type>, Error> {
+                    if !self.schema_type.internal.is_null() {
+                        return Ok(SchemaRef::borrowed($schema_type {
+                            internal: self.schema_type.internal,
+                        }));
+                    }
+
+                    let internal = unsafe {
+                        serialize_handle(
+                            self.component_id,
+                            self.kind(),
+                            self.user_handle as *const _,
+                        )?
+                    };
+                    Ok(SchemaRef::owned($schema_type {
+                        internal: internal as *mut _,
+                    }))
+                }
             }
-        }
-    }
 
-    #[derive(Debug)]
-    pub struct CommandResponse<'a> {
-        pub component_id: ComponentId,
-        pub command_index: FieldId,
-        pub schema_type: SchemaCommandResponse,
-        pub user_handle: *const Worker_CommandResponseHandle,
-
-        // NOTE: `user_handle` is borrowing data owned by the parent object, but it's a
-        // type-erased pointer that may be null, so we just mark that we're borrowing
-        // *something*.
-        pub _marker: PhantomData<&'a ()>,
-    }
-
-    impl<'a> From<&'a Worker_CommandResponse> for CommandResponse<'a> {
-        fn from(response: &Worker_CommandResponse) -> Self {
-            CommandResponse {
-                component_id: response.component_id,
-                command_index: response.command_index,
-                schema_type: SchemaCommandResponse {
-                    internal: response.schema_type,
-                },
-                user_handle: response.user_handle,
-                _marker: PhantomData,
+            impl<'a> From<&'a OwnedHandle> for $name<'a> {
+                fn from(handle: &OwnedHandle) -> Self {
+                    $name {
+                        component_id: handle.component_id(),
+                        $($command_index: handle.$command_index(),)?
+                        schema_type: $schema_type {
+                            internal: ptr::null_mut(),
+                        },
+                        user_handle: handle.as_ptr(),
+                        deserialized: OnceCell::new(),
+                        _marker: PhantomData,
+                    }
+                }
             }
-        }
+
+            impl<'a> From<&'a $worker_type> for $name<'a> {
+                fn from(payload: &$worker_type) -> Self {
+                    $name {
+                        component_id: payload.component_id,
+                        $($command_index: payload.$command_index,)?
+                        schema_type: $schema_type {
+                            internal: payload.schema_type,
+                        },
+                        user_handle: payload.user_handle,
+                        deserialized: OnceCell::new(),
+                        _marker: PhantomData,
+                    }
+                }
+            }
+        };
     }
+
+    impl_payload!(
+        ComponentData,
+        SchemaComponentData,
+        Worker_ComponentDataHandle,
+        Worker_ComponentData,
+        HandleKind::Data
+    );
+    impl_payload!(
+        ComponentUpdate,
+        SchemaComponentUpdate,
+        Worker_ComponentUpdateHandle,
+        Worker_ComponentUpdate,
+        HandleKind::Update
+    );
+    impl_payload!(
+        CommandRequest,
+        SchemaCommandRequest,
+        Worker_CommandRequestHandle,
+        Worker_CommandRequest,
+        HandleKind::CommandRequest,
+        command_index
+    );
+    impl_payload!(
+        CommandResponse,
+        SchemaCommandResponse,
+        Worker_CommandResponseHandle,
+        Worker_CommandResponse,
+        HandleKind::CommandResponse,
+        command_index
+    );
 }
 
 inventory::collect!(VTable);
@@ -240,15 +363,21 @@ lazy_static::lazy_static! {
     pub(crate) static ref DATABASE: ComponentDatabase = {
 
         let mut vtables = Vec::new();
+        let mut view_vtables = Vec::new();
+        let mut handle_vtables = Vec::new();
         let mut index_map = HashMap::new();
 
         for (i, table) in inventory::iter::<VTable>.into_iter().enumerate() {
             vtables.push(table.vtable);
+            view_vtables.push(table.view_vtable);
+            handle_vtables.push(table.handle_vtables);
             index_map.insert(table.vtable.component_id, i);
         }
 
         ComponentDatabase {
             component_vtables: vtables,
+            view_vtables,
+            handle_vtables,
             index_map,
         }
     };
@@ -257,6 +386,8 @@ lazy_static::lazy_static! {
 #[derive(Clone, Debug)]
 pub(crate) struct ComponentDatabase {
     component_vtables: Vec<Worker_ComponentVtable>,
+    view_vtables: Vec<ViewVTable>,
+    handle_vtables: Vec<HandleVTables>,
     index_map: HashMap<ComponentId, usize>,
 }
 
@@ -271,6 +402,28 @@ impl ComponentDatabase {
             .map(|index| &self.component_vtables[*index])
     }
 
+    pub(crate) fn get_view_vtable(&self, id: ComponentId) -> Option<&ViewVTable> {
+        self.index_map
+            .get(&id)
+            .map(|index| &self.view_vtables[*index])
+    }
+
+    pub(crate) fn get_handle_vtable(
+        &self,
+        id: ComponentId,
+        kind: HandleKind,
+    ) -> Option<HandleVTable> {
+        self.index_map.get(&id).map(|index| {
+            let vtables = &self.handle_vtables[*index];
+            match kind {
+                HandleKind::Data => vtables.data,
+                HandleKind::Update => vtables.update,
+                HandleKind::CommandRequest(_) => vtables.command_request,
+                HandleKind::CommandResponse(_) => vtables.command_response,
+            }
+        })
+    }
+
     pub(crate) fn to_worker_sdk(&self) -> *const Worker_ComponentVtable {
         self.component_vtables.as_ptr()
     }
@@ -283,6 +436,284 @@ impl ComponentDatabase {
 unsafe impl Sync for ComponentDatabase {}
 unsafe impl Send for ComponentDatabase {}
 
+/// The kind of user data pointed to by an [`OwnedHandle`], which determines the vtable
+/// functions used to copy and free it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HandleKind {
+    Data,
+    Update,
+    CommandRequest(CommandIndex),
+    CommandResponse(CommandIndex),
+}
+
+impl HandleKind {
+    fn name(self) -> &'static str {
+        match self {
+            HandleKind::Data => "component data",
+            HandleKind::Update => "component update",
+            HandleKind::CommandRequest(_) => "command request",
+            HandleKind::CommandResponse(_) => "command response",
+        }
+    }
+}
+
+/// The functions used to copy and free user data allocated with [`handle_allocate`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HandleVTable {
+    copy: unsafe fn(*mut raw::c_void) -> *mut raw::c_void,
+    free: unsafe fn(*mut raw::c_void),
+}
+
+impl HandleVTable {
+    fn new<T>() -> Self {
+        HandleVTable {
+            copy: handle_copy::<T>,
+            free: handle_free::<T>,
+        }
+    }
+}
+
+/// The [`HandleVTable`] for each kind of user data of a component.
+#[derive(Debug, Clone, Copy)]
+struct HandleVTables {
+    data: HandleVTable,
+    update: HandleVTable,
+    command_request: HandleVTable,
+    command_response: HandleVTable,
+}
+
+/// An owned, type-erased handle to user data allocated with [`handle_allocate`].
+///
+/// The functions used to copy and free the handle are resolved when it's created, either
+/// from the type of the data or from the vtable registered for the component, so that
+/// cloning and dropping the handle can't fail.
+#[derive(Debug)]
+pub(crate) struct OwnedHandle {
+    component_id: ComponentId,
+    kind: HandleKind,
+    ptr: *mut raw::c_void,
+    vtable: HandleVTable,
+}
+
+impl OwnedHandle {
+    pub(crate) fn new<T>(component_id: ComponentId, kind: HandleKind, data: T) -> Self {
+        OwnedHandle {
+            component_id,
+            kind,
+            ptr: handle_allocate(data),
+            vtable: HandleVTable::new::<T>(),
+        }
+    }
+
+    /// Creates an owned handle by copying borrowed user data. Returns an error if the
+    /// component has no vtable.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid, non-null user handle of the given kind for the component.
+    pub(crate) unsafe fn copy_from(
+        component_id: ComponentId,
+        kind: HandleKind,
+        handle: *const raw::c_void,
+    ) -> Result<Self, Error> {
+        let vtable = DATABASE
+            .get_handle_vtable(component_id, kind)
+            .ok_or(Error::MissingVtable(component_id))?;
+
+        Ok(OwnedHandle {
+            component_id,
+            kind,
+            ptr: (vtable.copy)(handle as *mut _),
+            vtable,
+        })
+    }
+
+    /// Creates an owned handle by deserializing schema data through the component's
+    /// vtable. Returns an error if the component has no vtable or deserialization fails.
+    ///
+    /// # Safety
+    ///
+    /// `schema_type` must point to a valid schema object of the type matching `kind`,
+    /// i.e. a `Schema_ComponentData` for `HandleKind::Data`.
+    pub(crate) unsafe fn deserialize(
+        component_id: ComponentId,
+        kind: HandleKind,
+        schema_type: *mut raw::c_void,
+    ) -> Result<Self, Error> {
+        let id = component_id;
+        let vtable = DATABASE.get_vtable(id).ok_or(Error::MissingVtable(id))?;
+        let handle_vtable = DATABASE
+            .get_handle_vtable(id, kind)
+            .ok_or(Error::MissingVtable(id))?;
+
+        let mut handle = ptr::null_mut();
+        let result = match kind {
+            HandleKind::Data => {
+                let deserialize = vtable
+                    .component_data_deserialize
+                    .ok_or(Error::MissingVtable(id))?;
+                deserialize(id, ptr::null_mut(), schema_type as *mut _, &mut handle)
+            }
+            HandleKind::Update => {
+                let deserialize = vtable
+                    .component_update_deserialize
+                    .ok_or(Error::MissingVtable(id))?;
+                deserialize(id, ptr::null_mut(), schema_type as *mut _, &mut handle)
+            }
+            HandleKind::CommandRequest(index) => {
+                let deserialize = vtable
+                    .command_request_deserialize
+                    .ok_or(Error::MissingVtable(id))?;
+                deserialize(
+                    id,
+                    index,
+                    ptr::null_mut(),
+                    schema_type as *mut _,
+                    &mut handle,
+                )
+            }
+            HandleKind::CommandResponse(index) => {
+                let deserialize = vtable
+                    .command_response_deserialize
+                    .ok_or(Error::MissingVtable(id))?;
+                deserialize(
+                    id,
+                    index,
+                    ptr::null_mut(),
+                    schema_type as *mut _,
+                    &mut handle,
+                )
+            }
+        };
+
+        if result != 1 || handle.is_null() {
+            return Err(Error::decode(
+                kind.name(),
+                "The component's vtable failed to deserialize the schema data",
+            )
+            .in_component(id));
+        }
+
+        Ok(OwnedHandle {
+            component_id,
+            kind,
+            ptr: handle,
+            vtable: handle_vtable,
+        })
+    }
+
+    pub(crate) fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    pub(crate) fn command_index(&self) -> CommandIndex {
+        match self.kind {
+            HandleKind::CommandRequest(index) | HandleKind::CommandResponse(index) => index,
+            HandleKind::Data | HandleKind::Update => 0,
+        }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut raw::c_void {
+        self.ptr
+    }
+
+    /// Returns a reference to the user data.
+    ///
+    /// # Safety
+    ///
+    /// `T` must be the type the handle was allocated with. Generally, this is ensured
+    /// by checking the component ID of the handle against `C::ID`.
+    pub(crate) unsafe fn get<T>(&self) -> &T {
+        &*(self.ptr as *const T)
+    }
+}
+
+// SAFETY: The data behind a handle is only ever accessed immutably, and the
+// `Component` trait requires that component types and their associated types are
+// `Send + Sync`.
+unsafe impl Send for OwnedHandle {}
+unsafe impl Sync for OwnedHandle {}
+
+impl Clone for OwnedHandle {
+    fn clone(&self) -> Self {
+        OwnedHandle {
+            component_id: self.component_id,
+            kind: self.kind,
+            ptr: unsafe { (self.vtable.copy)(self.ptr) },
+            vtable: self.vtable,
+        }
+    }
+}
+
+impl Drop for OwnedHandle {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.free)(self.ptr) }
+    }
+}
+
+/// Serializes borrowed user data into schema data through the component's vtable.
+///
+/// The returned pointer is a `Schema_ComponentData`, `Schema_ComponentUpdate`,
+/// `Schema_CommandRequest` or `Schema_CommandResponse` depending on `kind`, and is owned
+/// by the caller.
+///
+/// # Safety
+///
+/// `handle` must be null or a valid user handle of the given kind for the component.
+pub(crate) unsafe fn serialize_handle(
+    component_id: ComponentId,
+    kind: HandleKind,
+    handle: *const raw::c_void,
+) -> Result<*mut raw::c_void, Error> {
+    let id = component_id;
+    if handle.is_null() {
+        return Err(Error::encode("The op has neither schema data nor user data").in_component(id));
+    }
+
+    let vtable = DATABASE.get_vtable(id).ok_or(Error::MissingVtable(id))?;
+    let handle = handle as *mut _;
+    let schema: *mut raw::c_void = match kind {
+        HandleKind::Data => {
+            let mut data = ptr::null_mut();
+            let serialize = vtable
+                .component_data_serialize
+                .ok_or(Error::MissingVtable(id))?;
+            serialize(id, ptr::null_mut(), handle, &mut data);
+            data as *mut _
+        }
+        HandleKind::Update => {
+            let mut update = ptr::null_mut();
+            let serialize = vtable
+                .component_update_serialize
+                .ok_or(Error::MissingVtable(id))?;
+            serialize(id, ptr::null_mut(), handle, &mut update);
+            update as *mut _
+        }
+        HandleKind::CommandRequest(index) => {
+            let mut request = ptr::null_mut();
+            let serialize = vtable
+                .command_request_serialize
+                .ok_or(Error::MissingVtable(id))?;
+            serialize(id, index, ptr::null_mut(), handle, &mut request);
+            request as *mut _
+        }
+        HandleKind::CommandResponse(index) => {
+            let mut response = ptr::null_mut();
+            let serialize = vtable
+                .command_response_serialize
+                .ok_or(Error::MissingVtable(id))?;
+            serialize(id, index, ptr::null_mut(), handle, &mut response);
+            response as *mut _
+        }
+    };
+
+    if schema.is_null() {
+        return Err(Error::encode("Failed to serialize user data").in_component(id));
+    }
+
+    Ok(schema)
+}
+
 pub(crate) fn handle_allocate<T>(data: T) -> *mut raw::c_void {
     Arc::into_raw(Arc::new(data)) as *mut _
 }
@@ -298,13 +729,39 @@ pub(crate) unsafe fn handle_copy<T>(handle: *mut raw::c_void) -> *mut raw::c_voi
     Arc::into_raw(copy) as *mut _
 }
 
+/// Type-erased functions used by the [`View`] to store and update component data
+/// without knowing the concrete component type.
+///
+/// [`View`]: ../view/struct.View.html
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ViewVTable {
+    pub(crate) copy_data: unsafe fn(*const raw::c_void) -> Box<dyn Any + Send + Sync>,
+    pub(crate) merge_update: unsafe fn(&mut (dyn Any + Send + Sync), *const raw::c_void),
+}
+
 pub struct VTable {
     vtable: Worker_ComponentVtable,
+    view_vtable: ViewVTable,
+    handle_vtables: HandleVTables,
 }
 
 impl VTable {
-    pub fn new<C: Component>() -> Self {
+    pub fn new<C>() -> Self
+    where
+        C: Component + ComponentData<C> + Clone,
+        C::Update: Clone,
+    {
         VTable {
+            view_vtable: ViewVTable {
+                copy_data: view_copy_data::<C>,
+                merge_update: view_merge_update::<C>,
+            },
+            handle_vtables: HandleVTables {
+                data: HandleVTable::new::<C>(),
+                update: HandleVTable::new::<C::Update>(),
+                command_request: HandleVTable::new::<C::CommandRequest>(),
+                command_response: HandleVTable::new::<C::CommandResponse>(),
+            },
             vtable: Worker_ComponentVtable {
                 component_id: C::ID,
                 user_data: ptr::null_mut(),
@@ -329,6 +786,23 @@ impl VTable {
     }
 }
 
+unsafe fn view_copy_data<C: Component + Clone>(
+    handle: *const raw::c_void,
+) -> Box<dyn Any + Send + Sync> {
+    Box::new((*(handle as *const C)).clone())
+}
+
+unsafe fn view_merge_update<C>(data: &mut (dyn Any + Send + Sync), handle: *const raw::c_void)
+where
+    C: Component + ComponentData<C>,
+    C::Update: Clone,
+{
+    let update = &*(handle as *const C::Update);
+    if let Some(data) = data.downcast_mut::<C>() {
+        data.merge(update.clone());
+    }
+}
+
 unsafe extern "C" fn vtable_component_data_free<C: Component>(
     _: u32,
     _: *mut raw::c_void,
@@ -380,7 +854,7 @@ unsafe extern "C" fn vtable_component_update_free<C: Component>(
     _: *mut raw::c_void,
     handle: *mut raw::c_void,
 ) {
-    handle_free::<C>(handle)
+    handle_free::<C::Update>(handle)
 }
 
 unsafe extern "C" fn vtable_component_update_copy<C: Component>(
@@ -388,7 +862,7 @@ unsafe extern "C" fn vtable_component_update_copy<C: Component>(
     _: *mut raw::c_void,
     handle: *mut raw::c_void,
 ) -> *mut raw::c_void {
-    handle_copy::<C>(handle)
+    handle_copy::<C::Update>(handle)
 }
 
 unsafe extern "C" fn vtable_component_update_deserialize<C: Component>(
@@ -428,7 +902,7 @@ unsafe extern "C" fn vtable_command_request_free<C: Component>(
     _: *mut raw::c_void,
     handle: *mut raw::c_void,
 ) {
-    handle_free::<C>(handle)
+    handle_free::<C::CommandRequest>(handle)
 }
 
 unsafe extern "C" fn vtable_command_request_copy<C: Component>(
@@ -437,7 +911,7 @@ unsafe extern "C" fn vtable_command_request_copy<C: Component>(
     _: *mut raw::c_void,
     handle: *mut raw::c_void,
 ) -> *mut raw::c_void {
-    handle_copy::<C>(handle)
+    handle_copy::<C::CommandRequest>(handle)
 }
 
 unsafe extern "C" fn vtable_command_request_deserialize<C: Component>(
@@ -479,7 +953,7 @@ unsafe extern "C" fn vtable_command_response_free<C: Component>(
     _: *mut raw::c_void,
     handle: *mut raw::c_void,
 ) {
-    handle_free::<C>(handle)
+    handle_free::<C::CommandResponse>(handle)
 }
 
 unsafe extern "C" fn vtable_command_response_copy<C: Component>(
@@ -488,7 +962,7 @@ unsafe extern "C" fn vtable_command_response_copy<C: Component>(
     _: *mut raw::c_void,
     handle: *mut raw::c_void,
 ) -> *mut raw::c_void {
-    handle_copy::<C>(handle)
+    handle_copy::<C::CommandResponse>(handle)
 }
 
 unsafe extern "C" fn vtable_command_response_deserialize<C: Component>(
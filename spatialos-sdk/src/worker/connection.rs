@@ -1,20 +1,33 @@
 use crate::ptr::MutPtr;
 use crate::worker::{
     commands::*,
-    component::{self, Component, UpdateParameters},
+    component::{self, Command, Component, UpdateParameters},
     entity::Entity,
-    internal::utils::cstr_to_string,
+    internal::{background::BackgroundFuture, utils::cstr_to_string},
     locator::*,
     metrics::Metrics,
-    op::OpList,
+    op::{OpList, WorkerOp},
     parameters::ConnectionParameters,
     {EntityId, InterestOverride, LogLevel, RequestId},
 };
+use crate::Error;
 use futures::{Async, Future};
+use futures_core::Stream;
 use spatialos_sdk_sys::worker::*;
 use std::{
+    convert::TryFrom,
     ffi::{CStr, CString, NulError},
+    ops::{Deref, DerefMut},
+    pin::Pin,
     ptr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender, TryRecvError},
+        Arc, Mutex, MutexGuard,
+    },
+    task::{Context, Poll, Waker},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
 /// Information about the status of a worker connection or network request.
@@ -45,9 +58,11 @@ pub enum ConnectionStatusCode {
     ServerShutdown,
 }
 
-impl From<u8> for ConnectionStatusCode {
-    fn from(value: u8) -> Self {
-        match u32::from(value) {
+impl TryFrom<u8> for ConnectionStatusCode {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Error> {
+        let code = match u32::from(value) {
             1 => ConnectionStatusCode::Success,
             2 => ConnectionStatusCode::InternalError,
             3 => ConnectionStatusCode::InvalidArgument,
@@ -60,8 +75,14 @@ impl From<u8> for ConnectionStatusCode {
             10 => ConnectionStatusCode::CapacityExceeded,
             11 => ConnectionStatusCode::RateExceeded,
             12 => ConnectionStatusCode::ServerShutdown,
-            _ => panic!(format!("Unknown connection status code: {}", value)),
-        }
+            _ => {
+                return Err(Error::UnknownValue {
+                    type_name: "ConnectionStatusCode",
+                    value: u32::from(value),
+                })
+            }
+        };
+        Ok(code)
     }
 }
 
@@ -118,6 +139,38 @@ pub trait Connection {
         message: &str,
     ) -> Result<(), NulError>;
 
+    /// Sends a request for command `Cmd`.
+    ///
+    /// Unlike [`send_command_request`], the request type is tied to the specific command
+    /// being sent, so the response can be read back with
+    /// [`CommandResponse::get_command`] using the same command type.
+    ///
+    /// [`send_command_request`]: #tymethod.send_command_request
+    /// [`CommandResponse::get_command`]: ../op/struct.CommandResponse.html#method.get_command
+    fn send_command<Cmd: Command>(
+        &mut self,
+        entity_id: EntityId,
+        request: Cmd::Request,
+        timeout_millis: Option<u32>,
+        params: CommandParameters,
+    ) -> RequestId<OutgoingCommandRequest> {
+        self.send_command_request::<Cmd::Component>(
+            entity_id,
+            Cmd::into_request(request),
+            timeout_millis,
+            params,
+        )
+    }
+
+    /// Sends the response to a request for command `Cmd`.
+    fn send_response<Cmd: Command>(
+        &mut self,
+        request_id: RequestId<IncomingCommandRequest>,
+        response: Cmd::Response,
+    ) {
+        self.send_command_response::<Cmd::Component>(request_id, Cmd::into_response(response));
+    }
+
     fn send_component_update<C: Component>(
         &mut self,
         entity_id: EntityId,
@@ -125,6 +178,31 @@ pub trait Connection {
         parameters: UpdateParameters,
     );
 
+    /// Adds the component `C` to the given entity.
+    ///
+    /// Dynamic components must be enabled in the [`ConnectionParameters`] in order to
+    /// add components to an entity.
+    ///
+    /// [`ConnectionParameters`]: ../parameters/struct.ConnectionParameters.html
+    fn send_add_component<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        component: C,
+        parameters: UpdateParameters,
+    );
+
+    /// Removes the component `C` from the given entity.
+    ///
+    /// Dynamic components must be enabled in the [`ConnectionParameters`] in order to
+    /// remove components from an entity.
+    ///
+    /// [`ConnectionParameters`]: ../parameters/struct.ConnectionParameters.html
+    fn send_remove_component<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        parameters: UpdateParameters,
+    );
+
     fn send_component_interest(
         &mut self,
         entity_id: EntityId,
@@ -462,6 +540,48 @@ impl Connection for WorkerConnection {
         }
     }
 
+    fn send_add_component<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        component: C,
+        parameters: UpdateParameters,
+    ) {
+        let mut component_data = Worker_ComponentData {
+            reserved: ptr::null_mut(),
+            component_id: C::ID,
+            schema_type: ptr::null_mut(),
+            user_handle: component::handle_allocate(component),
+        };
+
+        let params = parameters.to_worker_sdk();
+        unsafe {
+            Worker_Connection_SendAddComponent(
+                self.connection_ptr.get(),
+                entity_id.id,
+                &mut component_data,
+                &params,
+            );
+
+            component::handle_free::<C>(component_data.user_handle);
+        }
+    }
+
+    fn send_remove_component<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        parameters: UpdateParameters,
+    ) {
+        let params = parameters.to_worker_sdk();
+        unsafe {
+            Worker_Connection_SendRemoveComponent(
+                self.connection_ptr.get(),
+                entity_id.id,
+                C::ID,
+                &params,
+            );
+        }
+    }
+
     fn send_component_interest(
         &mut self,
         entity_id: EntityId,
@@ -510,9 +630,17 @@ impl Connection for WorkerConnection {
     fn get_connection_status(&mut self) -> ConnectionStatus {
         let ptr = self.connection_ptr.get();
         unsafe {
-            let code = ConnectionStatusCode::from(Worker_Connection_GetConnectionStatusCode(ptr));
+            let raw_code = Worker_Connection_GetConnectionStatusCode(ptr);
             let detail = cstr_to_string(Worker_Connection_GetConnectionStatusDetailString(ptr));
-            ConnectionStatus { code, detail }
+            match ConnectionStatusCode::try_from(raw_code) {
+                Ok(code) => ConnectionStatus { code, detail },
+                // Report status codes from newer versions of the C API as internal errors,
+                // rather than panicking.
+                Err(error) => ConnectionStatus {
+                    code: ConnectionStatusCode::InternalError,
+                    detail: format!("{}: {}", error, detail),
+                },
+            }
         }
     }
 
@@ -576,71 +704,344 @@ unsafe impl Sync for WorkerConnection {}
 
 pub struct WorkerConnectionFuture {
     future_ptr: *mut Worker_ConnectionFuture,
-    was_consumed: bool,
+    future: BackgroundFuture<Result<WorkerConnection, Error>>,
 }
 
 impl WorkerConnectionFuture {
     pub(crate) fn new(ptr: *mut Worker_ConnectionFuture) -> Self {
         WorkerConnectionFuture {
             future_ptr: ptr,
-            was_consumed: false,
+            future: BackgroundFuture::new(),
+        }
+    }
+
+    // Returns `None` if the connection isn't available before the timeout expires. A
+    // timeout of `None` blocks until the connection is available.
+    unsafe fn get(
+        future_ptr: *mut Worker_ConnectionFuture,
+        timeout_millis: Option<u32>,
+    ) -> Option<Result<WorkerConnection, Error>> {
+        assert!(!future_ptr.is_null());
+        let connection_ptr = Worker_ConnectionFuture_Get(
+            future_ptr,
+            timeout_millis.as_ref().map_or(ptr::null(), |value| value),
+        );
+
+        if connection_ptr.is_null() {
+            return None;
+        }
+
+        let mut connection = WorkerConnection::new(connection_ptr);
+
+        let status = connection.get_connection_status();
+        if status.code == ConnectionStatusCode::Success {
+            return Some(Ok(connection));
         }
+
+        Some(Err(Error::Connection {
+            code: status.code,
+            detail: status.detail,
+        }))
     }
 }
 
 impl Drop for WorkerConnectionFuture {
     fn drop(&mut self) {
-        assert!(!self.future_ptr.is_null());
-        unsafe {
-            Worker_ConnectionFuture_Destroy(self.future_ptr);
-        };
+        // The future is destroyed by the background thread if it was polled as a
+        // `std::future::Future`.
+        if !self.future_ptr.is_null() {
+            unsafe {
+                Worker_ConnectionFuture_Destroy(self.future_ptr);
+            };
+        }
     }
 }
 
 impl Future for WorkerConnectionFuture {
     type Item = WorkerConnection;
-    type Error = String;
+    type Error = Error;
 
     fn poll(&mut self) -> Result<Async<<Self as Future>::Item>, <Self as Future>::Error> {
-        if self.was_consumed {
-            return Err("WorkerConnectionFuture has already been consumed.".to_owned());
+        if self.future.is_consumed() || self.future_ptr.is_null() {
+            return Err(Error::FutureConsumed);
         }
 
-        assert!(!self.future_ptr.is_null());
-        let connection_ptr = unsafe { Worker_ConnectionFuture_Get(self.future_ptr, &0) };
+        match unsafe { WorkerConnectionFuture::get(self.future_ptr, Some(0)) } {
+            Some(result) => {
+                self.future.set_consumed();
+                result.map(Async::Ready)
+            }
+            None => Ok(Async::NotReady),
+        }
+    }
 
-        if connection_ptr.is_null() {
-            return Ok(Async::NotReady);
+    fn wait(self) -> Result<<Self as Future>::Item, <Self as Future>::Error>
+    where
+        Self: Sized,
+    {
+        if self.future.is_consumed() || self.future_ptr.is_null() {
+            return Err(Error::FutureConsumed);
         }
 
-        self.was_consumed = true;
-        let mut connection = WorkerConnection::new(connection_ptr);
+        unsafe { WorkerConnectionFuture::get(self.future_ptr, None) }
+            .expect("Blocking call to Worker_ConnectionFuture_Get did not return a connection")
+    }
+}
 
-        let status = connection.get_connection_status();
-        if status.code == ConnectionStatusCode::Success {
-            return Ok(Async::Ready(connection));
+impl std::future::Future for WorkerConnectionFuture {
+    type Output = Result<WorkerConnection, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Hand the C future off to a background thread that blocks until the
+        // connection is established, and then wakes this task.
+        let this = &mut *self;
+        this.future
+            .poll(cx, &mut this.future_ptr, |future_ptr| unsafe {
+                let result = WorkerConnectionFuture::get(future_ptr, None).unwrap_or_else(|| {
+                    Err(internal_connection_error(
+                        "Blocking call to Worker_ConnectionFuture_Get did not return a connection",
+                    ))
+                });
+                Worker_ConnectionFuture_Destroy(future_ptr);
+                result
+            })
+            .map(|result| match result {
+                Some(Ok(result)) => result,
+                Some(Err(panic_message)) => Err(internal_connection_error(panic_message)),
+                None => Err(Error::FutureConsumed),
+            })
+    }
+}
+
+fn internal_connection_error<S: Into<String>>(detail: S) -> Error {
+    Error::Connection {
+        code: ConnectionStatusCode::InternalError,
+        detail: detail.into(),
+    }
+}
+
+/// An asynchronous stream of the [`OpList`]s received from a connection.
+///
+/// The stream takes ownership of the connection and fetches op lists on a single
+/// background thread, so that the task is only woken once there are ops to process. Op
+/// lists containing no ops are not yielded. The stream ends after yielding an op list
+/// that contains a [`WorkerOp::Disconnect`] op. Dropping the stream stops the
+/// background thread.
+///
+/// The connection can still be used to send messages through [`connection`], which
+/// first waits for any in-progress fetch to complete.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures_core::Stream;
+/// use spatialos_sdk::worker::connection::{OpStream, WorkerConnection};
+/// use std::{pin::Pin, task::Context};
+///
+/// # fn poll_ops(connection: WorkerConnection, cx: &mut Context<'_>) {
+/// let mut ops = OpStream::new(connection, 100);
+/// while let std::task::Poll::Ready(Some(op_list)) = Pin::new(&mut ops).poll_next(cx) {
+///     for op in &op_list {
+///         println!("Received op: {:?}", op);
+///     }
+/// }
+/// # }
+/// ```
+///
+/// [`OpList`]: ../op/struct.OpList.html
+/// [`WorkerOp::Disconnect`]: ../op/enum.WorkerOp.html#variant.Disconnect
+/// [`connection`]: #method.connection
+pub struct OpStream<C> {
+    shared: Arc<Shared<C>>,
+    op_lists: Receiver<OpList>,
+    fetch_thread: Option<JoinHandle<()>>,
+    disconnected: bool,
+}
+
+// The minimum timeout used when fetching op lists on the background thread, so that
+// the thread blocks between fetches rather than busy-polling the connection.
+const MIN_FETCH_TIMEOUT_MILLIS: u32 = 10;
+
+// The state shared between an `OpStream` and its fetch thread.
+struct Shared<C> {
+    // Only `None` once the stream has been consumed by `into_inner`.
+    connection: Mutex<Option<C>>,
+    // Held while waiting for the connection outside of the fetch thread, so that the
+    // fetch thread can't immediately lock the connection again between fetches.
+    turnstile: Mutex<()>,
+    waker: Mutex<Option<Waker>>,
+    stopped: AtomicBool,
+}
+
+/// The connection of an [`OpStream`], which is locked so that no ops are fetched on
+/// the stream's background thread while it's in use.
+///
+/// [`OpStream`]: struct.OpStream.html
+pub struct OpStreamConnection<'a, C> {
+    connection: MutexGuard<'a, Option<C>>,
+    _turnstile: MutexGuard<'a, ()>,
+}
+
+impl<'a, C> Deref for OpStreamConnection<'a, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.connection.as_ref().unwrap()
+    }
+}
+
+impl<'a, C> DerefMut for OpStreamConnection<'a, C> {
+    fn deref_mut(&mut self) -> &mut C {
+        self.connection.as_mut().unwrap()
+    }
+}
+
+impl<C: Connection + Send + 'static> OpStream<C> {
+    /// Creates a new stream of op lists from the connection, and starts fetching op
+    /// lists on a background thread.
+    ///
+    /// `timeout_millis` is the timeout used for each call to [`get_op_list`] on the
+    /// background thread, with a minimum of 10 milliseconds. It bounds how long
+    /// [`connection`] may block while waiting for an in-progress fetch to complete.
+    ///
+    /// [`get_op_list`]: trait.Connection.html#tymethod.get_op_list
+    /// [`connection`]: #method.connection
+    pub fn new(connection: C, timeout_millis: u32) -> Self {
+        let shared = Arc::new(Shared {
+            connection: Mutex::new(Some(connection)),
+            turnstile: Mutex::new(()),
+            waker: Mutex::new(None),
+            stopped: AtomicBool::new(false),
+        });
+        let (sender, op_lists) = mpsc::channel();
+
+        let thread_shared = shared.clone();
+        let timeout_millis = timeout_millis.max(MIN_FETCH_TIMEOUT_MILLIS);
+        let fetch_thread =
+            thread::spawn(move || fetch_op_lists(&thread_shared, &sender, timeout_millis));
+
+        OpStream {
+            shared,
+            op_lists,
+            fetch_thread: Some(fetch_thread),
+            disconnected: false,
+        }
+    }
+
+    /// Returns the underlying connection.
+    ///
+    /// If ops are currently being fetched on the background thread, this blocks until
+    /// the fetch completes. No ops are fetched while the returned connection is alive.
+    pub fn connection(&mut self) -> OpStreamConnection<'_, C> {
+        let turnstile = self.shared.turnstile.lock().unwrap();
+        OpStreamConnection {
+            connection: self.shared.connection.lock().unwrap(),
+            _turnstile: turnstile,
         }
+    }
 
-        Err(status.detail)
+    /// Consumes the stream, returning the underlying connection.
+    ///
+    /// Any ops that have been received but not yet yielded by the stream are
+    /// discarded.
+    pub fn into_inner(mut self) -> C {
+        self.stop_fetching();
+        self.shared.connection.lock().unwrap().take().unwrap()
     }
 
-    fn wait(self) -> Result<<Self as Future>::Item, <Self as Future>::Error>
-    where
-        Self: Sized,
-    {
-        if self.was_consumed {
-            return Err("WorkerConnectionFuture has already been consumed.".to_owned());
+    fn yield_op_list(&mut self, op_list: OpList) -> Poll<Option<OpList>> {
+        for op in &op_list {
+            if let WorkerOp::Disconnect(_) = op {
+                self.disconnected = true;
+            }
         }
+        Poll::Ready(Some(op_list))
+    }
+}
 
-        assert!(!self.future_ptr.is_null());
-        let connection_ptr = unsafe { Worker_ConnectionFuture_Get(self.future_ptr, ptr::null()) };
-        let mut connection = WorkerConnection::new(connection_ptr);
+impl<C> OpStream<C> {
+    // Stops the fetch thread, blocking until any in-progress fetch completes.
+    fn stop_fetching(&mut self) {
+        self.shared.stopped.store(true, Ordering::SeqCst);
+        if let Some(fetch_thread) = self.fetch_thread.take() {
+            // A panic on the fetch thread has already ended the stream, see `poll_next`.
+            let _ = fetch_thread.join();
+        }
+    }
+}
 
-        let status = connection.get_connection_status();
-        if status.code == ConnectionStatusCode::Success {
-            return Ok(connection);
+impl<C> Drop for OpStream<C> {
+    fn drop(&mut self) {
+        self.stop_fetching();
+    }
+}
+
+// Fetches op lists until the stream is stopped or dropped, or the connection is
+// disconnected. Op lists containing no ops are skipped.
+fn fetch_op_lists<C: Connection>(shared: &Shared<C>, sender: &Sender<OpList>, timeout_millis: u32) {
+    let timeout = Duration::from_millis(u64::from(timeout_millis));
+    while !shared.stopped.load(Ordering::SeqCst) {
+        let started = Instant::now();
+        let op_list = {
+            drop(shared.turnstile.lock().unwrap());
+            let mut connection = shared.connection.lock().unwrap();
+            connection.as_mut().unwrap().get_op_list(timeout_millis)
+        };
+
+        if op_list.is_empty() {
+            // Connections that return before the timeout expires without any ops
+            // (such as a `MockConnection`) would otherwise cause this loop to spin.
+            if let Some(remaining) = timeout.checked_sub(started.elapsed()) {
+                thread::sleep(remaining);
+            }
+            continue;
+        }
+
+        let mut disconnected = false;
+        for op in &op_list {
+            if let WorkerOp::Disconnect(_) = op {
+                disconnected = true;
+            }
+        }
+        if sender.send(op_list).is_err() {
+            return;
+        }
+        if let Some(waker) = shared.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+        if disconnected {
+            return;
         }
+    }
+}
+
+// The connection is never pinned, so the stream can be moved freely.
+impl<C> Unpin for OpStream<C> {}
+
+impl<C: Connection + Send + 'static> Stream for OpStream<C> {
+    type Item = OpList;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.disconnected {
+            return Poll::Ready(None);
+        }
+
+        // The waker is registered before checking the channel a second time, so that an
+        // op list sent in between is never missed.
+        let op_list = match self.op_lists.try_recv() {
+            Ok(op_list) => op_list,
+            Err(TryRecvError::Empty) => {
+                *self.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+                match self.op_lists.try_recv() {
+                    Ok(op_list) => op_list,
+                    Err(TryRecvError::Empty) => return Poll::Pending,
+                    Err(TryRecvError::Disconnected) => return Poll::Ready(None),
+                }
+            }
+            // The fetch thread only stops early if it panicked.
+            Err(TryRecvError::Disconnected) => return Poll::Ready(None),
+        };
 
-        Err(status.detail)
+        self.yield_op_list(op_list)
     }
 }
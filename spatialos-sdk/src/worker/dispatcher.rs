@@ -0,0 +1,332 @@
+use crate::worker::{
+    commands::*,
+    component::{Command, Component},
+    connection::Connection,
+    entity::Entity,
+    op::*,
+    {EntityId, RequestId},
+};
+use std::collections::HashMap;
+
+type CommandCallback = Box<dyn FnOnce(&CommandResponseOp)>;
+type ReserveEntityIdsCallback = Box<dyn FnOnce(StatusCode<ReservedEntityIdRange>)>;
+type CreateEntityCallback = Box<dyn FnOnce(StatusCode<EntityId>)>;
+type DeleteEntityCallback = Box<dyn FnOnce(EntityId, StatusCode<()>)>;
+type EntityQueryCallback = Box<dyn FnOnce(StatusCode<QueryResponse>)>;
+
+/// Dispatches the responses to outgoing requests to per-request callbacks.
+///
+/// Requests can either be sent through the dispatcher with methods such as
+/// [`send_command_request`], or sent directly through the connection and then
+/// registered with the dispatcher using the returned request ID. Each op list received
+/// from the connection should then be passed to [`process_op_list`], which invokes the
+/// callback registered for each response. Callbacks are invoked at most once, and are
+/// removed once invoked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use spatialos_sdk::worker::{
+///     commands::ReserveEntityIdsRequest,
+///     connection::{Connection, WorkerConnection},
+///     dispatcher::RequestDispatcher,
+///     op::StatusCode,
+/// };
+/// # fn get_connection() -> WorkerConnection { unimplemented!() }
+///
+/// let mut connection = get_connection();
+/// let mut dispatcher = RequestDispatcher::new();
+///
+/// dispatcher.send_reserve_entity_ids_request(
+///     &mut connection,
+///     ReserveEntityIdsRequest(1),
+///     None,
+///     |result| match result {
+///         StatusCode::Success(range) => println!("Reserved {:?}", range),
+///         _ => println!("Failed to reserve an entity ID"),
+///     },
+/// );
+///
+/// loop {
+///     let ops = connection.get_op_list(0);
+///     dispatcher.process_op_list(&ops);
+/// }
+/// ```
+///
+/// [`send_command_request`]: #method.send_command_request
+/// [`process_op_list`]: #method.process_op_list
+#[derive(Default)]
+pub struct RequestDispatcher {
+    commands: HashMap<RequestId<OutgoingCommandRequest>, CommandCallback>,
+    reserve_entity_ids: HashMap<RequestId<ReserveEntityIdsRequest>, ReserveEntityIdsCallback>,
+    create_entity: HashMap<RequestId<CreateEntityRequest>, CreateEntityCallback>,
+    delete_entity: HashMap<RequestId<DeleteEntityRequest>, DeleteEntityCallback>,
+    entity_query: HashMap<RequestId<EntityQueryRequest>, EntityQueryCallback>,
+}
+
+impl RequestDispatcher {
+    pub fn new() -> Self {
+        RequestDispatcher::default()
+    }
+
+    /// Returns the number of requests that are still awaiting a response.
+    pub fn pending_requests(&self) -> usize {
+        self.commands.len()
+            + self.reserve_entity_ids.len()
+            + self.create_entity.len()
+            + self.delete_entity.len()
+            + self.entity_query.len()
+    }
+
+    /// Invokes the callbacks for any responses in the op list.
+    pub fn process_op_list(&mut self, op_list: &OpList) {
+        for op in op_list {
+            self.process_op(&op);
+        }
+    }
+
+    /// Invokes the callback registered for the op, if any.
+    ///
+    /// Returns `true` if the op was a response to a registered request.
+    pub fn process_op(&mut self, op: &WorkerOp) -> bool {
+        match op {
+            WorkerOp::CommandResponse(op) => match self.commands.remove(&op.request_id) {
+                Some(callback) => {
+                    callback(op);
+                    true
+                }
+                None => false,
+            },
+            WorkerOp::ReserveEntityIdsResponse(op) => {
+                match self.reserve_entity_ids.remove(&op.request_id) {
+                    Some(callback) => {
+                        callback(op.status_code.clone());
+                        true
+                    }
+                    None => false,
+                }
+            }
+            WorkerOp::CreateEntityResponse(op) => match self.create_entity.remove(&op.request_id) {
+                Some(callback) => {
+                    callback(op.status_code.clone());
+                    true
+                }
+                None => false,
+            },
+            WorkerOp::DeleteEntityResponse(op) => match self.delete_entity.remove(&op.request_id) {
+                Some(callback) => {
+                    callback(op.entity_id, op.status_code.clone());
+                    true
+                }
+                None => false,
+            },
+            WorkerOp::EntityQueryResponse(op) => match self.entity_query.remove(&op.request_id) {
+                Some(callback) => {
+                    callback(op.status_code.clone());
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Registers a callback to be invoked with the response to a command request for
+    /// component `C`.
+    pub fn on_command_response<C, F>(
+        &mut self,
+        request_id: RequestId<OutgoingCommandRequest>,
+        callback: F,
+    ) where
+        C: Component,
+        F: FnOnce(StatusCode<&C::CommandResponse>) + 'static,
+    {
+        let callback = move |op: &CommandResponseOp| {
+            let response = op
+                .response
+                .as_ref()
+                .and_then(|response| match response.get::<C>() {
+                    Some(response) => StatusCode::Success(response),
+                    None => StatusCode::InternalError(format!(
+                        "Received command response for component {}, expected component {}",
+                        op.component_id,
+                        C::ID
+                    )),
+                });
+            callback(response);
+        };
+
+        self.commands.insert(request_id, Box::new(callback));
+    }
+
+    /// Registers a callback to be invoked with the response to a request for command
+    /// `Cmd`.
+    pub fn on_command<Cmd, F>(&mut self, request_id: RequestId<OutgoingCommandRequest>, callback: F)
+    where
+        Cmd: Command,
+        F: FnOnce(StatusCode<&Cmd::Response>) + 'static,
+    {
+        self.on_command_response::<Cmd::Component, _>(request_id, move |response| {
+            let response = response.and_then(|response| match Cmd::response(response) {
+                Some(response) => StatusCode::Success(response),
+                None => StatusCode::InternalError(format!(
+                    "Received response for the wrong command, expected command {} of component {}",
+                    Cmd::COMMAND_INDEX,
+                    <Cmd::Component as Component>::ID
+                )),
+            });
+            callback(response);
+        });
+    }
+
+    pub fn on_reserve_entity_ids_response<F>(
+        &mut self,
+        request_id: RequestId<ReserveEntityIdsRequest>,
+        callback: F,
+    ) where
+        F: FnOnce(StatusCode<ReservedEntityIdRange>) + 'static,
+    {
+        self.reserve_entity_ids
+            .insert(request_id, Box::new(callback));
+    }
+
+    pub fn on_create_entity_response<F>(
+        &mut self,
+        request_id: RequestId<CreateEntityRequest>,
+        callback: F,
+    ) where
+        F: FnOnce(StatusCode<EntityId>) + 'static,
+    {
+        self.create_entity.insert(request_id, Box::new(callback));
+    }
+
+    /// Registers a callback to be invoked with the response to a delete entity request,
+    /// along with the ID of the entity that the request was for.
+    pub fn on_delete_entity_response<F>(
+        &mut self,
+        request_id: RequestId<DeleteEntityRequest>,
+        callback: F,
+    ) where
+        F: FnOnce(EntityId, StatusCode<()>) + 'static,
+    {
+        self.delete_entity.insert(request_id, Box::new(callback));
+    }
+
+    pub fn on_entity_query_response<F>(
+        &mut self,
+        request_id: RequestId<EntityQueryRequest>,
+        callback: F,
+    ) where
+        F: FnOnce(StatusCode<QueryResponse>) + 'static,
+    {
+        self.entity_query.insert(request_id, Box::new(callback));
+    }
+
+    /// Sends a command request for component `C`, invoking `callback` with the
+    /// response once it's received.
+    pub fn send_command_request<C, T, F>(
+        &mut self,
+        connection: &mut T,
+        entity_id: EntityId,
+        request: C::CommandRequest,
+        timeout_millis: Option<u32>,
+        params: CommandParameters,
+        callback: F,
+    ) -> RequestId<OutgoingCommandRequest>
+    where
+        C: Component,
+        T: Connection,
+        F: FnOnce(StatusCode<&C::CommandResponse>) + 'static,
+    {
+        let request_id =
+            connection.send_command_request::<C>(entity_id, request, timeout_millis, params);
+        self.on_command_response::<C, F>(request_id, callback);
+        request_id
+    }
+
+    /// Sends a request for command `Cmd`, invoking `callback` with the response once it's
+    /// received.
+    pub fn send_command<Cmd, T, F>(
+        &mut self,
+        connection: &mut T,
+        entity_id: EntityId,
+        request: Cmd::Request,
+        timeout_millis: Option<u32>,
+        params: CommandParameters,
+        callback: F,
+    ) -> RequestId<OutgoingCommandRequest>
+    where
+        Cmd: Command,
+        T: Connection,
+        F: FnOnce(StatusCode<&Cmd::Response>) + 'static,
+    {
+        let request_id = connection.send_command::<Cmd>(entity_id, request, timeout_millis, params);
+        self.on_command::<Cmd, F>(request_id, callback);
+        request_id
+    }
+
+    pub fn send_reserve_entity_ids_request<T, F>(
+        &mut self,
+        connection: &mut T,
+        payload: ReserveEntityIdsRequest,
+        timeout_millis: Option<u32>,
+        callback: F,
+    ) -> RequestId<ReserveEntityIdsRequest>
+    where
+        T: Connection,
+        F: FnOnce(StatusCode<ReservedEntityIdRange>) + 'static,
+    {
+        let request_id = connection.send_reserve_entity_ids_request(payload, timeout_millis);
+        self.on_reserve_entity_ids_response(request_id, callback);
+        request_id
+    }
+
+    pub fn send_create_entity_request<T, F>(
+        &mut self,
+        connection: &mut T,
+        entity: Entity,
+        entity_id: Option<EntityId>,
+        timeout_millis: Option<u32>,
+        callback: F,
+    ) -> RequestId<CreateEntityRequest>
+    where
+        T: Connection,
+        F: FnOnce(StatusCode<EntityId>) + 'static,
+    {
+        let request_id = connection.send_create_entity_request(entity, entity_id, timeout_millis);
+        self.on_create_entity_response(request_id, callback);
+        request_id
+    }
+
+    pub fn send_delete_entity_request<T, F>(
+        &mut self,
+        connection: &mut T,
+        payload: DeleteEntityRequest,
+        timeout_millis: Option<u32>,
+        callback: F,
+    ) -> RequestId<DeleteEntityRequest>
+    where
+        T: Connection,
+        F: FnOnce(EntityId, StatusCode<()>) + 'static,
+    {
+        let request_id = connection.send_delete_entity_request(payload, timeout_millis);
+        self.on_delete_entity_response(request_id, callback);
+        request_id
+    }
+
+    pub fn send_entity_query_request<T, F>(
+        &mut self,
+        connection: &mut T,
+        payload: EntityQueryRequest,
+        timeout_millis: Option<u32>,
+        callback: F,
+    ) -> RequestId<EntityQueryRequest>
+    where
+        T: Connection,
+        F: FnOnce(StatusCode<QueryResponse>) + 'static,
+    {
+        let request_id = connection.send_entity_query_request(payload, timeout_millis);
+        self.on_entity_query_response(request_id, callback);
+        request_id
+    }
+}
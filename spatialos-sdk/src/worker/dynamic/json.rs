@@ -0,0 +1,348 @@
+//! Conversion between dynamic schema values and JSON.
+//!
+//! Objects are JSON objects keyed by field name, enums are their value names, options are
+//! `null` or the contained value, and bytes are base64 strings. Maps are JSON objects, so
+//! map keys are written as strings. Entities are JSON objects keyed by the fully qualified
+//! name of each component.
+
+use super::{FieldValue, SchemaObjectValue, SchemaRegistry, SchemaValue};
+use crate::worker::component::ComponentId;
+use crate::worker::EntityId;
+use crate::Error;
+use serde_json::{Map, Number, Value};
+use spatialos_sdk_code_generator::schema_bundle::{
+    FieldDefinition, FieldDefinition_FieldType, PrimitiveType, TypeReference,
+};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+impl SchemaRegistry {
+    /// Converts a value to JSON.
+    pub fn value_to_json(&self, value: &SchemaValue) -> Result<Value, Error> {
+        let json = match value {
+            SchemaValue::Bool(value) => Value::Bool(*value),
+            SchemaValue::Int32(value) => Value::from(*value),
+            SchemaValue::Int64(value) => Value::from(*value),
+            SchemaValue::Uint32(value) => Value::from(*value),
+            SchemaValue::Uint64(value) => Value::from(*value),
+            // Go through the shortest string representation of the `f32`, so that e.g. 0.1
+            // isn't written as 0.10000000149011612.
+            SchemaValue::Float(value) => float_to_json(value.to_string().parse().unwrap())?,
+            SchemaValue::Double(value) => float_to_json(*value)?,
+            SchemaValue::String(value) => Value::String(value.clone()),
+            SchemaValue::Bytes(value) => Value::String(base64::encode(value)),
+            SchemaValue::EntityId(value) => Value::from(value.id),
+            SchemaValue::Entity(components) => self.entity_to_json(components)?,
+            SchemaValue::Enum { name, .. } => Value::String(name.clone()),
+            SchemaValue::Object(object) => self.object_to_json(object)?,
+            SchemaValue::Option(value) => match value {
+                Some(value) => self.value_to_json(value)?,
+                None => Value::Null,
+            },
+            SchemaValue::List(values) => Value::Array(
+                values
+                    .iter()
+                    .map(|value| self.value_to_json(value))
+                    .collect::<Result<_, _>>()?,
+            ),
+            SchemaValue::Map(entries) => {
+                let mut map = Map::new();
+                for (key, value) in entries {
+                    map.insert(key_to_string(key)?, self.value_to_json(value)?);
+                }
+                Value::Object(map)
+            }
+        };
+
+        Ok(json)
+    }
+
+    /// Converts the fields of a type or component to a JSON object keyed by field name.
+    pub fn object_to_json(&self, object: &SchemaObjectValue) -> Result<Value, Error> {
+        let mut map = Map::new();
+        for field in &object.fields {
+            map.insert(field.name.clone(), self.value_to_json(&field.value)?);
+        }
+        Ok(Value::Object(map))
+    }
+
+    /// Converts the components of an entity to a JSON object keyed by component name.
+    pub fn entity_to_json(
+        &self,
+        components: &BTreeMap<ComponentId, SchemaObjectValue>,
+    ) -> Result<Value, Error> {
+        let mut map = Map::new();
+        for (component_id, component) in components {
+            let json = self
+                .object_to_json(component)
+                .map_err(|e| e.in_component(*component_id))?;
+            map.insert(component.type_name.clone(), json);
+        }
+        Ok(Value::Object(map))
+    }
+
+    /// Converts a JSON object to an object of the schema type `type_name`.
+    ///
+    /// Fields that are missing from the JSON are left unset.
+    pub fn object_from_json(
+        &self,
+        type_name: &str,
+        json: &Value,
+    ) -> Result<SchemaObjectValue, Error> {
+        match self.types.get(type_name) {
+            Some(type_definition) => {
+                self.fields_from_json(type_name, &type_definition.fields, json)
+            }
+            None => Err(Error::decode(
+                type_name,
+                "Type not found in the schema bundle.",
+            )),
+        }
+    }
+
+    /// Converts a JSON object to the data of the component with the given ID.
+    pub fn component_from_json(
+        &self,
+        component_id: ComponentId,
+        json: &Value,
+    ) -> Result<SchemaObjectValue, Error> {
+        match self.components.get(&component_id) {
+            Some(component) => self.fields_from_json(
+                &component.qualified_name,
+                self.component_fields(component),
+                json,
+            ),
+            None => Err(Error::decode(
+                "component data",
+                "Component not found in the schema bundle.",
+            )),
+        }
+        .map_err(|e| e.in_component(component_id))
+    }
+
+    /// Converts a JSON object keyed by component name to the components of an entity.
+    pub fn entity_from_json(
+        &self,
+        json: &Value,
+    ) -> Result<BTreeMap<ComponentId, SchemaObjectValue>, Error> {
+        let map = json
+            .as_object()
+            .ok_or_else(|| Error::decode("Entity", "Expected a JSON object."))?;
+
+        let mut components = BTreeMap::new();
+        for (name, json) in map {
+            let component_id = self
+                .component_by_name(name)
+                .map(|component| component.component_id)
+                .ok_or_else(|| {
+                    Error::decode(
+                        "Entity",
+                        format!("Component {} not found in the schema bundle.", name),
+                    )
+                })?;
+            components.insert(component_id, self.component_from_json(component_id, json)?);
+        }
+
+        Ok(components)
+    }
+
+    fn fields_from_json(
+        &self,
+        type_name: &str,
+        fields: &[FieldDefinition],
+        json: &Value,
+    ) -> Result<SchemaObjectValue, Error> {
+        let map = json
+            .as_object()
+            .ok_or_else(|| Error::decode(type_name, "Expected a JSON object."))?;
+
+        let mut values = Vec::with_capacity(map.len());
+        for (name, json) in map {
+            let field = fields
+                .iter()
+                .find(|field| field.name == *name)
+                .ok_or_else(|| Error::decode(type_name, format!("Unknown field {}.", name)))?;
+            let value = self
+                .field_from_json(&field.field_type, json)
+                .map_err(|e| e.in_field(field.field_id))?;
+            values.push(FieldValue {
+                name: field.name.clone(),
+                field_id: field.field_id,
+                value,
+            });
+        }
+
+        // Keep the fields in schema order, regardless of the order of the JSON keys.
+        values.sort_by_key(|field| field.field_id);
+
+        Ok(SchemaObjectValue {
+            type_name: type_name.to_owned(),
+            fields: values,
+        })
+    }
+
+    fn field_from_json(
+        &self,
+        field_type: &FieldDefinition_FieldType,
+        json: &Value,
+    ) -> Result<SchemaValue, Error> {
+        match field_type {
+            FieldDefinition_FieldType::Singular { type_reference } => {
+                self.value_from_json(type_reference, json)
+            }
+            FieldDefinition_FieldType::Option { inner_type } => match json {
+                Value::Null => Ok(SchemaValue::Option(None)),
+                json => Ok(SchemaValue::Option(Some(Box::new(
+                    self.value_from_json(inner_type, json)?,
+                )))),
+            },
+            FieldDefinition_FieldType::List { inner_type } => {
+                let values = json.as_array().ok_or_else(|| {
+                    Error::decode(type_name(inner_type), "Expected a JSON array.")
+                })?;
+                values
+                    .iter()
+                    .map(|json| self.value_from_json(inner_type, json))
+                    .collect::<Result<_, _>>()
+                    .map(SchemaValue::List)
+            }
+            FieldDefinition_FieldType::Map {
+                key_type,
+                value_type,
+            } => {
+                let map = json.as_object().ok_or_else(|| {
+                    Error::decode(type_name(value_type), "Expected a JSON object.")
+                })?;
+                let mut entries = Vec::with_capacity(map.len());
+                for (key, json) in map {
+                    entries.push((
+                        self.key_from_str(key_type, key)?,
+                        self.value_from_json(value_type, json)?,
+                    ));
+                }
+                Ok(SchemaValue::Map(entries))
+            }
+        }
+    }
+
+    fn value_from_json(
+        &self,
+        value_type: &TypeReference,
+        json: &Value,
+    ) -> Result<SchemaValue, Error> {
+        let invalid = || {
+            Error::decode(
+                type_name(value_type),
+                format!("Invalid JSON value {}.", json),
+            )
+        };
+
+        let value = match value_type {
+            TypeReference::Primitive(primitive) => match primitive {
+                PrimitiveType::Invalid => return Err(invalid()),
+                PrimitiveType::Int32 | PrimitiveType::Sint32 | PrimitiveType::Sfixed32 => {
+                    let value = json.as_i64().ok_or_else(invalid)?;
+                    SchemaValue::Int32(i32::try_from(value).map_err(|_| invalid())?)
+                }
+                PrimitiveType::Int64 | PrimitiveType::Sint64 | PrimitiveType::Sfixed64 => {
+                    SchemaValue::Int64(json.as_i64().ok_or_else(invalid)?)
+                }
+                PrimitiveType::Uint32 | PrimitiveType::Fixed32 => {
+                    let value = json.as_u64().ok_or_else(invalid)?;
+                    SchemaValue::Uint32(u32::try_from(value).map_err(|_| invalid())?)
+                }
+                PrimitiveType::Uint64 | PrimitiveType::Fixed64 => {
+                    SchemaValue::Uint64(json.as_u64().ok_or_else(invalid)?)
+                }
+                PrimitiveType::Bool => SchemaValue::Bool(json.as_bool().ok_or_else(invalid)?),
+                PrimitiveType::Float => {
+                    SchemaValue::Float(json.as_f64().ok_or_else(invalid)? as f32)
+                }
+                PrimitiveType::Double => SchemaValue::Double(json.as_f64().ok_or_else(invalid)?),
+                PrimitiveType::String => {
+                    SchemaValue::String(json.as_str().ok_or_else(invalid)?.to_owned())
+                }
+                PrimitiveType::EntityId => {
+                    SchemaValue::EntityId(EntityId::new(json.as_i64().ok_or_else(invalid)?))
+                }
+                PrimitiveType::Bytes => {
+                    let encoded = json.as_str().ok_or_else(invalid)?;
+                    SchemaValue::Bytes(base64::decode(encoded).map_err(|_| invalid())?)
+                }
+                PrimitiveType::Entity => SchemaValue::Entity(self.entity_from_json(json)?),
+            },
+            TypeReference::Enum(enum_name) => {
+                let name = json.as_str().ok_or_else(invalid)?;
+                let value = self
+                    .enums
+                    .get(enum_name)
+                    .and_then(|definition| {
+                        definition
+                            .values
+                            .iter()
+                            .find(|enum_value| enum_value.name == name)
+                    })
+                    .map(|enum_value| enum_value.value)
+                    .ok_or_else(invalid)?;
+                SchemaValue::Enum {
+                    name: name.to_owned(),
+                    value,
+                }
+            }
+            TypeReference::Type(type_name) => {
+                SchemaValue::Object(self.object_from_json(type_name, json)?)
+            }
+        };
+
+        Ok(value)
+    }
+
+    // Map keys are always JSON strings, so keys of other types are parsed as JSON first.
+    fn key_from_str(&self, key_type: &TypeReference, key: &str) -> Result<SchemaValue, Error> {
+        let json = match key_type {
+            TypeReference::Primitive(PrimitiveType::String)
+            | TypeReference::Primitive(PrimitiveType::Bytes)
+            | TypeReference::Enum(_) => Value::String(key.to_owned()),
+            _ => serde_json::from_str(key).map_err(|_| {
+                Error::decode(type_name(key_type), format!("Invalid map key {}.", key))
+            })?,
+        };
+        self.value_from_json(key_type, &json)
+    }
+}
+
+fn key_to_string(key: &SchemaValue) -> Result<String, Error> {
+    let key = match key {
+        SchemaValue::Bool(value) => value.to_string(),
+        SchemaValue::Int32(value) => value.to_string(),
+        SchemaValue::Int64(value) => value.to_string(),
+        SchemaValue::Uint32(value) => value.to_string(),
+        SchemaValue::Uint64(value) => value.to_string(),
+        SchemaValue::Float(value) => value.to_string(),
+        SchemaValue::Double(value) => value.to_string(),
+        SchemaValue::String(value) => value.clone(),
+        SchemaValue::Bytes(value) => base64::encode(value),
+        SchemaValue::EntityId(value) => value.id.to_string(),
+        SchemaValue::Enum { name, .. } => name.clone(),
+        key => {
+            return Err(Error::encode(format!(
+                "{:?} can't be used as a JSON map key.",
+                key
+            )))
+        }
+    };
+    Ok(key)
+}
+
+fn float_to_json(value: f64) -> Result<Value, Error> {
+    Number::from_f64(value)
+        .map(Value::Number)
+        .ok_or_else(|| Error::encode(format!("{} can't be represented in JSON.", value)))
+}
+
+fn type_name(value_type: &TypeReference) -> String {
+    match value_type {
+        TypeReference::Primitive(primitive) => format!("{:?}", primitive).to_lowercase(),
+        TypeReference::Enum(name) | TypeReference::Type(name) => name.clone(),
+    }
+}
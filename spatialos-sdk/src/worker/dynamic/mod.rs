@@ -0,0 +1,724 @@
+//! Runtime access to schema data for components that weren't compiled into the worker.
+//!
+//! A [`SchemaRegistry`](struct.SchemaRegistry.html) is loaded from the `bundle.json`
+//! file produced by the schema compiler, and uses the definitions in it to decode any
+//! `SchemaObject` into a [`SchemaValue`](enum.SchemaValue.html) tree with field and enum
+//! names, and to encode such a tree back into schema data. This is intended for generic
+//! tooling, such as inspectors and data-driven entity templates.
+//!
+//! Values can also be converted to and from JSON, using field names for object keys and
+//! enum names for enum values.
+//!
+//! This module requires the `dynamic` feature.
+
+use crate::worker::component::ComponentId;
+use crate::worker::internal::schema::*;
+use crate::worker::EntityId;
+use crate::Error;
+use spatialos_sdk_code_generator::schema_bundle::{
+    self, ComponentDefinition, EnumDefinition, FieldDefinition, FieldDefinition_FieldType,
+    PrimitiveType, SchemaBundle, TypeDefinition, TypeReference,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+mod json;
+
+/// A dynamically typed schema value.
+///
+/// Primitive values are grouped by their Rust representation, so e.g. `int32`, `sint32`
+/// and `sfixed32` values are all `Int32`. The exact wire type is taken from the schema
+/// when the value is encoded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaValue {
+    Bool(bool),
+    Int32(i32),
+    Int64(i64),
+    Uint32(u32),
+    Uint64(u64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    EntityId(EntityId),
+    /// The data of each component in an `Entity`, keyed by component ID.
+    Entity(BTreeMap<ComponentId, SchemaObjectValue>),
+    Enum {
+        name: String,
+        value: u32,
+    },
+    Object(SchemaObjectValue),
+    Option(Option<Box<SchemaValue>>),
+    List(Vec<SchemaValue>),
+    Map(Vec<(SchemaValue, SchemaValue)>),
+}
+
+/// The fields of a schema type or component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaObjectValue {
+    /// The fully qualified name of the type or component.
+    pub type_name: String,
+    pub fields: Vec<FieldValue>,
+}
+
+impl SchemaObjectValue {
+    /// Returns the value of the field with the given name.
+    pub fn field(&self, name: &str) -> Option<&SchemaValue> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| &field.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldValue {
+    pub name: String,
+    pub field_id: FieldId,
+    pub value: SchemaValue,
+}
+
+/// The schema definitions from a schema bundle, indexed for lookup at runtime.
+#[derive(Debug, Clone)]
+pub struct SchemaRegistry {
+    types: HashMap<String, TypeDefinition>,
+    enums: HashMap<String, EnumDefinition>,
+    components: HashMap<ComponentId, ComponentDefinition>,
+}
+
+impl SchemaRegistry {
+    pub fn new(bundle: SchemaBundle) -> Self {
+        let mut registry = SchemaRegistry {
+            types: HashMap::new(),
+            enums: HashMap::new(),
+            components: HashMap::new(),
+        };
+
+        for file in bundle.schema_files {
+            for type_definition in file.types {
+                registry
+                    .types
+                    .insert(type_definition.qualified_name.clone(), type_definition);
+            }
+            for enum_definition in file.enums {
+                registry
+                    .enums
+                    .insert(enum_definition.qualified_name.clone(), enum_definition);
+            }
+            for component in file.components {
+                registry
+                    .components
+                    .insert(component.component_id, component);
+            }
+        }
+
+        registry
+    }
+
+    /// Parses the contents of a `bundle.json` file.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        schema_bundle::load_bundle(json)
+            .map(SchemaRegistry::new)
+            .map_err(|error| Error::SchemaBundle(error.to_string()))
+    }
+
+    /// Loads a `bundle.json` file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let json = fs::read_to_string(path.as_ref()).map_err(|error| {
+            Error::SchemaBundle(format!(
+                "Failed to read {}: {}",
+                path.as_ref().display(),
+                error
+            ))
+        })?;
+        SchemaRegistry::from_json(&json)
+    }
+
+    pub fn type_definition(&self, qualified_name: &str) -> Option<&TypeDefinition> {
+        self.types.get(qualified_name)
+    }
+
+    pub fn enum_definition(&self, qualified_name: &str) -> Option<&EnumDefinition> {
+        self.enums.get(qualified_name)
+    }
+
+    pub fn component(&self, component_id: ComponentId) -> Option<&ComponentDefinition> {
+        self.components.get(&component_id)
+    }
+
+    /// Returns the component with the given fully qualified name.
+    pub fn component_by_name(&self, qualified_name: &str) -> Option<&ComponentDefinition> {
+        self.components
+            .values()
+            .find(|component| component.qualified_name == qualified_name)
+    }
+
+    /// Decodes an object of the schema type `type_name`.
+    pub fn decode_object(
+        &self,
+        type_name: &str,
+        object: &SchemaObject,
+    ) -> Result<SchemaObjectValue, Error> {
+        let fields = match self.types.get(type_name) {
+            Some(type_definition) => &type_definition.fields,
+            None => {
+                return Err(Error::decode(
+                    type_name,
+                    "Type not found in the schema bundle.",
+                ))
+            }
+        };
+        self.decode_fields(type_name, fields, object)
+    }
+
+    /// Encodes an object into `object`, using the type named by `value.type_name`.
+    ///
+    /// Fields that are missing from `value` aren't written.
+    pub fn encode_object(
+        &self,
+        value: &SchemaObjectValue,
+        object: &mut SchemaObject,
+    ) -> Result<(), Error> {
+        match self.types.get(&value.type_name) {
+            Some(type_definition) => self.encode_fields(&type_definition.fields, value, object),
+            None => Err(Error::encode(format!(
+                "Type {} not found in the schema bundle.",
+                value.type_name
+            ))),
+        }
+    }
+
+    /// Decodes the data of the component with the given ID.
+    pub fn decode_component(
+        &self,
+        component_id: ComponentId,
+        data: &SchemaComponentData,
+    ) -> Result<SchemaObjectValue, Error> {
+        self.decode_component_fields(component_id, &data.fields())
+            .map_err(|e| e.in_component(component_id))
+    }
+
+    /// Encodes the data of the component with the given ID.
+    pub fn encode_component(
+        &self,
+        component_id: ComponentId,
+        value: &SchemaObjectValue,
+    ) -> Result<SchemaComponentData, Error> {
+        let mut data = SchemaComponentData::new();
+        let result = self.encode_component_fields(component_id, value, &mut data.fields_mut());
+        if let Err(error) = result {
+            unsafe { spatialos_sdk_sys::worker::Schema_DestroyComponentData(data.internal) };
+            return Err(error.in_component(component_id));
+        }
+        Ok(data)
+    }
+
+    /// Adds each field that's missing from the data of the component with the given ID,
+    /// set to its default value. This also applies to the fields of any objects and
+    /// entities nested in the component.
+    ///
+    /// Options, lists and maps default to being empty, enums to their first value, and
+    /// objects to having each of their fields set to its default value.
+    pub fn fill_component_defaults(
+        &self,
+        component_id: ComponentId,
+        value: &mut SchemaObjectValue,
+    ) -> Result<(), Error> {
+        match self.components.get(&component_id) {
+            Some(component) => self.fill_defaults(self.component_fields(component), value),
+            None => Err(Error::encode("Component not found in the schema bundle.")),
+        }
+        .map_err(|e| e.in_component(component_id))
+    }
+
+    fn fill_defaults(
+        &self,
+        fields: &[FieldDefinition],
+        value: &mut SchemaObjectValue,
+    ) -> Result<(), Error> {
+        for field in fields {
+            match value
+                .fields
+                .iter_mut()
+                .find(|field_value| field_value.field_id == field.field_id)
+            {
+                Some(field_value) => self.fill_nested_defaults(&mut field_value.value)?,
+                None => value.fields.push(FieldValue {
+                    name: field.name.clone(),
+                    field_id: field.field_id,
+                    value: self.default_field_value(&field.field_type)?,
+                }),
+            }
+        }
+
+        value.fields.sort_by_key(|field| field.field_id);
+        Ok(())
+    }
+
+    // Fills in the missing fields of any objects or entities contained in `value`.
+    fn fill_nested_defaults(&self, value: &mut SchemaValue) -> Result<(), Error> {
+        match value {
+            SchemaValue::Object(object) => {
+                let type_definition = self.types.get(&object.type_name).ok_or_else(|| {
+                    Error::encode(format!(
+                        "Type {} not found in the schema bundle.",
+                        object.type_name
+                    ))
+                })?;
+                self.fill_defaults(&type_definition.fields, object)?;
+            }
+            SchemaValue::Entity(components) => {
+                for (component_id, component) in components {
+                    self.fill_component_defaults(*component_id, component)?;
+                }
+            }
+            SchemaValue::Option(Some(value)) => self.fill_nested_defaults(value)?,
+            SchemaValue::List(values) => {
+                for value in values {
+                    self.fill_nested_defaults(value)?;
+                }
+            }
+            SchemaValue::Map(entries) => {
+                for (_, value) in entries {
+                    self.fill_nested_defaults(value)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn default_field_value(
+        &self,
+        field_type: &FieldDefinition_FieldType,
+    ) -> Result<SchemaValue, Error> {
+        let value = match field_type {
+            FieldDefinition_FieldType::Singular { type_reference } => {
+                self.default_value(type_reference)?
+            }
+            FieldDefinition_FieldType::Option { .. } => SchemaValue::Option(None),
+            FieldDefinition_FieldType::List { .. } => SchemaValue::List(Vec::new()),
+            FieldDefinition_FieldType::Map { .. } => SchemaValue::Map(Vec::new()),
+        };
+        Ok(value)
+    }
+
+    fn default_value(&self, value_type: &TypeReference) -> Result<SchemaValue, Error> {
+        let value = match value_type {
+            TypeReference::Primitive(primitive) => match primitive {
+                PrimitiveType::Invalid => return Err(Error::encode("Invalid primitive type.")),
+                PrimitiveType::Int32 | PrimitiveType::Sint32 | PrimitiveType::Sfixed32 => {
+                    SchemaValue::Int32(0)
+                }
+                PrimitiveType::Int64 | PrimitiveType::Sint64 | PrimitiveType::Sfixed64 => {
+                    SchemaValue::Int64(0)
+                }
+                PrimitiveType::Uint32 | PrimitiveType::Fixed32 => SchemaValue::Uint32(0),
+                PrimitiveType::Uint64 | PrimitiveType::Fixed64 => SchemaValue::Uint64(0),
+                PrimitiveType::Bool => SchemaValue::Bool(false),
+                PrimitiveType::Float => SchemaValue::Float(0.0),
+                PrimitiveType::Double => SchemaValue::Double(0.0),
+                PrimitiveType::String => SchemaValue::String(String::new()),
+                PrimitiveType::EntityId => SchemaValue::EntityId(EntityId::new(0)),
+                PrimitiveType::Bytes => SchemaValue::Bytes(Vec::new()),
+                PrimitiveType::Entity => SchemaValue::Entity(BTreeMap::new()),
+            },
+            TypeReference::Enum(enum_name) => {
+                let first = self
+                    .enums
+                    .get(enum_name)
+                    .and_then(|definition| definition.values.first())
+                    .ok_or_else(|| {
+                        Error::encode(format!(
+                            "Enum {} not found in the schema bundle.",
+                            enum_name
+                        ))
+                    })?;
+                SchemaValue::Enum {
+                    name: first.name.clone(),
+                    value: first.value,
+                }
+            }
+            TypeReference::Type(type_name) => {
+                let mut object = SchemaValue::Object(SchemaObjectValue {
+                    type_name: type_name.clone(),
+                    fields: Vec::new(),
+                });
+                self.fill_nested_defaults(&mut object)?;
+                object
+            }
+        };
+        Ok(value)
+    }
+
+    fn decode_component_fields(
+        &self,
+        component_id: ComponentId,
+        object: &SchemaObject,
+    ) -> Result<SchemaObjectValue, Error> {
+        match self.components.get(&component_id) {
+            Some(component) => self.decode_fields(
+                &component.qualified_name,
+                self.component_fields(component),
+                object,
+            ),
+            None => Err(Error::decode(
+                "component data",
+                "Component not found in the schema bundle.",
+            )
+            .in_component(component_id)),
+        }
+    }
+
+    fn encode_component_fields(
+        &self,
+        component_id: ComponentId,
+        value: &SchemaObjectValue,
+        object: &mut SchemaObject,
+    ) -> Result<(), Error> {
+        match self.components.get(&component_id) {
+            Some(component) => self.encode_fields(self.component_fields(component), value, object),
+            None => Err(Error::encode("Component not found in the schema bundle.")
+                .in_component(component_id)),
+        }
+    }
+
+    // Returns the fields of a component, which are those of its data type if the component
+    // is defined with `data` instead of listing its fields.
+    fn component_fields<'a>(&'a self, component: &'a ComponentDefinition) -> &'a [FieldDefinition] {
+        match component.data_definition {
+            Some(ref data_definition) => self
+                .types
+                .get(data_definition.as_str())
+                .map_or(&[][..], |data_type| &data_type.fields[..]),
+            None => &component.fields,
+        }
+    }
+
+    fn decode_fields(
+        &self,
+        type_name: &str,
+        fields: &[FieldDefinition],
+        object: &SchemaObject,
+    ) -> Result<SchemaObjectValue, Error> {
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            let value = self
+                .decode_field(field, object)
+                .map_err(|e| e.in_field(field.field_id))?;
+            values.push(FieldValue {
+                name: field.name.clone(),
+                field_id: field.field_id,
+                value,
+            });
+        }
+
+        Ok(SchemaObjectValue {
+            type_name: type_name.to_owned(),
+            fields: values,
+        })
+    }
+
+    fn decode_field(
+        &self,
+        field: &FieldDefinition,
+        object: &SchemaObject,
+    ) -> Result<SchemaValue, Error> {
+        let id = field.field_id;
+        match &field.field_type {
+            FieldDefinition_FieldType::Singular { type_reference } => {
+                self.decode_value(type_reference, object, id, None)
+            }
+            FieldDefinition_FieldType::Option { inner_type } => {
+                if count(inner_type, object, id) == 0 {
+                    return Ok(SchemaValue::Option(None));
+                }
+                let value = self.decode_value(inner_type, object, id, None)?;
+                Ok(SchemaValue::Option(Some(Box::new(value))))
+            }
+            FieldDefinition_FieldType::List { inner_type } => {
+                let size = count(inner_type, object, id);
+                let mut values = Vec::with_capacity(size);
+                for i in 0..size {
+                    values.push(self.decode_value(inner_type, object, id, Some(i))?);
+                }
+                Ok(SchemaValue::List(values))
+            }
+            FieldDefinition_FieldType::Map {
+                key_type,
+                value_type,
+            } => {
+                let entries = object.field::<SchemaObject>(id);
+                let mut values = Vec::with_capacity(entries.count());
+                for i in 0..entries.count() {
+                    let kv = entries.index(i);
+                    values.push((
+                        self.decode_value(key_type, &kv, 1, None)?,
+                        self.decode_value(value_type, &kv, 2, None)?,
+                    ));
+                }
+                Ok(SchemaValue::Map(values))
+            }
+        }
+    }
+
+    // Decodes a single value of field `id`. Singular values are read when `index` is `None`,
+    // otherwise the value at `index` in a list is read.
+    fn decode_value(
+        &self,
+        value_type: &TypeReference,
+        object: &SchemaObject,
+        id: FieldId,
+        index: Option<usize>,
+    ) -> Result<SchemaValue, Error> {
+        macro_rules! read {
+            ($schema_type:ty) => {{
+                let field = object.field::<$schema_type>(id);
+                match index {
+                    Some(i) => field.index(i),
+                    None => field.get_or_default(),
+                }
+            }};
+        }
+
+        let value = match value_type {
+            TypeReference::Primitive(primitive) => match primitive {
+                PrimitiveType::Invalid => {
+                    return Err(Error::decode("primitive", "Invalid primitive type."))
+                }
+                PrimitiveType::Int32 => SchemaValue::Int32(read!(SchemaInt32)),
+                PrimitiveType::Int64 => SchemaValue::Int64(read!(SchemaInt64)),
+                PrimitiveType::Uint32 => SchemaValue::Uint32(read!(SchemaUint32)),
+                PrimitiveType::Uint64 => SchemaValue::Uint64(read!(SchemaUint64)),
+                PrimitiveType::Sint32 => SchemaValue::Int32(read!(SchemaSint32)),
+                PrimitiveType::Sint64 => SchemaValue::Int64(read!(SchemaSint64)),
+                PrimitiveType::Fixed32 => SchemaValue::Uint32(read!(SchemaFixed32)),
+                PrimitiveType::Fixed64 => SchemaValue::Uint64(read!(SchemaFixed64)),
+                PrimitiveType::Sfixed32 => SchemaValue::Int32(read!(SchemaSfixed32)),
+                PrimitiveType::Sfixed64 => SchemaValue::Int64(read!(SchemaSfixed64)),
+                PrimitiveType::Bool => SchemaValue::Bool(read!(SchemaBool)),
+                PrimitiveType::Float => SchemaValue::Float(read!(SchemaFloat)),
+                PrimitiveType::Double => SchemaValue::Double(read!(SchemaDouble)),
+                PrimitiveType::String => SchemaValue::String(read!(SchemaString)),
+                PrimitiveType::EntityId => SchemaValue::EntityId(read!(SchemaEntityId)),
+                PrimitiveType::Bytes => SchemaValue::Bytes(read!(SchemaBytes)),
+                PrimitiveType::Entity => {
+                    let entity = read!(SchemaObject);
+                    let mut components = BTreeMap::new();
+                    for component_id in entity.unique_field_ids() {
+                        let fields = entity.field::<SchemaObject>(component_id).get_or_default();
+                        let component = self
+                            .decode_component_fields(component_id, &fields)
+                            .map_err(|e| e.in_component(component_id))?;
+                        components.insert(component_id, component);
+                    }
+                    SchemaValue::Entity(components)
+                }
+            },
+            TypeReference::Enum(enum_name) => {
+                let value = read!(SchemaEnum);
+                let definition = self.enums.get(enum_name).ok_or_else(|| {
+                    Error::decode(enum_name.as_str(), "Enum not found in the schema bundle.")
+                })?;
+                let name = definition
+                    .values
+                    .iter()
+                    .find(|enum_value| enum_value.value == value)
+                    .map(|enum_value| enum_value.name.clone())
+                    .ok_or_else(|| {
+                        Error::decode(enum_name.as_str(), format!("Unknown enum value {}.", value))
+                    })?;
+                SchemaValue::Enum { name, value }
+            }
+            TypeReference::Type(type_name) => {
+                SchemaValue::Object(self.decode_object(type_name, &read!(SchemaObject))?)
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn encode_fields(
+        &self,
+        fields: &[FieldDefinition],
+        value: &SchemaObjectValue,
+        object: &mut SchemaObject,
+    ) -> Result<(), Error> {
+        for field_value in &value.fields {
+            let field = fields
+                .iter()
+                .find(|field| field.name == field_value.name)
+                .ok_or_else(|| {
+                    Error::encode(format!(
+                        "{} has no field named {}.",
+                        value.type_name, field_value.name
+                    ))
+                })?;
+            self.encode_field(field, &field_value.value, object)
+                .map_err(|e| match e {
+                    Error::SchemaEncode {
+                        component_id,
+                        message,
+                    } => Error::SchemaEncode {
+                        component_id,
+                        message: format!("{}.{}: {}", value.type_name, field.name, message),
+                    },
+                    e => e,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    fn encode_field(
+        &self,
+        field: &FieldDefinition,
+        value: &SchemaValue,
+        object: &mut SchemaObject,
+    ) -> Result<(), Error> {
+        let id = field.field_id;
+        match (&field.field_type, value) {
+            (FieldDefinition_FieldType::Singular { type_reference }, value) => {
+                self.encode_value(type_reference, value, object, id)
+            }
+            (FieldDefinition_FieldType::Option { inner_type }, SchemaValue::Option(value)) => {
+                match value {
+                    Some(value) => self.encode_value(inner_type, value, object, id),
+                    None => Ok(()),
+                }
+            }
+            (FieldDefinition_FieldType::List { inner_type }, SchemaValue::List(values)) => {
+                for value in values {
+                    self.encode_value(inner_type, value, object, id)?;
+                }
+                Ok(())
+            }
+            (
+                FieldDefinition_FieldType::Map {
+                    key_type,
+                    value_type,
+                },
+                SchemaValue::Map(entries),
+            ) => {
+                for (key, value) in entries {
+                    let mut kv = object.field::<SchemaObject>(id).add();
+                    self.encode_value(key_type, key, &mut kv, 1)?;
+                    self.encode_value(value_type, value, &mut kv, 2)?;
+                }
+                Ok(())
+            }
+            (_, value) => Err(mismatch(value)),
+        }
+    }
+
+    fn encode_value(
+        &self,
+        value_type: &TypeReference,
+        value: &SchemaValue,
+        object: &mut SchemaObject,
+        id: FieldId,
+    ) -> Result<(), Error> {
+        macro_rules! add {
+            ($schema_type:ty, $value:expr) => {
+                object.field::<$schema_type>(id).add($value)
+            };
+        }
+
+        match (value_type, value) {
+            (TypeReference::Primitive(primitive), value) => match (primitive, value) {
+                (PrimitiveType::Int32, SchemaValue::Int32(v)) => add!(SchemaInt32, *v),
+                (PrimitiveType::Int64, SchemaValue::Int64(v)) => add!(SchemaInt64, *v),
+                (PrimitiveType::Uint32, SchemaValue::Uint32(v)) => add!(SchemaUint32, *v),
+                (PrimitiveType::Uint64, SchemaValue::Uint64(v)) => add!(SchemaUint64, *v),
+                (PrimitiveType::Sint32, SchemaValue::Int32(v)) => add!(SchemaSint32, *v),
+                (PrimitiveType::Sint64, SchemaValue::Int64(v)) => add!(SchemaSint64, *v),
+                (PrimitiveType::Fixed32, SchemaValue::Uint32(v)) => add!(SchemaFixed32, *v),
+                (PrimitiveType::Fixed64, SchemaValue::Uint64(v)) => add!(SchemaFixed64, *v),
+                (PrimitiveType::Sfixed32, SchemaValue::Int32(v)) => add!(SchemaSfixed32, *v),
+                (PrimitiveType::Sfixed64, SchemaValue::Int64(v)) => add!(SchemaSfixed64, *v),
+                (PrimitiveType::Bool, SchemaValue::Bool(v)) => add!(SchemaBool, *v),
+                (PrimitiveType::Float, SchemaValue::Float(v)) => add!(SchemaFloat, *v),
+                (PrimitiveType::Double, SchemaValue::Double(v)) => add!(SchemaDouble, *v),
+                (PrimitiveType::String, SchemaValue::String(v)) => add!(SchemaString, v),
+                (PrimitiveType::EntityId, SchemaValue::EntityId(v)) => {
+                    add!(SchemaEntityId, *v)
+                }
+                (PrimitiveType::Bytes, SchemaValue::Bytes(v)) => add!(SchemaBytes, v),
+                (PrimitiveType::Entity, SchemaValue::Entity(components)) => {
+                    let entity = object.field::<SchemaObject>(id).add();
+                    for (component_id, component) in components {
+                        let mut fields = entity.field::<SchemaObject>(*component_id).add();
+                        self.encode_component_fields(*component_id, component, &mut fields)
+                            .map_err(|e| e.in_component(*component_id))?;
+                    }
+                }
+                (_, value) => return Err(mismatch(value)),
+            },
+            (TypeReference::Enum(enum_name), SchemaValue::Enum { name, value }) => {
+                let definition = self.enums.get(enum_name).ok_or_else(|| {
+                    Error::encode(format!(
+                        "Enum {} not found in the schema bundle.",
+                        enum_name
+                    ))
+                })?;
+                let known = definition
+                    .values
+                    .iter()
+                    .any(|enum_value| enum_value.value == *value && enum_value.name == *name);
+                if !known {
+                    return Err(Error::encode(format!(
+                        "{} = {} is not a value of {}.",
+                        name, value, enum_name
+                    )));
+                }
+                add!(SchemaEnum, *value)
+            }
+            (TypeReference::Type(type_name), SchemaValue::Object(value)) => {
+                if value.type_name != *type_name {
+                    return Err(Error::encode(format!(
+                        "Expected a {}, got a {}.",
+                        type_name, value.type_name
+                    )));
+                }
+                self.encode_object(value, &mut object.field::<SchemaObject>(id).add())?
+            }
+            (_, value) => return Err(mismatch(value)),
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the number of values of field `id`, which is stored as `value_type`.
+fn count(value_type: &TypeReference, object: &SchemaObject, id: FieldId) -> usize {
+    match value_type {
+        TypeReference::Primitive(primitive) => match primitive {
+            PrimitiveType::Invalid => 0,
+            PrimitiveType::Int32 => object.field::<SchemaInt32>(id).count(),
+            PrimitiveType::Int64 => object.field::<SchemaInt64>(id).count(),
+            PrimitiveType::Uint32 => object.field::<SchemaUint32>(id).count(),
+            PrimitiveType::Uint64 => object.field::<SchemaUint64>(id).count(),
+            PrimitiveType::Sint32 => object.field::<SchemaSint32>(id).count(),
+            PrimitiveType::Sint64 => object.field::<SchemaSint64>(id).count(),
+            PrimitiveType::Fixed32 => object.field::<SchemaFixed32>(id).count(),
+            PrimitiveType::Fixed64 => object.field::<SchemaFixed64>(id).count(),
+            PrimitiveType::Sfixed32 => object.field::<SchemaSfixed32>(id).count(),
+            PrimitiveType::Sfixed64 => object.field::<SchemaSfixed64>(id).count(),
+            PrimitiveType::Bool => object.field::<SchemaBool>(id).count(),
+            PrimitiveType::Float => object.field::<SchemaFloat>(id).count(),
+            PrimitiveType::Double => object.field::<SchemaDouble>(id).count(),
+            PrimitiveType::String => object.field::<SchemaString>(id).count(),
+            PrimitiveType::EntityId => object.field::<SchemaEntityId>(id).count(),
+            PrimitiveType::Bytes => object.field::<SchemaBytes>(id).count(),
+            PrimitiveType::Entity => object.field::<SchemaObject>(id).count(),
+        },
+        TypeReference::Enum(_) => object.field::<SchemaEnum>(id).count(),
+        TypeReference::Type(_) => object.field::<SchemaObject>(id).count(),
+    }
+}
+
+fn mismatch(value: &SchemaValue) -> Error {
+    Error::encode(format!("Value {:?} doesn't match the schema type.", value))
+}
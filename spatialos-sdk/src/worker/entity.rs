@@ -1,6 +1,16 @@
-use crate::worker::component::{self, Component, ComponentId, DATABASE};
-use crate::worker::internal::schema::SchemaComponentData;
-use spatialos_sdk_sys::worker::{Schema_DestroyComponentData, Worker_ComponentData, Worker_Entity};
+use crate::worker::component::{
+    self, Component, ComponentId, HandleKind, TypeConversion, DATABASE,
+};
+use crate::worker::internal::schema::{
+    SchemaComponentData, SchemaObject, SchemaObjectField, SchemaRef,
+};
+use crate::Error;
+use spatialos_sdk_sys::worker::{
+    Schema_ComponentData, Schema_DestroyComponentData, Worker_ComponentData,
+    Worker_ComponentDataHandle, Worker_Entity,
+};
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::ptr;
 use std::slice;
@@ -15,7 +25,7 @@ impl Entity {
         Entity::default()
     }
 
-    pub(crate) unsafe fn from_worker_sdk(raw_entity: &Worker_Entity) -> Result<Self, String> {
+    pub(crate) unsafe fn from_worker_sdk(raw_entity: &Worker_Entity) -> Result<Self, Error> {
         let mut entity = Entity::new();
 
         let component_data =
@@ -28,7 +38,7 @@ impl Entity {
         Ok(entity)
     }
 
-    pub(crate) fn add<C: Component>(&mut self, component: C) -> Result<(), String> {
+    pub(crate) fn add<C: Component>(&mut self, component: C) -> Result<(), Error> {
         self.pre_add_check(C::ID)?;
 
         let data_ptr = component::handle_allocate(component);
@@ -44,30 +54,27 @@ impl Entity {
         Ok(())
     }
 
-    pub(crate) unsafe fn add_raw(
-        &mut self,
-        component: &Worker_ComponentData,
-    ) -> Result<(), String> {
+    pub(crate) unsafe fn add_raw(&mut self, component: &Worker_ComponentData) -> Result<(), Error> {
         let id = component.component_id;
 
         self.pre_add_check(id)?;
 
-        // Call copy on the component data. We don't own this Worker_ComponentData.
-        let vtable = DATABASE.get_vtable(id).unwrap();
-        let copy_data_func = vtable
-            .component_data_copy
-            .unwrap_or_else(|| panic!("No component_data_free method defined for {}", id));
-        copy_data_func(id, ptr::null_mut(), component.user_handle);
+        // Components read from a connection or snapshot without internal serialization
+        // only have schema data, which we deserialize into our own user handle.
+        // Otherwise we call copy on the component data, as we don't own this
+        // Worker_ComponentData.
+        let user_handle = if component.user_handle.is_null() && !component.schema_type.is_null() {
+            deserialize_component_data(id, component.schema_type)?
+        } else {
+            let copy_data_func = DATABASE
+                .get_vtable(id)
+                .and_then(|vtable| vtable.component_data_copy)
+                .ok_or(Error::MissingVtable(id))?;
+            copy_data_func(id, ptr::null_mut(), component.user_handle);
+            component.user_handle
+        };
 
-        self.components.insert(
-            id,
-            Worker_ComponentData {
-                reserved: ptr::null_mut(),
-                component_id: id,
-                schema_type: ptr::null_mut(),
-                user_handle: component.user_handle,
-            },
-        );
+        self.insert_handle(id, user_handle);
 
         Ok(())
     }
@@ -76,48 +83,32 @@ impl Entity {
         &mut self,
         component_id: ComponentId,
         component: SchemaComponentData,
-    ) -> Result<(), String> {
-        let vtable = DATABASE.get_vtable(component_id).unwrap();
-        let deserialize_func = vtable.component_data_deserialize.unwrap_or_else(|| {
-            Schema_DestroyComponentData(component.internal);
-            panic!(
-                "No component_data_deserialize method defined for {}",
-                component_id
-            )
-        });
-
-        // Create the **void that the C API requires. We need to then clean this up later.
-        // The value pointed to by handle_out_ptr is written to during the deserialize method.
-        let placeholder_ptr = Box::into_raw(Box::new(0)) as *mut ::std::os::raw::c_void;
-        let handle_out_ptr = Box::into_raw(Box::new(placeholder_ptr));
-
-        let deserialize_result = deserialize_func(
-            component_id,
-            ptr::null_mut(),
-            component.internal,
-            handle_out_ptr,
-        );
+    ) -> Result<(), Error> {
+        let result = self
+            .pre_add_check(component_id)
+            .and_then(|_| deserialize_component_data(component_id, component.internal));
         Schema_DestroyComponentData(component.internal);
 
-        match deserialize_result {
-            1 => {},
-            0 => return Err("Error deserializing serialized data. Is the SchemaComponentData malformed?".to_owned()),
-            _ => panic!("Unexpected return value from deserialize function. Expected true or false. Received other.")
-        };
-
-        let component_data = Worker_ComponentData {
-            reserved: ptr::null_mut(),
-            component_id,
-            schema_type: ptr::null_mut(),
-            user_handle: *handle_out_ptr,
-        };
+        self.insert_handle(component_id, result?);
 
-        // Reconstruct these objects so they can be de-alloc'ed. We have pulled the data required
-        // from them by de-referencing handle_out_ptr above.
-        Box::from_raw(placeholder_ptr);
-        Box::from_raw(handle_out_ptr);
+        Ok(())
+    }
 
-        self.add_raw(&component_data)
+    // Takes ownership of a user handle created through the component's vtable.
+    fn insert_handle(
+        &mut self,
+        component_id: ComponentId,
+        user_handle: *mut Worker_ComponentDataHandle,
+    ) {
+        self.components.insert(
+            component_id,
+            Worker_ComponentData {
+                reserved: ptr::null_mut(),
+                component_id,
+                schema_type: ptr::null_mut(),
+                user_handle,
+            },
+        );
     }
 
     pub fn get<C: Component>(&self) -> Option<&C> {
@@ -126,23 +117,127 @@ impl Entity {
             .map(|data| unsafe { &*(data.user_handle as *const _) })
     }
 
+    /// Adds component `C` to the entity, replacing the existing data for `C` if the
+    /// entity already has it.
+    ///
+    /// Returns an error if no vtable has been registered for `C`.
+    pub fn set<C: Component>(&mut self, component: C) -> Result<(), Error> {
+        self.remove::<C>();
+        self.add(component)
+    }
+
+    /// Removes component `C` from the entity, returning `true` if it was present.
+    pub fn remove<C: Component>(&mut self) -> bool {
+        match self.components.remove(&C::ID) {
+            Some(component_data) => {
+                free_component_data(&component_data);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the schema data of the component with the given ID, or `None` if the entity
+    /// doesn't have the component.
+    ///
+    /// This makes it possible to inspect components without knowing their Rust type. The
+    /// component data is serialized through the component's vtable.
+    pub fn schema(
+        &self,
+        component_id: ComponentId,
+    ) -> Option<Result<SchemaRef<'_, SchemaComponentData>, Error>> {
+        self.components.get(&component_id).map(|data| {
+            let internal = unsafe {
+                component::serialize_handle(
+                    component_id,
+                    HandleKind::Data,
+                    data.user_handle as *const _,
+                )?
+            };
+            Ok(SchemaRef::owned(SchemaComponentData {
+                internal: internal as *mut _,
+            }))
+        })
+    }
+
+    pub fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.keys().cloned()
+    }
+
     pub(crate) fn raw_component_data(&self) -> RawEntity {
         RawEntity::new(self.components.values())
     }
 
-    fn pre_add_check(&self, id: ComponentId) -> Result<(), String> {
+    fn pre_add_check(&self, id: ComponentId) -> Result<(), Error> {
         if self.components.contains_key(&id) {
-            return Err(format!(
-                "Duplicate component with ID {} added to `Entity`.",
-                id
-            ));
+            return Err(Error::DuplicateComponent(id));
         }
 
         if !DATABASE.has_vtable(id) {
-            panic!(format!(
-                "Could not find a vtable implementation for component {}",
-                id
-            ));
+            return Err(Error::MissingVtable(id));
+        }
+
+        Ok(())
+    }
+}
+
+// Entities embedded in schema types are stored as an object with one field per component, where
+// each field is the component's data object and the field ID is the component ID.
+impl TypeConversion for Entity {
+    fn from_type(input: &SchemaObject) -> Result<Self, Error> {
+        let mut entity = Entity::new();
+
+        for component_id in input.unique_field_ids() {
+            if !DATABASE.has_vtable(component_id) {
+                return Err(Error::MissingVtable(component_id));
+            }
+
+            let mut data = SchemaComponentData::new();
+            let fields = input.field::<SchemaObject>(component_id).get_or_default();
+            if let Err(message) = data.fields_mut().merge_from(&fields) {
+                unsafe { Schema_DestroyComponentData(data.internal) };
+                return Err(Error::decode("Entity", message).in_component(component_id));
+            }
+
+            // `add_serialized` takes ownership of the component data.
+            unsafe { entity.add_serialized(component_id, data)? };
+        }
+
+        Ok(entity)
+    }
+
+    fn to_type(input: &Self, output: &mut SchemaObject) -> Result<(), Error> {
+        let mut component_ids: Vec<_> = input.component_ids().collect();
+        component_ids.sort();
+
+        for component_id in component_ids {
+            let serialize_func = DATABASE
+                .get_vtable(component_id)
+                .and_then(|vtable| vtable.component_data_serialize)
+                .ok_or(Error::MissingVtable(component_id))?;
+
+            let mut data = ptr::null_mut();
+            unsafe {
+                serialize_func(
+                    component_id,
+                    ptr::null_mut(),
+                    input.components[&component_id].user_handle,
+                    &mut data,
+                )
+            };
+            if data.is_null() {
+                return Err(
+                    Error::encode("Failed to serialize component").in_component(component_id)
+                );
+            }
+
+            let data = SchemaComponentData { internal: data };
+            let result = output
+                .field::<SchemaObject>(component_id)
+                .add()
+                .merge_from(&data.fields());
+            unsafe { Schema_DestroyComponentData(data.internal) };
+            result.map_err(|message| Error::encode(message).in_component(component_id))?;
         }
 
         Ok(())
@@ -157,22 +252,209 @@ impl Default for Entity {
     }
 }
 
-impl Drop for Entity {
-    fn drop(&mut self) {
+// SAFETY: The component data is only ever accessed immutably through the user
+// handles, and the `Component` trait requires component types to be `Send + Sync`.
+unsafe impl Send for Entity {}
+unsafe impl Sync for Entity {}
+
+impl Clone for Entity {
+    fn clone(&self) -> Self {
+        let mut entity = Entity::new();
         for component_data in self.components.values() {
-            let id = component_data.component_id;
+            // Adding the raw data copies the component through its vtable.
+            unsafe {
+                entity
+                    .add_raw(component_data)
+                    .expect("Cloned entity cannot contain duplicate components");
+            }
+        }
+        entity
+    }
+}
 
-            let vtable = DATABASE.get_vtable(id).unwrap();
+// Two entities are equal if they have the same components and each component serializes to the
+// same schema data.
+impl PartialEq for Entity {
+    fn eq(&self, other: &Self) -> bool {
+        if self.components.len() != other.components.len() {
+            return false;
+        }
 
-            let free_data_func = vtable
-                .component_data_free
-                .unwrap_or_else(|| panic!("No component_data_free method defined for {}", id));
+        self.components.keys().all(|&component_id| {
+            match (self.schema(component_id), other.schema(component_id)) {
+                (Some(Ok(data)), Some(Ok(other_data))) => {
+                    match (data.fields().serialize(), other_data.fields().serialize()) {
+                        (Ok(bytes), Ok(other_bytes)) => bytes == other_bytes,
+                        _ => false,
+                    }
+                }
+                _ => false,
+            }
+        })
+    }
+}
 
-            unsafe { free_data_func(id, ptr::null_mut(), component_data.user_handle) };
+// With serde, an entity is a map from component ID to the component's data in the schema wire
+// format, so it can be serialized without knowing the types of its components. The data is a
+// base64 string in human-readable formats such as JSON, and raw bytes in binary formats.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Entity {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeMap};
+
+        let mut component_ids: Vec<_> = self.component_ids().collect();
+        component_ids.sort();
+
+        let mut map = serializer.serialize_map(Some(component_ids.len()))?;
+        for component_id in component_ids {
+            let data = self
+                .schema(component_id)
+                .unwrap()
+                .map_err(S::Error::custom)?;
+            let bytes = data.fields().serialize().map_err(|message| {
+                S::Error::custom(Error::encode(message).in_component(component_id))
+            })?;
+            map.serialize_entry(&component_id, &SchemaBytes(bytes))?;
         }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Entity {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error as _;
+
+        let components = BTreeMap::<ComponentId, SchemaBytes>::deserialize(deserializer)?;
+        let mut entity = Entity::new();
+        for (component_id, SchemaBytes(bytes)) in components {
+            let mut data = SchemaComponentData::new();
+            if let Err(message) = data.fields_mut().merge_from_buffer(&bytes) {
+                unsafe { Schema_DestroyComponentData(data.internal) };
+                return Err(D::Error::custom(
+                    Error::decode("Entity", message).in_component(component_id),
+                ));
+            }
+
+            // `add_serialized` takes ownership of the component data.
+            unsafe { entity.add_serialized(component_id, data) }.map_err(D::Error::custom)?;
+        }
+
+        Ok(entity)
     }
 }
 
+// The serialized schema data of a component.
+#[cfg(feature = "serde")]
+struct SchemaBytes(Vec<u8>);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SchemaBytes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&base64::encode(&self.0))
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SchemaBytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{SeqAccess, Visitor};
+        use std::fmt;
+
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = SchemaBytes;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("serialized schema data")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<SchemaBytes, E> {
+                base64::decode(value)
+                    .map(SchemaBytes)
+                    .map_err(|_| E::custom("Invalid base64 schema data"))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<SchemaBytes, E> {
+                Ok(SchemaBytes(value.to_vec()))
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, value: Vec<u8>) -> Result<SchemaBytes, E> {
+                Ok(SchemaBytes(value))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<SchemaBytes, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(SchemaBytes(bytes))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BytesVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+}
+
+impl Drop for Entity {
+    fn drop(&mut self) {
+        for component_data in self.components.values() {
+            free_component_data(component_data);
+        }
+    }
+}
+
+// Deserializes schema component data into a new user handle through the component's vtable.
+// The schema data is borrowed and isn't destroyed.
+unsafe fn deserialize_component_data(
+    component_id: ComponentId,
+    component: *mut Schema_ComponentData,
+) -> Result<*mut Worker_ComponentDataHandle, Error> {
+    let deserialize_func = DATABASE
+        .get_vtable(component_id)
+        .and_then(|vtable| vtable.component_data_deserialize)
+        .ok_or(Error::MissingVtable(component_id))?;
+
+    let mut handle = ptr::null_mut();
+    match deserialize_func(component_id, ptr::null_mut(), component, &mut handle) {
+        1 => Ok(handle),
+        0 => Err(Error::decode(
+            "component data",
+            "Error deserializing serialized data. Is the SchemaComponentData malformed?",
+        )
+        .in_component(component_id)),
+        result => Err(Error::decode(
+            "component data",
+            format!(
+                "Unexpected return value {} from the deserialize function, expected 0 or 1",
+                result
+            ),
+        )
+        .in_component(component_id)),
+    }
+}
+
+fn free_component_data(component_data: &Worker_ComponentData) {
+    let id = component_data.component_id;
+
+    let vtable = DATABASE.get_vtable(id).unwrap();
+
+    let free_data_func = vtable
+        .component_data_free
+        .unwrap_or_else(|| panic!("No component_data_free method defined for {}", id));
+
+    unsafe { free_data_func(id, ptr::null_mut(), component_data.user_handle) };
+}
+
 // Required for when we call Entity::raw_component_data() and want a Vec<Worker_ComponentData> rather
 // than a Vec<&Worker_ComponentData> which most callers *will* want due to how Worker_Entity is structured.
 pub(crate) struct RawEntity {
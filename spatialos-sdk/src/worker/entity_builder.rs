@@ -7,6 +7,7 @@ use crate::worker::{
         SchemaString, SchemaStringField, SchemaUint32,
     },
 };
+use crate::Error;
 use std::collections::{HashMap, HashSet};
 
 const ENTITY_ACL_COMPONENT_ID: ComponentId = 50;
@@ -24,7 +25,7 @@ pub struct EntityBuilder {
     write_permissions: HashMap<ComponentId, String>,
     read_permissions: HashSet<String>,
 
-    error: Option<String>,
+    error: Option<Error>,
 }
 
 impl EntityBuilder {
@@ -79,7 +80,7 @@ impl EntityBuilder {
         self.read_permissions.insert(layer);
     }
 
-    pub fn build(mut self) -> Result<Entity, String> {
+    pub fn build(mut self) -> Result<Entity, Error> {
         if let Some(e) = self.error {
             return Err(e);
         }
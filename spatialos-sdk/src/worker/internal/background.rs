@@ -0,0 +1,150 @@
+//! Utilities for exposing blocking C API calls as `std::future::Future`s.
+//!
+//! The C API doesn't provide a way to be notified when a future completes, only a
+//! way to block on it with a timeout. To avoid busy-polling, the blocking call is
+//! made on a background thread which wakes the task once the result is available.
+
+use std::{
+    any::Any,
+    mem,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+/// The result of a blocking call running on a background thread.
+///
+/// If the call panics, the panic message is returned as an error rather than leaving
+/// the task pending forever.
+struct BackgroundTask<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+struct Shared<T> {
+    result: Option<Result<T, String>>,
+    waker: Option<Waker>,
+}
+
+impl<T: Send + 'static> BackgroundTask<T> {
+    fn spawn<F>(f: F) -> Self
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(Shared {
+            result: None,
+            waker: None,
+        }));
+
+        let thread_shared = shared.clone();
+        thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message);
+
+            let mut shared = thread_shared.lock().unwrap();
+            shared.result = Some(result);
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        });
+
+        BackgroundTask { shared }
+    }
+
+    /// Returns the result if the call has completed, otherwise registers the task to be
+    /// woken once it does.
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, String>> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".to_owned());
+    format!(
+        "Background thread panicked while waiting on the C API: {}",
+        message
+    )
+}
+
+/// A `std::future::Future` backed by a C API future object.
+///
+/// On the first poll, ownership of the C future is moved to a background thread which
+/// blocks on it. The result is returned once, after which the future is consumed.
+pub(crate) struct BackgroundFuture<T> {
+    task: Option<BackgroundTask<T>>,
+    consumed: bool,
+}
+
+impl<T: Send + 'static> BackgroundFuture<T> {
+    pub(crate) fn new() -> Self {
+        BackgroundFuture {
+            task: None,
+            consumed: false,
+        }
+    }
+
+    pub(crate) fn is_consumed(&self) -> bool {
+        self.consumed
+    }
+
+    pub(crate) fn set_consumed(&mut self) {
+        self.consumed = true;
+    }
+
+    /// Polls the blocking call, starting it on the first poll.
+    ///
+    /// When first polled, the pointer is taken (leaving it null) and passed to `get`
+    /// on a background thread. `get` must block until the result is available and
+    /// then destroy the C future. Returns `None` if the result has already been taken,
+    /// and the panic message as an error if `get` panicked.
+    pub(crate) fn poll<P, F>(
+        &mut self,
+        cx: &mut Context<'_>,
+        future_ptr: &mut *mut P,
+        get: F,
+    ) -> Poll<Option<Result<T, String>>>
+    where
+        P: 'static,
+        F: FnOnce(*mut P) -> T + Send + 'static,
+    {
+        if self.consumed {
+            return Poll::Ready(None);
+        }
+
+        if self.task.is_none() {
+            let future_ptr = SendPtr(mem::replace(future_ptr, ptr::null_mut()));
+            self.task = Some(BackgroundTask::spawn(move || {
+                let future_ptr = future_ptr;
+                get(future_ptr.0)
+            }));
+        }
+
+        let result = match self.task.as_mut().unwrap().poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        self.consumed = true;
+        self.task = None;
+        Poll::Ready(Some(result))
+    }
+}
+
+/// Wrapper that allows a pointer to a C API future to be moved to a background thread.
+///
+/// The future objects in the C API can be used from any thread, as long as they are
+/// only used by one thread at a time.
+struct SendPtr<T>(*mut T);
+
+unsafe impl<T> Send for SendPtr<T> {}
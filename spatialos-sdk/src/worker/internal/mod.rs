@@ -1,2 +1,3 @@
+pub(crate) mod background;
 pub mod schema;
 pub(crate) mod utils;
@@ -1,7 +1,9 @@
 use crate::worker::component::ComponentId;
+use crate::worker::internal::utils::cstr_to_string;
 use crate::worker::EntityId;
 use spatialos_sdk_sys::worker::*;
 use std::marker::PhantomData;
+use std::ops::Deref;
 use std::slice;
 
 pub type FieldId = u32;
@@ -62,7 +64,33 @@ impl SchemaComponentUpdate {
         }
     }
 
-    // TODO: Cleared fields.
+    /// Returns the IDs of the option, list and map fields that this update sets to the
+    /// empty value.
+    pub fn cleared_fields(&self) -> Vec<FieldId> {
+        let count = unsafe { Schema_GetComponentUpdateClearedFieldCount(self.internal) };
+        let mut field_ids = vec![0; count as usize];
+        unsafe {
+            Schema_GetComponentUpdateClearedFieldList(self.internal, field_ids.as_mut_ptr());
+        }
+        field_ids
+    }
+
+    pub fn is_field_cleared(&self, field_id: FieldId) -> bool {
+        unsafe { Schema_IsComponentUpdateFieldCleared(self.internal, field_id) != 0 }
+    }
+
+    /// Marks an option, list or map field as being set to the empty value by this update.
+    pub fn add_cleared_field(&mut self, field_id: FieldId) {
+        unsafe {
+            Schema_AddComponentUpdateClearedField(self.internal, field_id);
+        }
+    }
+
+    pub fn clear_cleared_fields(&mut self) {
+        unsafe {
+            Schema_ClearComponentUpdateClearedFields(self.internal);
+        }
+    }
 }
 
 impl Default for SchemaComponentUpdate {
@@ -149,6 +177,85 @@ impl Default for SchemaCommandResponse {
     }
 }
 
+/// A schema type that owns the schema data under it, and that can be destroyed when that
+/// data is no longer needed.
+pub trait SchemaRoot {
+    #[doc(hidden)]
+    unsafe fn destroy(&self);
+}
+
+impl SchemaRoot for SchemaComponentData {
+    unsafe fn destroy(&self) {
+        Schema_DestroyComponentData(self.internal);
+    }
+}
+
+impl SchemaRoot for SchemaComponentUpdate {
+    unsafe fn destroy(&self) {
+        Schema_DestroyComponentUpdate(self.internal);
+    }
+}
+
+impl SchemaRoot for SchemaCommandRequest {
+    unsafe fn destroy(&self) {
+        Schema_DestroyCommandRequest(self.internal);
+    }
+}
+
+impl SchemaRoot for SchemaCommandResponse {
+    unsafe fn destroy(&self) {
+        Schema_DestroyCommandResponse(self.internal);
+    }
+}
+
+/// A read-only view of the schema data of a component, component update or command
+/// payload.
+///
+/// The schema data is either borrowed from the op or entity it was read from, or
+/// serialized from the op's user data, in which case it's destroyed when the
+/// `SchemaRef` is dropped. It dereferences to the underlying schema type, so the fields
+/// can be read without a generated type for the component.
+#[derive(Debug)]
+pub struct SchemaRef<'a, T: SchemaRoot> {
+    schema: T,
+    owned: bool,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a, T: SchemaRoot> SchemaRef<'a, T> {
+    pub(crate) fn borrowed(schema: T) -> Self {
+        SchemaRef {
+            schema,
+            owned: false,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn owned(schema: T) -> Self {
+        SchemaRef {
+            schema,
+            owned: true,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: SchemaRoot> Deref for SchemaRef<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.schema
+    }
+}
+
+impl<'a, T: SchemaRoot> Drop for SchemaRef<'a, T> {
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe { self.schema.destroy() };
+        }
+    }
+}
+
 // A schema field. T is a schema type tag.
 #[derive(Debug)]
 pub struct SchemaFieldContainer<'a, T> {
@@ -271,6 +378,46 @@ impl SchemaObject {
             _phantom: PhantomData,
         }
     }
+
+    /// Returns the sorted IDs of the fields that are set in the object.
+    pub fn unique_field_ids(&self) -> Vec<FieldId> {
+        unsafe {
+            let count = Schema_GetUniqueFieldIdCount(self.internal) as usize;
+            let mut field_ids = vec![0; count];
+            Schema_GetUniqueFieldIds(self.internal, field_ids.as_mut_ptr());
+            field_ids
+        }
+    }
+
+    /// Copies all of the fields of `other` into this object.
+    ///
+    /// Unlike `Schema_ShallowCopy`, this performs a deep copy, so the two objects don't
+    /// need to share an owner.
+    pub fn merge_from(&mut self, other: &SchemaObject) -> Result<(), String> {
+        self.merge_from_buffer(&other.serialize()?)
+    }
+
+    /// Merges an object in its wire format, as produced by `serialize`, into this object.
+    pub fn merge_from_buffer(&mut self, buffer: &[u8]) -> Result<(), String> {
+        unsafe {
+            if Schema_MergeFromBuffer(self.internal, buffer.as_ptr(), buffer.len() as u32) == 0 {
+                return Err(cstr_to_string(Schema_GetError(self.internal)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes the object to its wire format.
+    pub fn serialize(&self) -> Result<Vec<u8>, String> {
+        unsafe {
+            let length = Schema_GetWriteBufferLength(self.internal);
+            let mut buffer = vec![0u8; length as usize];
+            if Schema_SerializeToBuffer(self.internal, buffer.as_mut_ptr(), length) == 0 {
+                return Err(cstr_to_string(Schema_GetError(self.internal)));
+            }
+            Ok(buffer)
+        }
+    }
 }
 
 macro_rules! impl_primitive_field {
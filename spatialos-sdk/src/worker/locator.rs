@@ -1,10 +1,14 @@
-use std::ffi::CString;
+use std::{
+    ffi::CString,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use futures::{Async, Future};
 
 use spatialos_sdk_sys::worker::*;
 
-use crate::worker::internal::utils::cstr_to_string;
+use crate::worker::internal::{background::BackgroundFuture, utils::cstr_to_string};
 use crate::worker::parameters::ProtocolLoggingParameters;
 
 pub struct Locator {
@@ -153,7 +157,7 @@ impl Deployment {
 
 pub struct DeploymentListFuture {
     internal: *mut Worker_DeploymentListFuture,
-    consumed: bool,
+    future: BackgroundFuture<Result<Vec<Deployment>, String>>,
 }
 
 impl DeploymentListFuture {
@@ -180,6 +184,24 @@ impl DeploymentListFuture {
             *data = Some(Ok(deployments));
         }
     }
+
+    fn get_future(
+        internal: *mut Worker_DeploymentListFuture,
+        timeout: Option<u32>,
+    ) -> Option<Result<Vec<Deployment>, String>> {
+        assert!(!internal.is_null());
+        let mut data: Option<Result<Vec<Deployment>, String>> = None;
+        unsafe {
+            Worker_DeploymentListFuture_Get(
+                internal,
+                timeout.as_ref().map_or(::std::ptr::null(), |value| value),
+                &mut data as *mut _ as *mut ::std::os::raw::c_void,
+                Some(DeploymentListFuture::callback_handler),
+            );
+        }
+
+        data
+    }
 }
 
 impl Future for DeploymentListFuture {
@@ -187,47 +209,50 @@ impl Future for DeploymentListFuture {
     type Error = String;
 
     fn poll(&mut self) -> Result<Async<<Self as Future>::Item>, <Self as Future>::Error> {
-        if self.consumed {
+        if self.future.is_consumed() || self.internal.is_null() {
             return Err("DeploymentListFuture has already been consumed".to_owned());
         }
 
-        assert!(!self.internal.is_null());
-        let mut data: Option<Result<Vec<Deployment>, String>> = None;
-        unsafe {
-            Worker_DeploymentListFuture_Get(
-                self.internal,
-                &0,
-                &mut data as *mut _ as *mut ::std::os::raw::c_void,
-                Some(DeploymentListFuture::callback_handler),
-            );
-        }
-
-        data.map_or(Ok(Async::NotReady), |result| {
-            self.consumed = true;
-            result.map(Async::Ready)
-        })
+        DeploymentListFuture::get_future(self.internal, Some(0)).map_or(
+            Ok(Async::NotReady),
+            |result| {
+                self.future.set_consumed();
+                result.map(Async::Ready)
+            },
+        )
     }
 
     fn wait(self) -> Result<<Self as Future>::Item, <Self as Future>::Error>
     where
         Self: Sized,
     {
-        if self.consumed {
+        if self.future.is_consumed() || self.internal.is_null() {
             return Err("DeploymentListFuture has already been consumed".to_owned());
         }
 
-        assert!(!self.internal.is_null());
-        let mut data: Option<Result<Vec<Deployment>, String>> = None;
-        unsafe {
-            Worker_DeploymentListFuture_Get(
-                self.internal,
-                ::std::ptr::null(),
-                &mut data as *mut _ as *mut ::std::os::raw::c_void,
-                Some(DeploymentListFuture::callback_handler),
-            );
-        }
+        DeploymentListFuture::get_future(self.internal, None)
+            .expect("Blocking call to Worker_DeploymentListFuture_Get did not trigger callback")
+    }
+}
 
-        data.expect("Blocking call to Worker_DeploymentListFuture_Get did not trigger callback")
+impl std::future::Future for DeploymentListFuture {
+    type Output = Result<Vec<Deployment>, String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.future
+            .poll(cx, &mut this.internal, |internal| {
+                let result = DeploymentListFuture::get_future(internal, None).unwrap_or_else(|| {
+                    Err("Blocking call to Worker_DeploymentListFuture_Get did not trigger callback".to_owned())
+                });
+                unsafe { Worker_DeploymentListFuture_Destroy(internal) };
+                result
+            })
+            .map(|result| match result {
+                Some(Ok(result)) => result,
+                Some(Err(panic_message)) => Err(panic_message),
+                None => Err("DeploymentListFuture has already been consumed".to_owned()),
+            })
     }
 }
 
@@ -319,7 +344,7 @@ impl PlayerIdentityTokenResponse {
 
 pub struct PlayerIdentityTokenFuture {
     internal: *mut Worker_Alpha_PlayerIdentityTokenResponseFuture,
-    consumed: bool,
+    future: BackgroundFuture<Result<PlayerIdentityTokenResponse, String>>,
 }
 
 impl PlayerIdentityTokenFuture {
@@ -327,7 +352,7 @@ impl PlayerIdentityTokenFuture {
         assert!(!ptr.is_null());
         PlayerIdentityTokenFuture {
             internal: ptr,
-            consumed: false,
+            future: BackgroundFuture::new(),
         }
     }
 
@@ -354,16 +379,16 @@ impl PlayerIdentityTokenFuture {
     }
 
     fn get_future(
-        &self,
+        internal: *mut Worker_Alpha_PlayerIdentityTokenResponseFuture,
         timeout: Option<u32>,
     ) -> Option<Result<PlayerIdentityTokenResponse, String>> {
-        assert!(!self.internal.is_null());
+        assert!(!internal.is_null());
 
         let mut data: Option<Result<PlayerIdentityTokenResponse, String>> = None;
         unsafe {
             Worker_Alpha_PlayerIdentityTokenResponseFuture_Get(
-                self.internal,
-                timeout.map_or(::std::ptr::null(), |value| &value),
+                internal,
+                timeout.as_ref().map_or(::std::ptr::null(), |value| value),
                 &mut data as *mut _ as *mut ::std::os::raw::c_void,
                 Some(PlayerIdentityTokenFuture::callback_handler),
             );
@@ -378,32 +403,58 @@ impl Future for PlayerIdentityTokenFuture {
     type Error = String;
 
     fn poll(&mut self) -> Result<Async<<Self as Future>::Item>, <Self as Future>::Error> {
-        if self.consumed {
+        if self.future.is_consumed() || self.internal.is_null() {
             return Err("PlayerIdentityTokenFuture has already been consumed".to_owned());
         }
 
-        self.get_future(Some(0))
-            .map_or(Ok(Async::NotReady), |result| {
-                self.consumed = true;
+        PlayerIdentityTokenFuture::get_future(self.internal, Some(0)).map_or(
+            Ok(Async::NotReady),
+            |result| {
+                self.future.set_consumed();
                 result.map(Async::Ready)
-            })
+            },
+        )
     }
 
     fn wait(self) -> Result<<Self as Future>::Item, <Self as Future>::Error>
     where
         Self: Sized,
     {
-        if self.consumed {
+        if self.future.is_consumed() || self.internal.is_null() {
             return Err("PlayerIdentityTokenFuture has already been consumed".to_owned());
         }
 
-        self.get_future(None).expect("Blocking call to Worker_Alpha_PlayerIdentityTokenResponseFuture_Get did not trigger callback")
+        PlayerIdentityTokenFuture::get_future(self.internal, None)
+            .expect("Blocking call to Worker_Alpha_PlayerIdentityTokenResponseFuture_Get did not trigger callback")
+    }
+}
+
+impl std::future::Future for PlayerIdentityTokenFuture {
+    type Output = Result<PlayerIdentityTokenResponse, String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.future
+            .poll(cx, &mut this.internal, |internal| {
+                let result = PlayerIdentityTokenFuture::get_future(internal, None).unwrap_or_else(|| {
+                    Err("Blocking call to Worker_Alpha_PlayerIdentityTokenResponseFuture_Get did not trigger callback".to_owned())
+                });
+                unsafe { Worker_Alpha_PlayerIdentityTokenResponseFuture_Destroy(internal) };
+                result
+            })
+            .map(|result| match result {
+                Some(Ok(result)) => result,
+                Some(Err(panic_message)) => Err(panic_message),
+                None => Err("PlayerIdentityTokenFuture has already been consumed".to_owned()),
+            })
     }
 }
 
 impl Drop for PlayerIdentityTokenFuture {
     fn drop(&mut self) {
-        unsafe { Worker_Alpha_PlayerIdentityTokenResponseFuture_Destroy(self.internal) }
+        if !self.internal.is_null() {
+            unsafe { Worker_Alpha_PlayerIdentityTokenResponseFuture_Destroy(self.internal) }
+        }
     }
 }
 
@@ -499,7 +550,7 @@ impl LoginTokenDetails {
 
 pub struct LoginTokensFuture {
     internal: *mut Worker_Alpha_LoginTokensResponseFuture,
-    consumed: bool,
+    future: BackgroundFuture<Result<LoginTokensResponse, String>>,
 }
 
 impl LoginTokensFuture {
@@ -507,7 +558,7 @@ impl LoginTokensFuture {
         assert!(!ptr.is_null());
         LoginTokensFuture {
             internal: ptr,
-            consumed: false,
+            future: BackgroundFuture::new(),
         }
     }
 
@@ -532,13 +583,16 @@ impl LoginTokensFuture {
         }
     }
 
-    fn get_future(&self, timeout: Option<u32>) -> Option<Result<LoginTokensResponse, String>> {
-        assert!(!self.internal.is_null());
+    fn get_future(
+        internal: *mut Worker_Alpha_LoginTokensResponseFuture,
+        timeout: Option<u32>,
+    ) -> Option<Result<LoginTokensResponse, String>> {
+        assert!(!internal.is_null());
         let mut data: Option<Result<LoginTokensResponse, String>> = None;
         unsafe {
             Worker_Alpha_LoginTokensResponseFuture_Get(
-                self.internal,
-                timeout.map_or(::std::ptr::null(), |value| &value),
+                internal,
+                timeout.as_ref().map_or(::std::ptr::null(), |value| value),
                 &mut data as *mut _ as *mut ::std::os::raw::c_void,
                 Some(LoginTokensFuture::callback_handler),
             );
@@ -553,32 +607,57 @@ impl Future for LoginTokensFuture {
     type Error = String;
 
     fn poll(&mut self) -> Result<Async<<Self as Future>::Item>, <Self as Future>::Error> {
-        if self.consumed {
+        if self.future.is_consumed() || self.internal.is_null() {
             return Err("LoginTokensFuture has already been consumed".to_owned());
         }
 
-        self.get_future(Some(0))
-            .map_or(Ok(Async::NotReady), |result| {
-                self.consumed = true;
+        LoginTokensFuture::get_future(self.internal, Some(0)).map_or(
+            Ok(Async::NotReady),
+            |result| {
+                self.future.set_consumed();
                 result.map(Async::Ready)
-            })
+            },
+        )
     }
 
     fn wait(self) -> Result<<Self as Future>::Item, <Self as Future>::Error>
     where
         Self: Sized,
     {
-        if self.consumed {
+        if self.future.is_consumed() || self.internal.is_null() {
             return Err("LoginTokensFuture has already been consumed".to_owned());
         }
 
-        self.get_future(None)
+        LoginTokensFuture::get_future(self.internal, None)
             .expect("Blocking call to Worker_Alpha_LoginTokensFuture_Get did not trigger callback")
     }
 }
 
+impl std::future::Future for LoginTokensFuture {
+    type Output = Result<LoginTokensResponse, String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.future
+            .poll(cx, &mut this.internal, |internal| {
+                let result = LoginTokensFuture::get_future(internal, None).unwrap_or_else(|| {
+                    Err("Blocking call to Worker_Alpha_LoginTokensFuture_Get did not trigger callback".to_owned())
+                });
+                unsafe { Worker_Alpha_LoginTokensResponseFuture_Destroy(internal) };
+                result
+            })
+            .map(|result| match result {
+                Some(Ok(result)) => result,
+                Some(Err(panic_message)) => Err(panic_message),
+                None => Err("LoginTokensFuture has already been consumed".to_owned()),
+            })
+    }
+}
+
 impl Drop for LoginTokensFuture {
     fn drop(&mut self) {
-        unsafe { Worker_Alpha_LoginTokensResponseFuture_Destroy(self.internal) }
+        if !self.internal.is_null() {
+            unsafe { Worker_Alpha_LoginTokensResponseFuture_Destroy(self.internal) }
+        }
     }
 }
@@ -0,0 +1,583 @@
+use crate::worker::{
+    commands::*,
+    component::{Component, ComponentId, HandleKind, OwnedHandle, UpdateParameters},
+    connection::{Connection, ConnectionStatus, ConnectionStatusCode},
+    entity::Entity,
+    metrics::Metrics,
+    op::*,
+    query::EntityQuery,
+    {Authority, EntityId, InterestOverride, LogLevel, RequestId},
+};
+use std::{
+    collections::HashMap,
+    ffi::{CString, NulError},
+};
+
+/// A [`Connection`] implementation that doesn't connect to SpatialOS.
+///
+/// `MockConnection` records every message sent through it so that tests can make
+/// assertions about the behaviour of worker logic, and allows tests to script the
+/// ops that are returned from [`get_op_list`].
+///
+/// # Examples
+///
+/// ```
+/// use spatialos_sdk::worker::{
+///     connection::Connection,
+///     mock::{MockConnection, SentMessage},
+///     op::WorkerOp,
+///     EntityId, LogLevel,
+/// };
+///
+/// let mut connection = MockConnection::new("TestWorker");
+/// connection.push_add_entity(EntityId::new(1));
+///
+/// for op in &connection.get_op_list(0) {
+///     if let WorkerOp::AddEntity(op) = op {
+///         connection.send_log_message(LogLevel::Info, "test", "Added entity", Some(op.entity_id));
+///     }
+/// }
+///
+/// match &connection.sent_messages()[0] {
+///     SentMessage::LogMessage { entity_id, .. } => assert_eq!(Some(EntityId::new(1)), *entity_id),
+///     _ => panic!("Expected a log message"),
+/// }
+/// ```
+///
+/// [`Connection`]: ../connection/trait.Connection.html
+/// [`get_op_list`]: ../connection/trait.Connection.html#tymethod.get_op_list
+#[derive(Debug)]
+pub struct MockConnection {
+    worker_id: String,
+    attributes: Vec<String>,
+    flags: HashMap<String, String>,
+    status: ConnectionStatus,
+    protocol_logging_enabled: bool,
+
+    pending_ops: Vec<OwnedWorkerOp>,
+    sent_messages: Vec<SentMessage>,
+    next_request_id: i64,
+}
+
+impl MockConnection {
+    pub fn new<T: Into<String>>(worker_id: T) -> Self {
+        MockConnection {
+            worker_id: worker_id.into(),
+            attributes: Vec::new(),
+            flags: HashMap::new(),
+            status: ConnectionStatus {
+                code: ConnectionStatusCode::Success,
+                detail: "OK".to_owned(),
+            },
+            protocol_logging_enabled: false,
+
+            pending_ops: Vec::new(),
+            sent_messages: Vec::new(),
+            next_request_id: 1,
+        }
+    }
+
+    pub fn with_attribute<T: Into<String>>(mut self, attribute: T) -> Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    pub fn set_worker_flag<T: Into<String>, U: Into<String>>(&mut self, name: T, value: U) {
+        self.flags.insert(name.into(), value.into());
+    }
+
+    pub fn set_connection_status(&mut self, status: ConnectionStatus) {
+        self.status = status;
+    }
+
+    pub fn is_protocol_logging_enabled(&self) -> bool {
+        self.protocol_logging_enabled
+    }
+
+    /// Queues an op to be returned from the next call to `get_op_list`.
+    pub fn push_op(&mut self, op: OwnedWorkerOp) {
+        self.pending_ops.push(op);
+    }
+
+    pub fn push_add_entity(&mut self, entity_id: EntityId) {
+        self.push_op(OwnedWorkerOp::AddEntity(AddEntityOp { entity_id }));
+    }
+
+    pub fn push_remove_entity(&mut self, entity_id: EntityId) {
+        self.push_op(OwnedWorkerOp::RemoveEntity(RemoveEntityOp { entity_id }));
+    }
+
+    pub fn push_add_component<C: Component>(&mut self, entity_id: EntityId, component: C) {
+        self.push_op(OwnedWorkerOp::AddComponent(OwnedAddComponentOp::new(
+            entity_id, component,
+        )));
+    }
+
+    pub fn push_remove_component<C: Component>(&mut self, entity_id: EntityId) {
+        self.push_op(OwnedWorkerOp::RemoveComponent(RemoveComponentOp {
+            entity_id,
+            component_id: C::ID,
+        }));
+    }
+
+    pub fn push_component_update<C: Component>(&mut self, entity_id: EntityId, update: C::Update) {
+        self.push_op(OwnedWorkerOp::ComponentUpdate(
+            OwnedComponentUpdateOp::new::<C>(entity_id, update),
+        ));
+    }
+
+    pub fn push_authority_change(
+        &mut self,
+        entity_id: EntityId,
+        component_id: ComponentId,
+        authority: Authority,
+    ) {
+        self.push_op(OwnedWorkerOp::AuthorityChange(AuthorityChangeOp {
+            entity_id,
+            component_id,
+            authority,
+        }));
+    }
+
+    /// Queues an incoming command request, returning the request ID that should be used
+    /// when responding to it.
+    pub fn push_command_request<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        request: C::CommandRequest,
+    ) -> RequestId<IncomingCommandRequest> {
+        let request_id = RequestId::new(self.next_request_id());
+        self.push_op(OwnedWorkerOp::CommandRequest(
+            OwnedCommandRequestOp::new::<C>(request_id, entity_id, request),
+        ));
+        request_id
+    }
+
+    /// Queues the response to a command request previously sent through this connection.
+    pub fn push_command_response<C: Component>(
+        &mut self,
+        request_id: RequestId<OutgoingCommandRequest>,
+        entity_id: EntityId,
+        response: StatusCode<C::CommandResponse>,
+    ) {
+        self.push_op(OwnedWorkerOp::CommandResponse(
+            OwnedCommandResponseOp::new::<C>(request_id, entity_id, response),
+        ));
+    }
+
+    /// Returns every message sent through this connection, in the order they were sent.
+    pub fn sent_messages(&self) -> &[SentMessage] {
+        &self.sent_messages
+    }
+
+    pub fn clear_sent_messages(&mut self) {
+        self.sent_messages.clear();
+    }
+
+    /// Returns the updates for component `C` sent through this connection, along with
+    /// the entity each was sent to.
+    pub fn sent_component_updates<C: Component>(&self) -> Vec<(EntityId, &C::Update)> {
+        self.sent_messages
+            .iter()
+            .filter_map(|message| match message {
+                SentMessage::ComponentUpdate(update) => {
+                    update.get::<C>().map(|data| (update.entity_id, data))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the command requests for component `C` sent through this connection,
+    /// along with the entity each was sent to.
+    pub fn sent_command_requests<C: Component>(&self) -> Vec<(EntityId, &C::CommandRequest)> {
+        self.sent_messages
+            .iter()
+            .filter_map(|message| match message {
+                SentMessage::CommandRequest(request) => {
+                    request.get::<C>().map(|data| (request.entity_id, data))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the command responses for component `C` sent through this connection,
+    /// along with the ID of the request each was responding to.
+    pub fn sent_command_responses<C: Component>(
+        &self,
+    ) -> Vec<(RequestId<IncomingCommandRequest>, &C::CommandResponse)> {
+        self.sent_messages
+            .iter()
+            .filter_map(|message| match message {
+                SentMessage::CommandResponse(response) => {
+                    response.get::<C>().map(|data| (response.request_id, data))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn next_request_id(&mut self) -> i64 {
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        id
+    }
+}
+
+impl Connection for MockConnection {
+    fn send_log_message(
+        &mut self,
+        level: LogLevel,
+        logger_name: &str,
+        message: &str,
+        entity_id: Option<EntityId>,
+    ) {
+        self.sent_messages.push(SentMessage::LogMessage {
+            level,
+            logger_name: logger_name.to_owned(),
+            message: message.to_owned(),
+            entity_id,
+        });
+    }
+
+    fn send_metrics(&mut self, metrics: &Metrics) {
+        self.sent_messages
+            .push(SentMessage::Metrics(metrics.clone()));
+    }
+
+    fn send_reserve_entity_ids_request(
+        &mut self,
+        payload: ReserveEntityIdsRequest,
+        timeout_millis: Option<u32>,
+    ) -> RequestId<ReserveEntityIdsRequest> {
+        let request_id = RequestId::new(self.next_request_id());
+        self.sent_messages
+            .push(SentMessage::ReserveEntityIdsRequest {
+                request_id,
+                number_of_entity_ids: payload.0,
+                timeout_millis,
+            });
+        request_id
+    }
+
+    fn send_create_entity_request(
+        &mut self,
+        entity: Entity,
+        entity_id: Option<EntityId>,
+        timeout_millis: Option<u32>,
+    ) -> RequestId<CreateEntityRequest> {
+        let request_id = RequestId::new(self.next_request_id());
+        self.sent_messages.push(SentMessage::CreateEntityRequest {
+            request_id,
+            entity,
+            entity_id,
+            timeout_millis,
+        });
+        request_id
+    }
+
+    fn send_delete_entity_request(
+        &mut self,
+        payload: DeleteEntityRequest,
+        timeout_millis: Option<u32>,
+    ) -> RequestId<DeleteEntityRequest> {
+        let request_id = RequestId::new(self.next_request_id());
+        self.sent_messages.push(SentMessage::DeleteEntityRequest {
+            request_id,
+            entity_id: payload.0,
+            timeout_millis,
+        });
+        request_id
+    }
+
+    fn send_entity_query_request(
+        &mut self,
+        payload: EntityQueryRequest,
+        timeout_millis: Option<u32>,
+    ) -> RequestId<EntityQueryRequest> {
+        let request_id = RequestId::new(self.next_request_id());
+        self.sent_messages.push(SentMessage::EntityQueryRequest {
+            request_id,
+            query: payload.0,
+            timeout_millis,
+        });
+        request_id
+    }
+
+    fn send_command_request<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        request: C::CommandRequest,
+        timeout_millis: Option<u32>,
+        params: CommandParameters,
+    ) -> RequestId<OutgoingCommandRequest> {
+        let request_id = RequestId::new(self.next_request_id());
+        let command_index = C::get_request_command_index(&request);
+        self.sent_messages
+            .push(SentMessage::CommandRequest(SentCommandRequest {
+                request_id,
+                entity_id,
+                component_id: C::ID,
+                timeout_millis,
+                params,
+                request: OwnedHandle::new(
+                    C::ID,
+                    HandleKind::CommandRequest(command_index),
+                    request,
+                ),
+            }));
+        request_id
+    }
+
+    fn send_command_response<C: Component>(
+        &mut self,
+        request_id: RequestId<IncomingCommandRequest>,
+        response: C::CommandResponse,
+    ) {
+        let command_index = C::get_response_command_index(&response);
+        self.sent_messages
+            .push(SentMessage::CommandResponse(SentCommandResponse {
+                request_id,
+                component_id: C::ID,
+                response: OwnedHandle::new(
+                    C::ID,
+                    HandleKind::CommandResponse(command_index),
+                    response,
+                ),
+            }));
+    }
+
+    fn send_command_failure(
+        &mut self,
+        request_id: RequestId<IncomingCommandRequest>,
+        message: &str,
+    ) -> Result<(), NulError> {
+        // Match the behaviour of the real connection, which rejects messages that
+        // can't be passed to the C API.
+        CString::new(message)?;
+        self.sent_messages.push(SentMessage::CommandFailure {
+            request_id,
+            message: message.to_owned(),
+        });
+        Ok(())
+    }
+
+    fn send_component_update<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        update: C::Update,
+        parameters: UpdateParameters,
+    ) {
+        self.sent_messages
+            .push(SentMessage::ComponentUpdate(SentComponentUpdate {
+                entity_id,
+                component_id: C::ID,
+                parameters,
+                update: OwnedHandle::new(C::ID, HandleKind::Update, update),
+            }));
+    }
+
+    fn send_add_component<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        component: C,
+        parameters: UpdateParameters,
+    ) {
+        self.sent_messages
+            .push(SentMessage::AddComponent(SentAddComponent {
+                entity_id,
+                component_id: C::ID,
+                parameters,
+                data: OwnedHandle::new(C::ID, HandleKind::Data, component),
+            }));
+    }
+
+    fn send_remove_component<C: Component>(
+        &mut self,
+        entity_id: EntityId,
+        parameters: UpdateParameters,
+    ) {
+        self.sent_messages.push(SentMessage::RemoveComponent {
+            entity_id,
+            component_id: C::ID,
+            parameters,
+        });
+    }
+
+    fn send_component_interest(
+        &mut self,
+        entity_id: EntityId,
+        interest_overrides: &[InterestOverride],
+    ) {
+        self.sent_messages.push(SentMessage::ComponentInterest {
+            entity_id,
+            interest_overrides: interest_overrides.to_vec(),
+        });
+    }
+
+    fn send_authority_loss_imminent_acknowledgement(
+        &mut self,
+        entity_id: EntityId,
+        component_id: u32,
+    ) {
+        self.sent_messages
+            .push(SentMessage::AuthorityLossImminentAcknowledgement {
+                entity_id,
+                component_id,
+            });
+    }
+
+    fn set_protocol_logging_enabled(&mut self, enabled: bool) {
+        self.protocol_logging_enabled = enabled;
+    }
+
+    fn get_connection_status(&mut self) -> ConnectionStatus {
+        self.status.clone()
+    }
+
+    fn get_worker_flag(&mut self, name: &str) -> Option<String> {
+        self.flags.get(name).cloned()
+    }
+
+    fn get_op_list(&mut self, _timeout_millis: u32) -> OpList {
+        OpList::from(self.pending_ops.drain(..).collect::<Vec<_>>())
+    }
+
+    fn get_worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    fn get_worker_attributes(&self) -> &[String] {
+        &self.attributes
+    }
+}
+
+/// A message sent through a [`MockConnection`].
+///
+/// [`MockConnection`]: struct.MockConnection.html
+#[derive(Debug)]
+pub enum SentMessage {
+    LogMessage {
+        level: LogLevel,
+        logger_name: String,
+        message: String,
+        entity_id: Option<EntityId>,
+    },
+    Metrics(Metrics),
+    ReserveEntityIdsRequest {
+        request_id: RequestId<ReserveEntityIdsRequest>,
+        number_of_entity_ids: u32,
+        timeout_millis: Option<u32>,
+    },
+    CreateEntityRequest {
+        request_id: RequestId<CreateEntityRequest>,
+        entity: Entity,
+        entity_id: Option<EntityId>,
+        timeout_millis: Option<u32>,
+    },
+    DeleteEntityRequest {
+        request_id: RequestId<DeleteEntityRequest>,
+        entity_id: EntityId,
+        timeout_millis: Option<u32>,
+    },
+    EntityQueryRequest {
+        request_id: RequestId<EntityQueryRequest>,
+        query: EntityQuery,
+        timeout_millis: Option<u32>,
+    },
+    CommandRequest(SentCommandRequest),
+    CommandResponse(SentCommandResponse),
+    CommandFailure {
+        request_id: RequestId<IncomingCommandRequest>,
+        message: String,
+    },
+    ComponentUpdate(SentComponentUpdate),
+    AddComponent(SentAddComponent),
+    RemoveComponent {
+        entity_id: EntityId,
+        component_id: ComponentId,
+        parameters: UpdateParameters,
+    },
+    ComponentInterest {
+        entity_id: EntityId,
+        interest_overrides: Vec<InterestOverride>,
+    },
+    AuthorityLossImminentAcknowledgement {
+        entity_id: EntityId,
+        component_id: ComponentId,
+    },
+}
+
+#[derive(Debug)]
+pub struct SentCommandRequest {
+    pub request_id: RequestId<OutgoingCommandRequest>,
+    pub entity_id: EntityId,
+    pub component_id: ComponentId,
+    pub timeout_millis: Option<u32>,
+    pub params: CommandParameters,
+    request: OwnedHandle,
+}
+
+impl SentCommandRequest {
+    pub fn get<C: Component>(&self) -> Option<&C::CommandRequest> {
+        if C::ID == self.component_id {
+            Some(unsafe { self.request.get() })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SentCommandResponse {
+    pub request_id: RequestId<IncomingCommandRequest>,
+    pub component_id: ComponentId,
+    response: OwnedHandle,
+}
+
+impl SentCommandResponse {
+    pub fn get<C: Component>(&self) -> Option<&C::CommandResponse> {
+        if C::ID == self.component_id {
+            Some(unsafe { self.response.get() })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SentComponentUpdate {
+    pub entity_id: EntityId,
+    pub component_id: ComponentId,
+    pub parameters: UpdateParameters,
+    update: OwnedHandle,
+}
+
+impl SentComponentUpdate {
+    pub fn get<C: Component>(&self) -> Option<&C::Update> {
+        if C::ID == self.component_id {
+            Some(unsafe { self.update.get() })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SentAddComponent {
+    pub entity_id: EntityId,
+    pub component_id: ComponentId,
+    pub parameters: UpdateParameters,
+    data: OwnedHandle,
+}
+
+impl SentAddComponent {
+    pub fn get<C: Component>(&self) -> Option<&C> {
+        if C::ID == self.component_id {
+            Some(unsafe { self.data.get() })
+        } else {
+            None
+        }
+    }
+}
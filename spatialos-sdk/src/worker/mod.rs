@@ -1,26 +1,38 @@
 pub mod internal;
 
+pub mod command_handlers;
 pub mod commands;
 pub mod component;
 pub mod connection;
+pub mod dispatcher;
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
 pub mod entity;
 pub mod entity_builder;
 pub mod locator;
 pub mod metrics;
+pub mod mock;
 pub mod op;
 pub mod parameters;
 pub mod query;
 pub mod snapshot;
+pub mod view;
 pub mod vtable;
 
 use component::ComponentId;
 use derivative::Derivative;
 use spatialos_sdk_sys::worker::Worker_InterestOverride;
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt::{Display, Error, Formatter};
 use std::marker::PhantomData;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct EntityId {
     pub id: i64,
 }
@@ -94,13 +106,18 @@ impl Authority {
     }
 }
 
-impl From<u8> for Authority {
-    fn from(auth: u8) -> Self {
+impl TryFrom<u8> for Authority {
+    type Error = crate::Error;
+
+    fn try_from(auth: u8) -> Result<Self, crate::Error> {
         match auth {
-            0 => Authority::NotAuthoritative,
-            1 => Authority::Authoritative,
-            2 => Authority::AuthorityLossImminent,
-            _ => panic!("Unknown authority state: {}", auth),
+            0 => Ok(Authority::NotAuthoritative),
+            1 => Ok(Authority::Authoritative),
+            2 => Ok(Authority::AuthorityLossImminent),
+            _ => Err(crate::Error::UnknownValue {
+                type_name: "Authority",
+                value: u32::from(auth),
+            }),
         }
     }
 }
@@ -142,6 +159,7 @@ impl From<u8> for LogLevel {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct InterestOverride {
     pub component_id: ComponentId,
     pub is_interested: bool,
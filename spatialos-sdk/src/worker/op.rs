@@ -6,24 +6,42 @@ use crate::worker::{
     entity::Entity,
     internal::{
         schema::{
-            SchemaCommandRequest, SchemaCommandResponse, SchemaComponentData, SchemaComponentUpdate,
+            SchemaCommandRequest, SchemaCommandResponse, SchemaComponentData,
+            SchemaComponentUpdate, SchemaRef,
         },
         utils::*,
     },
     metrics::Metrics,
     {Authority, EntityId, LogLevel, RequestId},
 };
+use crate::Error;
 use spatialos_sdk_sys::worker::*;
-use std::{collections::HashMap, slice};
+use std::{collections::HashMap, convert::TryFrom, slice};
 
+/// A list of ops received from SpatialOS.
+///
+/// An `OpList` is usually returned by [`Connection::get_op_list`], in which case it
+/// wraps the list of ops owned by the C API. It can also be created from a list of
+/// [`OwnedWorkerOp`]s, which allows ops to be constructed without a connection to
+/// SpatialOS (e.g. for testing purposes).
+///
+/// [`Connection::get_op_list`]: ../connection/trait.Connection.html#tymethod.get_op_list
+/// [`OwnedWorkerOp`]: enum.OwnedWorkerOp.html
 pub struct OpList {
-    raw: *mut Worker_OpList,
+    inner: OpListInner,
+}
+
+enum OpListInner {
+    Raw(*mut Worker_OpList),
+    Owned(Vec<OwnedWorkerOp>),
 }
 
 impl OpList {
     pub(crate) fn new(raw: *mut Worker_OpList) -> Self {
         assert!(!raw.is_null());
-        OpList { raw }
+        OpList {
+            inner: OpListInner::Raw(raw),
+        }
     }
 
     /// Returns an iterator over the list.
@@ -42,16 +60,24 @@ impl OpList {
     }
 
     /// Returns the number of ops in the list.
-    fn len(&self) -> usize {
-        self.raw().op_count as usize
+    pub fn len(&self) -> usize {
+        match &self.inner {
+            OpListInner::Raw(raw) => unsafe { (**raw).op_count as usize },
+            OpListInner::Owned(ops) => ops.len(),
+        }
     }
 
-    /// Returns a reference to the raw `Worker_OpList`.
-    ///
-    /// This is a simple helper to reduce the `unsafe` boilerplate in all the
-    /// places where we need to access data on the raw op list.
-    fn raw(&self) -> &Worker_OpList {
-        unsafe { &*self.raw }
+    /// Returns `true` if the list contains no ops.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<Vec<OwnedWorkerOp>> for OpList {
+    fn from(ops: Vec<OwnedWorkerOp>) -> Self {
+        OpList {
+            inner: OpListInner::Owned(ops),
+        }
     }
 }
 
@@ -60,33 +86,54 @@ impl<'a> IntoIterator for &'a OpList {
     type IntoIter = Iter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let slice = unsafe { slice::from_raw_parts(self.raw().ops, self.len()) };
-        Iter { iter: slice.iter() }
+        let inner = match &self.inner {
+            OpListInner::Raw(raw) => {
+                let slice = unsafe { slice::from_raw_parts((**raw).ops, self.len()) };
+                IterInner::Raw(slice.iter())
+            }
+            OpListInner::Owned(ops) => IterInner::Owned(ops.iter()),
+        };
+
+        Iter { inner }
     }
 }
 
+// SAFE: The op list owns its ops and doesn't share them with the connection it came
+// from, so it can be moved between threads.
+unsafe impl Send for OpList {}
+
 impl Drop for OpList {
     fn drop(&mut self) {
-        assert!(!self.raw.is_null());
-        unsafe {
-            Worker_OpList_Destroy(self.raw);
+        if let OpListInner::Raw(raw) = self.inner {
+            assert!(!raw.is_null());
+            unsafe {
+                Worker_OpList_Destroy(raw);
+            }
         }
     }
 }
 
 pub struct Iter<'a> {
-    iter: slice::Iter<'a, Worker_Op>,
+    inner: IterInner<'a>,
+}
+
+enum IterInner<'a> {
+    Raw(slice::Iter<'a, Worker_Op>),
+    Owned(slice::Iter<'a, OwnedWorkerOp>),
 }
 
 impl<'a> Iterator for Iter<'a> {
     type Item = WorkerOp<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(WorkerOp::from)
+        match &mut self.inner {
+            IterInner::Raw(iter) => iter.next().map(WorkerOp::from),
+            IterInner::Owned(iter) => iter.next().map(WorkerOp::from),
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum StatusCode<T> {
     Success(T),
     Timeout(String),
@@ -97,6 +144,53 @@ pub enum StatusCode<T> {
     InternalError(String),
 }
 
+impl<T> StatusCode<T> {
+    /// Converts the payload of a successful status code, keeping the message of any other
+    /// status code.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> StatusCode<U> {
+        self.and_then(|value| StatusCode::Success(f(value)))
+    }
+
+    /// Replaces a successful status code with the result of `f`, keeping the message of any
+    /// other status code.
+    pub fn and_then<U, F: FnOnce(T) -> StatusCode<U>>(self, f: F) -> StatusCode<U> {
+        match self {
+            StatusCode::Success(value) => f(value),
+            StatusCode::Timeout(message) => StatusCode::Timeout(message),
+            StatusCode::NotFound(message) => StatusCode::NotFound(message),
+            StatusCode::AuthorityLost(message) => StatusCode::AuthorityLost(message),
+            StatusCode::PermissionDenied(message) => StatusCode::PermissionDenied(message),
+            StatusCode::ApplicationError(message) => StatusCode::ApplicationError(message),
+            StatusCode::InternalError(message) => StatusCode::InternalError(message),
+        }
+    }
+
+    /// Borrows the payload of a successful status code, cloning the message of any other
+    /// status code.
+    pub fn as_ref(&self) -> StatusCode<&T> {
+        match self {
+            StatusCode::Success(value) => StatusCode::Success(value),
+            StatusCode::Timeout(message) => StatusCode::Timeout(message.clone()),
+            StatusCode::NotFound(message) => StatusCode::NotFound(message.clone()),
+            StatusCode::AuthorityLost(message) => StatusCode::AuthorityLost(message.clone()),
+            StatusCode::PermissionDenied(message) => StatusCode::PermissionDenied(message.clone()),
+            StatusCode::ApplicationError(message) => StatusCode::ApplicationError(message.clone()),
+            StatusCode::InternalError(message) => StatusCode::InternalError(message.clone()),
+        }
+    }
+}
+
+impl<T, E> StatusCode<Result<T, E>> {
+    /// Converts a successful status code holding a result into a result holding a status
+    /// code, returning the error if the conversion failed.
+    pub fn transpose(self) -> Result<StatusCode<T>, E> {
+        match self {
+            StatusCode::Success(result) => result.map(StatusCode::Success),
+            other => Ok(other.map(|_| unreachable!())),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum WorkerOp<'a> {
     Disconnect(DisconnectOp),
@@ -116,13 +210,84 @@ pub enum WorkerOp<'a> {
     CreateEntityResponse(CreateEntityResponseOp),
     DeleteEntityResponse(DeleteEntityResponseOp),
     EntityQueryResponse(EntityQueryResponseOp),
+    Unknown(UnknownOp),
+}
+
+impl<'a> WorkerOp<'a> {
+    /// Creates an owned copy of the op that no longer borrows from the [`OpList`].
+    ///
+    /// Component data, updates, and command payloads are copied through the vtables
+    /// registered for their components, so the component for any such op must be
+    /// known to the component database. Returns an error if the component has no vtable
+    /// or its schema data fails to deserialize.
+    ///
+    /// [`OpList`]: struct.OpList.html
+    pub fn to_owned(&self) -> Result<OwnedWorkerOp, Error> {
+        let op = match self {
+            WorkerOp::Disconnect(op) => OwnedWorkerOp::Disconnect(op.clone()),
+            WorkerOp::FlagUpdate(op) => OwnedWorkerOp::FlagUpdate(op.clone()),
+            WorkerOp::LogMessage(op) => OwnedWorkerOp::LogMessage(op.clone()),
+            WorkerOp::Metrics(op) => OwnedWorkerOp::Metrics(op.clone()),
+            WorkerOp::CriticalSection(op) => OwnedWorkerOp::CriticalSection(op.clone()),
+            WorkerOp::AddEntity(op) => OwnedWorkerOp::AddEntity(op.clone()),
+            WorkerOp::RemoveEntity(op) => OwnedWorkerOp::RemoveEntity(op.clone()),
+            WorkerOp::AddComponent(op) => OwnedWorkerOp::AddComponent(OwnedAddComponentOp {
+                entity_id: op.entity_id,
+                component_id: op.component_id,
+                component_data: op.component_data.to_owned_handle()?,
+            }),
+            WorkerOp::RemoveComponent(op) => OwnedWorkerOp::RemoveComponent(op.clone()),
+            WorkerOp::ComponentUpdate(op) => {
+                OwnedWorkerOp::ComponentUpdate(OwnedComponentUpdateOp {
+                    entity_id: op.entity_id,
+                    component_id: op.component_id,
+                    component_update: op.component_update.to_owned_handle()?,
+                })
+            }
+            WorkerOp::AuthorityChange(op) => OwnedWorkerOp::AuthorityChange(op.clone()),
+            WorkerOp::CommandRequest(op) => OwnedWorkerOp::CommandRequest(OwnedCommandRequestOp {
+                request_id: op.request_id,
+                entity_id: op.entity_id,
+                timeout_millis: op.timeout_millis,
+                caller_worker_id: op.caller_worker_id.clone(),
+                caller_attribute_set: op.caller_attribute_set.clone(),
+                component_id: op.component_id,
+                request: op.request.to_owned_handle()?,
+            }),
+            WorkerOp::CommandResponse(op) => {
+                OwnedWorkerOp::CommandResponse(OwnedCommandResponseOp {
+                    request_id: op.request_id,
+                    entity_id: op.entity_id,
+                    component_id: op.component_id,
+                    response: op
+                        .response
+                        .as_ref()
+                        .map(|response| {
+                            response
+                                .response
+                                .to_owned_handle()
+                                .map(|response| OwnedCommandResponse { response })
+                        })
+                        .transpose()?,
+                })
+            }
+            WorkerOp::ReserveEntityIdsResponse(op) => {
+                OwnedWorkerOp::ReserveEntityIdsResponse(op.clone())
+            }
+            WorkerOp::CreateEntityResponse(op) => OwnedWorkerOp::CreateEntityResponse(op.clone()),
+            WorkerOp::DeleteEntityResponse(op) => OwnedWorkerOp::DeleteEntityResponse(op.clone()),
+            WorkerOp::EntityQueryResponse(op) => OwnedWorkerOp::EntityQueryResponse(op.clone()),
+            WorkerOp::Unknown(op) => OwnedWorkerOp::Unknown(op.clone()),
+        };
+        Ok(op)
+    }
 }
 
 impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
     fn from(op: &'a Worker_Op) -> Self {
         unsafe {
             let erased_op = &op.op;
-            let op_type = Worker_StatusCode::from(op.op_type);
+            let op_type = Worker_OpType::from(op.op_type);
             match op_type {
                 Worker_OpType_WORKER_OP_TYPE_DISCONNECT => {
                     let op = erased_op.disconnect;
@@ -194,10 +359,19 @@ impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
                 }
                 Worker_OpType_WORKER_OP_TYPE_AUTHORITY_CHANGE => {
                     let op = erased_op.authority_change;
+                    let authority = match Authority::try_from(op.authority) {
+                        Ok(authority) => authority,
+                        Err(error) => {
+                            return WorkerOp::Unknown(UnknownOp {
+                                op_type,
+                                message: error.to_string(),
+                            })
+                        }
+                    };
                     let authority_change_op = AuthorityChangeOp {
                         entity_id: EntityId::new(op.entity_id),
                         component_id: op.component_id,
-                        authority: Authority::from(op.authority),
+                        authority,
                     };
                     WorkerOp::AuthorityChange(authority_change_op)
                 }
@@ -230,35 +404,12 @@ impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
                 }
                 Worker_OpType_WORKER_OP_TYPE_COMMAND_RESPONSE => {
                     let op = &erased_op.command_response;
-                    let status_code = match Worker_StatusCode::from(op.status_code) {
-                        Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS => {
+                    let status_code =
+                        status_code_from_worker_sdk(op.status_code, op.message, || {
                             StatusCode::Success(CommandResponse {
                                 response: internal::CommandResponse::from(&op.response),
                             })
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_TIMEOUT => {
-                            StatusCode::Timeout(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_NOT_FOUND => {
-                            StatusCode::NotFound(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_AUTHORITY_LOST => {
-                            StatusCode::AuthorityLost(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_PERMISSION_DENIED => {
-                            StatusCode::PermissionDenied(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_APPLICATION_ERROR => {
-                            StatusCode::ApplicationError(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_INTERNAL_ERROR => {
-                            StatusCode::InternalError(cstr_to_string(op.message))
-                        }
-                        _ => panic!(
-                            "Unknown command response status code received: {}",
-                            op.status_code
-                        ),
-                    };
+                        });
 
                     let command_response_op = CommandResponseOp {
                         entity_id: EntityId::new(op.entity_id),
@@ -270,33 +421,13 @@ impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
                 }
                 Worker_OpType_WORKER_OP_TYPE_RESERVE_ENTITY_IDS_RESPONSE => {
                     let op = erased_op.reserve_entity_ids_response;
-                    let status_code = match Worker_StatusCode::from(op.status_code) {
-                        Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS => StatusCode::Success(
-                            ReservedEntityIdRange::new(op.first_entity_id, op.number_of_entity_ids),
-                        ),
-                        Worker_StatusCode_WORKER_STATUS_CODE_TIMEOUT => {
-                            StatusCode::Timeout(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_NOT_FOUND => {
-                            StatusCode::NotFound(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_AUTHORITY_LOST => {
-                            StatusCode::AuthorityLost(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_PERMISSION_DENIED => {
-                            StatusCode::PermissionDenied(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_APPLICATION_ERROR => {
-                            StatusCode::ApplicationError(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_INTERNAL_ERROR => {
-                            StatusCode::InternalError(cstr_to_string(op.message))
-                        }
-                        _ => panic!(
-                            "Unknown command response status code received: {}",
-                            op.status_code
-                        ),
-                    };
+                    let status_code =
+                        status_code_from_worker_sdk(op.status_code, op.message, || {
+                            StatusCode::Success(ReservedEntityIdRange::new(
+                                op.first_entity_id,
+                                op.number_of_entity_ids,
+                            ))
+                        });
 
                     let reserve_entity_ids_response_op = ReserveEntityIdsResponseOp {
                         request_id: RequestId::new(op.request_id),
@@ -306,33 +437,10 @@ impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
                 }
                 Worker_OpType_WORKER_OP_TYPE_CREATE_ENTITY_RESPONSE => {
                     let op = erased_op.create_entity_response;
-                    let status_code = match Worker_StatusCode::from(op.status_code) {
-                        Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS => {
+                    let status_code =
+                        status_code_from_worker_sdk(op.status_code, op.message, || {
                             StatusCode::Success(EntityId::new(op.entity_id))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_TIMEOUT => {
-                            StatusCode::Timeout(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_NOT_FOUND => {
-                            StatusCode::NotFound(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_AUTHORITY_LOST => {
-                            StatusCode::AuthorityLost(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_PERMISSION_DENIED => {
-                            StatusCode::PermissionDenied(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_APPLICATION_ERROR => {
-                            StatusCode::ApplicationError(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_INTERNAL_ERROR => {
-                            StatusCode::InternalError(cstr_to_string(op.message))
-                        }
-                        _ => panic!(
-                            "Unknown command response status code received: {}",
-                            op.status_code
-                        ),
-                    };
+                        });
 
                     let create_entity_response_op = CreateEntityResponseOp {
                         request_id: RequestId::new(op.request_id),
@@ -342,31 +450,10 @@ impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
                 }
                 Worker_OpType_WORKER_OP_TYPE_DELETE_ENTITY_RESPONSE => {
                     let op = erased_op.delete_entity_response;
-                    let status_code = match Worker_StatusCode::from(op.status_code) {
-                        Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS => StatusCode::Success(()),
-                        Worker_StatusCode_WORKER_STATUS_CODE_TIMEOUT => {
-                            StatusCode::Timeout(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_NOT_FOUND => {
-                            StatusCode::NotFound(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_AUTHORITY_LOST => {
-                            StatusCode::AuthorityLost(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_PERMISSION_DENIED => {
-                            StatusCode::PermissionDenied(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_APPLICATION_ERROR => {
-                            StatusCode::ApplicationError(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_INTERNAL_ERROR => {
-                            StatusCode::InternalError(cstr_to_string(op.message))
-                        }
-                        _ => panic!(
-                            "Unknown command response status code received: {}",
-                            op.status_code
-                        ),
-                    };
+                    let status_code =
+                        status_code_from_worker_sdk(op.status_code, op.message, || {
+                            StatusCode::Success(())
+                        });
 
                     let delete_entity_response_op = DeleteEntityResponseOp {
                         request_id: RequestId::new(op.request_id),
@@ -377,49 +464,31 @@ impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
                 }
                 Worker_OpType_WORKER_OP_TYPE_ENTITY_QUERY_RESPONSE => {
                     let op = erased_op.entity_query_response;
-                    let status_code = match Worker_StatusCode::from(op.status_code) {
-                        Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS => {
+                    let status_code =
+                        status_code_from_worker_sdk(op.status_code, op.message, || {
                             if op.results.is_null() {
                                 // Is count type.
-                                StatusCode::Success(QueryResponse::Result(op.result_count))
-                            } else {
-                                let mut entities = HashMap::new();
-                                let raw_entities =
-                                    slice::from_raw_parts(op.results, op.result_count as usize);
-
-                                for raw_entity in raw_entities {
-                                    entities.insert(
-                                        EntityId::new(raw_entity.entity_id),
-                                        Entity::from_worker_sdk(raw_entity).unwrap(),
-                                    );
-                                }
+                                return StatusCode::Success(QueryResponse::Result(op.result_count));
+                            }
 
-                                StatusCode::Success(QueryResponse::Snapshot(entities))
+                            let mut entities = HashMap::new();
+                            let raw_entities =
+                                slice::from_raw_parts(op.results, op.result_count as usize);
+
+                            for raw_entity in raw_entities {
+                                match Entity::from_worker_sdk(raw_entity) {
+                                    Ok(entity) => {
+                                        entities
+                                            .insert(EntityId::new(raw_entity.entity_id), entity);
+                                    }
+                                    Err(error) => {
+                                        return StatusCode::InternalError(error.to_string())
+                                    }
+                                }
                             }
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_TIMEOUT => {
-                            StatusCode::Timeout(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_NOT_FOUND => {
-                            StatusCode::NotFound(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_AUTHORITY_LOST => {
-                            StatusCode::AuthorityLost(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_PERMISSION_DENIED => {
-                            StatusCode::PermissionDenied(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_APPLICATION_ERROR => {
-                            StatusCode::ApplicationError(cstr_to_string(op.message))
-                        }
-                        Worker_StatusCode_WORKER_STATUS_CODE_INTERNAL_ERROR => {
-                            StatusCode::InternalError(cstr_to_string(op.message))
-                        }
-                        _ => panic!(
-                            "Unknown command response status code received: {}",
-                            op.status_code
-                        ),
-                    };
+
+                            StatusCode::Success(QueryResponse::Snapshot(entities))
+                        });
 
                     let entity_query_response_op = EntityQueryResponseOp {
                         request_id: RequestId::new(op.request_id),
@@ -428,50 +497,103 @@ impl<'a> From<&'a Worker_Op> for WorkerOp<'a> {
 
                     WorkerOp::EntityQueryResponse(entity_query_response_op)
                 }
-                _ => panic!("Unknown op code received: {}", op_type),
+                _ => WorkerOp::Unknown(UnknownOp {
+                    op_type,
+                    message: format!("Unknown op type received: {}", op_type),
+                }),
             }
         }
     }
 }
 
-#[derive(Debug)]
+// Converts a status code and message received from the C API. `success` is only called if
+// the status code is `WORKER_STATUS_CODE_SUCCESS`. Status codes that aren't known to this
+// version of the SDK are reported as internal errors, rather than failing the whole op.
+unsafe fn status_code_from_worker_sdk<T, F>(
+    status_code: u8,
+    message: *const std::os::raw::c_char,
+    success: F,
+) -> StatusCode<T>
+where
+    F: FnOnce() -> StatusCode<T>,
+{
+    match Worker_StatusCode::from(status_code) {
+        Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS => success(),
+        Worker_StatusCode_WORKER_STATUS_CODE_TIMEOUT => {
+            StatusCode::Timeout(cstr_to_string(message))
+        }
+        Worker_StatusCode_WORKER_STATUS_CODE_NOT_FOUND => {
+            StatusCode::NotFound(cstr_to_string(message))
+        }
+        Worker_StatusCode_WORKER_STATUS_CODE_AUTHORITY_LOST => {
+            StatusCode::AuthorityLost(cstr_to_string(message))
+        }
+        Worker_StatusCode_WORKER_STATUS_CODE_PERMISSION_DENIED => {
+            StatusCode::PermissionDenied(cstr_to_string(message))
+        }
+        Worker_StatusCode_WORKER_STATUS_CODE_APPLICATION_ERROR => {
+            StatusCode::ApplicationError(cstr_to_string(message))
+        }
+        Worker_StatusCode_WORKER_STATUS_CODE_INTERNAL_ERROR => {
+            StatusCode::InternalError(cstr_to_string(message))
+        }
+        _ => StatusCode::InternalError(format!(
+            "Unknown status code received: {}. {}",
+            status_code,
+            cstr_to_string(message)
+        )),
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct DisconnectOp {
     pub reason: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FlagUpdateOp {
     pub name: String,
     pub value: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LogMessageOp {
     pub message: String,
     pub log_level: LogLevel,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MetricsOp {
     pub metrics: Metrics,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CriticalSectionOp {
     pub in_critical_section: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AddEntityOp {
     pub entity_id: EntityId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RemoveEntityOp {
     pub entity_id: EntityId,
 }
 
-#[derive(Debug)]
+/// An op that couldn't be converted, usually because it was sent by a newer version of the
+/// C API than this SDK supports.
+///
+/// Unknown ops can be logged and otherwise ignored, rather than stopping the worker.
+#[derive(Debug, Clone)]
+pub struct UnknownOp {
+    /// The raw `Worker_OpType` of the op.
+    pub op_type: i32,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct ReserveEntityIdsResponseOp {
     pub request_id: RequestId<ReserveEntityIdsRequest>,
     pub status_code: StatusCode<ReservedEntityIdRange>,
@@ -479,7 +601,7 @@ pub struct ReserveEntityIdsResponseOp {
 
 // TODO: When https://doc.rust-lang.org/std/iter/trait.Step.html is stabilized - replace this
 //       with std::ops::Range<EntityId> and implement Step for EntityId.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReservedEntityIdRange {
     current: i64,
     consumed: u32,
@@ -487,7 +609,7 @@ pub struct ReservedEntityIdRange {
 }
 
 impl ReservedEntityIdRange {
-    pub(crate) fn new(first: i64, number: u32) -> Self {
+    pub fn new(first: i64, number: u32) -> Self {
         ReservedEntityIdRange {
             current: first,
             consumed: 0,
@@ -512,26 +634,26 @@ impl Iterator for ReservedEntityIdRange {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CreateEntityResponseOp {
     pub request_id: RequestId<CreateEntityRequest>,
     pub status_code: StatusCode<EntityId>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeleteEntityResponseOp {
     pub request_id: RequestId<DeleteEntityRequest>,
     pub entity_id: EntityId,
     pub status_code: StatusCode<()>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum QueryResponse {
     Snapshot(HashMap<EntityId, Entity>),
     Result(u32),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EntityQueryResponseOp {
     pub request_id: RequestId<EntityQueryRequest>,
     pub status_code: StatusCode<QueryResponse>,
@@ -541,31 +663,42 @@ pub struct EntityQueryResponseOp {
 pub struct AddComponentOp<'a> {
     pub entity_id: EntityId,
     pub component_id: ComponentId,
-    component_data: component::internal::ComponentData<'a>,
+    pub(crate) component_data: component::internal::ComponentData<'a>,
 }
 
 impl<'a> AddComponentOp<'a> {
     pub fn get<C: Component>(&self) -> Option<&C> {
-        if C::ID == self.component_data.component_id {
-            // TODO: Deserialize schema_type if user_handle is null.
-            Some(unsafe { &*(self.component_data.user_handle as *const _) })
-        } else {
+        // The ID is checked first, so that the payload isn't deserialized for the wrong
+        // component.
+        if C::ID != self.component_data.component_id {
+            return None;
+        }
+
+        let handle = self.component_data.handle();
+        if handle.is_null() {
             None
+        } else {
+            Some(unsafe { &*(handle as *const _) })
         }
     }
 
-    fn schema(&self) -> &SchemaComponentData {
-        &self.component_data.schema_type
+    /// Returns the schema data of the component.
+    ///
+    /// Unlike [`get`](#method.get), this works for any component, including components
+    /// without generated code. If the op only has user data, the data is serialized
+    /// through the component's vtable.
+    pub fn schema(&self) -> Result<SchemaRef<'_, SchemaComponentData>, Error> {
+        self.component_data.schema()
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RemoveComponentOp {
     pub entity_id: EntityId,
     pub component_id: ComponentId,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AuthorityChangeOp {
     pub entity_id: EntityId,
     pub component_id: ComponentId,
@@ -581,18 +714,25 @@ pub struct ComponentUpdateOp<'a> {
 
 impl<'a> ComponentUpdateOp<'a> {
     pub fn get<C: Component>(&self) -> Option<&C::Update> {
-        // TODO: Deserialize schema_type if user_handle is null.
-        if C::ID == self.component_update.component_id
-            && !self.component_update.user_handle.is_null()
-        {
-            Some(unsafe { &*(self.component_update.user_handle as *const _) })
-        } else {
+        if C::ID != self.component_update.component_id {
+            return None;
+        }
+
+        let handle = self.component_update.handle();
+        if handle.is_null() {
             None
+        } else {
+            Some(unsafe { &*(handle as *const _) })
         }
     }
 
-    fn schema(&self) -> &SchemaComponentUpdate {
-        &self.component_update.schema_type
+    /// Returns the schema data of the update.
+    ///
+    /// Unlike [`get`](#method.get), this works for any component, including components
+    /// without generated code. If the op only has user data, the update is serialized
+    /// through the component's vtable.
+    pub fn schema(&self) -> Result<SchemaRef<'_, SchemaComponentUpdate>, Error> {
+        self.component_update.schema()
     }
 }
 
@@ -609,16 +749,34 @@ pub struct CommandRequestOp<'a> {
 
 impl<'a> CommandRequestOp<'a> {
     pub fn get<C: Component>(&self) -> Option<&C::CommandRequest> {
-        // TODO: Deserialize schema_type if user_handle is null.
-        if C::ID == self.component_id && !self.request.user_handle.is_null() {
-            Some(unsafe { &*(self.request.user_handle as *const _) })
-        } else {
+        if C::ID != self.component_id {
+            return None;
+        }
+
+        let handle = self.request.handle();
+        if handle.is_null() {
             None
+        } else {
+            Some(unsafe { &*(handle as *const _) })
         }
     }
 
-    fn schema(&self) -> &SchemaCommandRequest {
-        &self.request.schema_type
+    /// Returns the request if it is for command `Cmd`.
+    pub fn get_command<Cmd: Command>(&self) -> Option<&Cmd::Request> {
+        self.get::<Cmd::Component>().and_then(Cmd::request)
+    }
+
+    pub fn command_index(&self) -> CommandIndex {
+        self.request.command_index
+    }
+
+    /// Returns the schema data of the request.
+    ///
+    /// Unlike [`get`](#method.get), this works for any component, including components
+    /// without generated code. If the op only has user data, the request is serialized
+    /// through the component's vtable.
+    pub fn schema(&self) -> Result<SchemaRef<'_, SchemaCommandRequest>, Error> {
+        self.request.schema()
     }
 }
 
@@ -637,22 +795,279 @@ pub struct CommandResponse<'a> {
 
 impl<'a> CommandResponse<'a> {
     pub fn get<C: Component>(&self) -> Option<&C::CommandResponse> {
-        // TODO: Deserialize schema_type if user_handle is null.
-        if C::ID == self.response.component_id && !self.response.user_handle.is_null() {
-            Some(unsafe { &*(self.response.user_handle as *const _) })
+        if C::ID != self.response.component_id {
+            return None;
+        }
+
+        let handle = self.response.handle();
+        if handle.is_null() {
+            None
+        } else {
+            Some(unsafe { &*(handle as *const _) })
+        }
+    }
+
+    /// Returns the response if it is for command `Cmd`.
+    pub fn get_command<Cmd: Command>(&self) -> Option<&Cmd::Response> {
+        self.get::<Cmd::Component>().and_then(Cmd::response)
+    }
+
+    /// Returns the schema data of the response.
+    ///
+    /// Unlike [`get`](#method.get), this works for any component, including components
+    /// without generated code. If the op only has user data, the response is serialized
+    /// through the component's vtable.
+    pub fn schema(&self) -> Result<SchemaRef<'_, SchemaCommandResponse>, Error> {
+        self.response.schema()
+    }
+
+    /// Returns the index of the command the response is for.
+    pub fn command_index(&self) -> CommandIndex {
+        self.response.command_index
+    }
+}
+
+/// An owned version of [`WorkerOp`].
+///
+/// Unlike [`WorkerOp`], an `OwnedWorkerOp` doesn't borrow from an [`OpList`], so it can
+/// be stored after the list is dropped or sent to another thread. An owned op can be
+/// created from a [`WorkerOp`] with [`WorkerOp::to_owned`]. A list of owned ops can be
+/// converted into an [`OpList`], which makes it possible to feed ops into worker logic
+/// without a connection to SpatialOS.
+///
+/// [`WorkerOp`]: enum.WorkerOp.html
+/// [`WorkerOp::to_owned`]: enum.WorkerOp.html#method.to_owned
+/// [`OpList`]: struct.OpList.html
+#[derive(Debug, Clone)]
+pub enum OwnedWorkerOp {
+    Disconnect(DisconnectOp),
+    FlagUpdate(FlagUpdateOp),
+    LogMessage(LogMessageOp),
+    Metrics(MetricsOp),
+    CriticalSection(CriticalSectionOp),
+    AddEntity(AddEntityOp),
+    RemoveEntity(RemoveEntityOp),
+    AddComponent(OwnedAddComponentOp),
+    RemoveComponent(RemoveComponentOp),
+    ComponentUpdate(OwnedComponentUpdateOp),
+    AuthorityChange(AuthorityChangeOp),
+    CommandRequest(OwnedCommandRequestOp),
+    CommandResponse(OwnedCommandResponseOp),
+    ReserveEntityIdsResponse(ReserveEntityIdsResponseOp),
+    CreateEntityResponse(CreateEntityResponseOp),
+    DeleteEntityResponse(DeleteEntityResponseOp),
+    EntityQueryResponse(EntityQueryResponseOp),
+    Unknown(UnknownOp),
+}
+
+impl<'a> From<&'a OwnedWorkerOp> for WorkerOp<'a> {
+    fn from(op: &'a OwnedWorkerOp) -> Self {
+        match op {
+            OwnedWorkerOp::Disconnect(op) => WorkerOp::Disconnect(op.clone()),
+            OwnedWorkerOp::FlagUpdate(op) => WorkerOp::FlagUpdate(op.clone()),
+            OwnedWorkerOp::LogMessage(op) => WorkerOp::LogMessage(op.clone()),
+            OwnedWorkerOp::Metrics(op) => WorkerOp::Metrics(op.clone()),
+            OwnedWorkerOp::CriticalSection(op) => WorkerOp::CriticalSection(op.clone()),
+            OwnedWorkerOp::AddEntity(op) => WorkerOp::AddEntity(op.clone()),
+            OwnedWorkerOp::RemoveEntity(op) => WorkerOp::RemoveEntity(op.clone()),
+            OwnedWorkerOp::AddComponent(op) => WorkerOp::AddComponent(AddComponentOp {
+                entity_id: op.entity_id,
+                component_id: op.component_id,
+                component_data: internal::ComponentData::from(&op.component_data),
+            }),
+            OwnedWorkerOp::RemoveComponent(op) => WorkerOp::RemoveComponent(op.clone()),
+            OwnedWorkerOp::ComponentUpdate(op) => WorkerOp::ComponentUpdate(ComponentUpdateOp {
+                entity_id: op.entity_id,
+                component_id: op.component_id,
+                component_update: internal::ComponentUpdate::from(&op.component_update),
+            }),
+            OwnedWorkerOp::AuthorityChange(op) => WorkerOp::AuthorityChange(op.clone()),
+            OwnedWorkerOp::CommandRequest(op) => WorkerOp::CommandRequest(CommandRequestOp {
+                request_id: op.request_id,
+                entity_id: op.entity_id,
+                timeout_millis: op.timeout_millis,
+                caller_worker_id: op.caller_worker_id.clone(),
+                caller_attribute_set: op.caller_attribute_set.clone(),
+                component_id: op.component_id,
+                request: internal::CommandRequest::from(&op.request),
+            }),
+            OwnedWorkerOp::CommandResponse(op) => WorkerOp::CommandResponse(CommandResponseOp {
+                request_id: op.request_id,
+                entity_id: op.entity_id,
+                component_id: op.component_id,
+                response: op.response.as_ref().map(|response| CommandResponse {
+                    response: internal::CommandResponse::from(&response.response),
+                }),
+            }),
+            OwnedWorkerOp::ReserveEntityIdsResponse(op) => {
+                WorkerOp::ReserveEntityIdsResponse(op.clone())
+            }
+            OwnedWorkerOp::CreateEntityResponse(op) => WorkerOp::CreateEntityResponse(op.clone()),
+            OwnedWorkerOp::DeleteEntityResponse(op) => WorkerOp::DeleteEntityResponse(op.clone()),
+            OwnedWorkerOp::EntityQueryResponse(op) => WorkerOp::EntityQueryResponse(op.clone()),
+            OwnedWorkerOp::Unknown(op) => WorkerOp::Unknown(op.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OwnedAddComponentOp {
+    pub entity_id: EntityId,
+    pub component_id: ComponentId,
+    component_data: OwnedHandle,
+}
+
+impl OwnedAddComponentOp {
+    pub fn new<C: Component>(entity_id: EntityId, component: C) -> Self {
+        OwnedAddComponentOp {
+            entity_id,
+            component_id: C::ID,
+            component_data: OwnedHandle::new(C::ID, HandleKind::Data, component),
+        }
+    }
+
+    pub fn get<C: Component>(&self) -> Option<&C> {
+        if C::ID == self.component_id {
+            Some(unsafe { self.component_data.get() })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OwnedComponentUpdateOp {
+    pub entity_id: EntityId,
+    pub component_id: ComponentId,
+    component_update: OwnedHandle,
+}
+
+impl OwnedComponentUpdateOp {
+    pub fn new<C: Component>(entity_id: EntityId, update: C::Update) -> Self {
+        OwnedComponentUpdateOp {
+            entity_id,
+            component_id: C::ID,
+            component_update: OwnedHandle::new(C::ID, HandleKind::Update, update),
+        }
+    }
+
+    pub fn get<C: Component>(&self) -> Option<&C::Update> {
+        if C::ID == self.component_id {
+            Some(unsafe { self.component_update.get() })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OwnedCommandRequestOp {
+    pub request_id: RequestId<IncomingCommandRequest>,
+    pub entity_id: EntityId,
+    pub timeout_millis: u32,
+    pub caller_worker_id: String,
+    pub caller_attribute_set: Vec<String>,
+    pub component_id: ComponentId,
+    request: OwnedHandle,
+}
+
+impl OwnedCommandRequestOp {
+    /// Creates a new command request op with no timeout or caller information.
+    ///
+    /// The caller information can be filled in afterwards by setting the
+    /// corresponding fields.
+    pub fn new<C: Component>(
+        request_id: RequestId<IncomingCommandRequest>,
+        entity_id: EntityId,
+        request: C::CommandRequest,
+    ) -> Self {
+        let command_index = C::get_request_command_index(&request);
+        OwnedCommandRequestOp {
+            request_id,
+            entity_id,
+            timeout_millis: 0,
+            caller_worker_id: String::new(),
+            caller_attribute_set: Vec::new(),
+            component_id: C::ID,
+            request: OwnedHandle::new(C::ID, HandleKind::CommandRequest(command_index), request),
+        }
+    }
+
+    pub fn get<C: Component>(&self) -> Option<&C::CommandRequest> {
+        if C::ID == self.component_id {
+            Some(unsafe { self.request.get() })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_command<Cmd: Command>(&self) -> Option<&Cmd::Request> {
+        self.get::<Cmd::Component>().and_then(Cmd::request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OwnedCommandResponseOp {
+    pub request_id: RequestId<OutgoingCommandRequest>,
+    pub entity_id: EntityId,
+    pub component_id: ComponentId,
+    pub response: StatusCode<OwnedCommandResponse>,
+}
+
+impl OwnedCommandResponseOp {
+    pub fn new<C: Component>(
+        request_id: RequestId<OutgoingCommandRequest>,
+        entity_id: EntityId,
+        response: StatusCode<C::CommandResponse>,
+    ) -> Self {
+        let response = response.map(|response| {
+            let command_index = C::get_response_command_index(&response);
+            OwnedCommandResponse {
+                response: OwnedHandle::new(
+                    C::ID,
+                    HandleKind::CommandResponse(command_index),
+                    response,
+                ),
+            }
+        });
+
+        OwnedCommandResponseOp {
+            request_id,
+            entity_id,
+            component_id: C::ID,
+            response,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OwnedCommandResponse {
+    response: OwnedHandle,
+}
+
+impl OwnedCommandResponse {
+    pub fn get<C: Component>(&self) -> Option<&C::CommandResponse> {
+        if C::ID == self.response.component_id() {
+            Some(unsafe { self.response.get() })
         } else {
             None
         }
     }
 
-    fn schema(&self) -> &SchemaCommandResponse {
-        &self.response.schema_type
+    pub fn get_command<Cmd: Command>(&self) -> Option<&Cmd::Response> {
+        self.get::<Cmd::Component>().and_then(Cmd::response)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::ReservedEntityIdRange;
+    use super::{OwnedWorkerOp, ReservedEntityIdRange};
+
+    #[test]
+    fn owned_worker_op_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<OwnedWorkerOp>();
+    }
 
     #[test]
     fn reserved_entity_id_range_iterator_contains_correct_count() {
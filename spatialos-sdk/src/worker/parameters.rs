@@ -14,6 +14,7 @@ pub struct ConnectionParameters {
     pub built_in_metrics_report_period_millis: u32,
     pub protocol_logging: ProtocolLoggingParameters,
     pub enable_protocol_logging_at_startup: bool,
+    pub enable_dynamic_components: bool,
     pub thread_affinity: ThreadAffinityParameters,
     use_internal_serialization: bool,
 }
@@ -61,6 +62,9 @@ impl ConnectionParameters {
         self
     }
 
+    /// Deserializes components on the SDK's network thread using the generated vtables,
+    /// rather than when ops are read. Typed access to ops and entities behaves the same
+    /// either way.
     pub fn enable_internal_serialization(mut self) -> Self {
         self.use_internal_serialization = true;
         self
@@ -77,6 +81,7 @@ impl ConnectionParameters {
                 WORKER_DEFAULTS_BUILT_IN_METRICS_REPORT_PERIOD_MILLIS,
             protocol_logging: ProtocolLoggingParameters::default(),
             enable_protocol_logging_at_startup: false,
+            enable_dynamic_components: false,
             thread_affinity: ThreadAffinityParameters::default(),
             use_internal_serialization: false,
         }
@@ -510,7 +515,7 @@ impl<'a> IntermediateConnectionParameters<'a> {
             protocol_logging: self.params.protocol_logging.to_worker_sdk(),
             enable_protocol_logging_at_startup: self.params.enable_protocol_logging_at_startup
                 as u8,
-            enable_dynamic_components: 0,
+            enable_dynamic_components: self.params.enable_dynamic_components as u8,
             thread_affinity: self.params.thread_affinity.to_worker_sdk(),
 
             component_vtable_count: if self.params.use_internal_serialization {
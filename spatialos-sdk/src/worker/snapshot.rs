@@ -1,18 +1,55 @@
+#[cfg(feature = "dynamic")]
+use crate::worker::{
+    dynamic::SchemaRegistry,
+    internal::schema::{SchemaComponentData, SchemaRef},
+    vtable::PASSTHROUGH_VTABLE,
+};
 use crate::{
     worker::component::DATABASE, worker::entity::Entity, worker::internal::utils::cstr_to_string,
     worker::EntityId,
 };
+#[cfg(feature = "dynamic")]
+use serde_json::{Map, Value};
 use spatialos_sdk_sys::worker::*;
-use std::{ffi::CString, path::Path};
+#[cfg(feature = "dynamic")]
+use std::{collections::BTreeMap, slice};
+use std::{
+    error::Error,
+    ffi::CString,
+    fmt::{self, Display, Formatter},
+    path::Path,
+    ptr,
+};
 
 #[derive(Debug)]
 pub enum SnapshotError {
     BadState(String),
     InvalidData(String),
     EntitySerializationFailure(String),
+    InvalidPath(String),
     EOF,
 }
 
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::BadState(message) => write!(f, "Snapshot stream error: {}", message),
+            SnapshotError::InvalidData(message) => {
+                write!(f, "Invalid data in snapshot: {}", message)
+            }
+            SnapshotError::EntitySerializationFailure(message) => {
+                write!(f, "Failed to serialize entity: {}", message)
+            }
+            SnapshotError::InvalidPath(message) => {
+                write!(f, "Invalid snapshot path: {}", message)
+            }
+            SnapshotError::EOF => write!(f, "Reached the end of the snapshot"),
+        }
+    }
+}
+
+impl Error for SnapshotError {}
+
 impl From<Worker_SnapshotState> for SnapshotError {
     fn from(state: Worker_SnapshotState) -> SnapshotError {
         match Worker_StreamState::from(state.stream_state) {
@@ -34,15 +71,14 @@ pub struct SnapshotOutputStream {
 
 impl SnapshotOutputStream {
     pub fn new<P: AsRef<Path>>(filename: P) -> Result<Self, SnapshotError> {
-        let filename_cstr = CString::new(filename.as_ref().to_str().unwrap()).unwrap();
+        SnapshotOutputStream::create(filename.as_ref(), &vtable_parameters())
+    }
+
+    fn create(filename: &Path, params: &Worker_SnapshotParameters) -> Result<Self, SnapshotError> {
+        let filename_cstr = path_to_cstring(filename)?;
 
-        let params = Worker_SnapshotParameters {
-            component_vtable_count: DATABASE.len() as u32,
-            component_vtables: DATABASE.to_worker_sdk(),
-            default_component_vtable: std::ptr::null(),
-        };
         let stream_ptr =
-            unsafe { Worker_SnapshotOutputStream_Create(filename_cstr.as_ptr(), &params) };
+            unsafe { Worker_SnapshotOutputStream_Create(filename_cstr.as_ptr(), params) };
 
         let state = unsafe { Worker_SnapshotOutputStream_GetState(stream_ptr) };
         match Worker_StreamState::from(state.stream_state) {
@@ -58,14 +94,16 @@ impl SnapshotOutputStream {
 
     pub fn write_entity(&mut self, id: EntityId, entity: &Entity) -> Result<(), SnapshotError> {
         let components = entity.raw_component_data();
-        let wrk_entity = Worker_Entity {
+        self.write_raw_entity(&Worker_Entity {
             entity_id: id.id,
             components: components.components.as_ptr(),
             component_count: components.components.len() as u32,
-        };
+        })
+    }
 
+    fn write_raw_entity(&mut self, entity: &Worker_Entity) -> Result<(), SnapshotError> {
         let state = unsafe {
-            Worker_SnapshotOutputStream_WriteEntity(self.ptr, &wrk_entity);
+            Worker_SnapshotOutputStream_WriteEntity(self.ptr, entity);
             Worker_SnapshotOutputStream_GetState(self.ptr)
         };
         match Worker_StreamState::from(state.stream_state) {
@@ -81,28 +119,33 @@ impl Drop for SnapshotOutputStream {
     }
 }
 
+/// Reads entities from a snapshot file.
+///
+/// The stream is an iterator over the entities in the snapshot, along with their entity
+/// IDs. Entities are read one at a time, so arbitrarily large snapshots can be processed
+/// without loading them into memory. Iteration stops after the first error.
 pub struct SnapshotInputStream {
     ptr: *mut Worker_SnapshotInputStream,
+    failed: bool,
 }
 
 impl SnapshotInputStream {
     pub fn new<P: AsRef<Path>>(filename: P) -> Result<Self, SnapshotError> {
-        let filename_cstr = CString::new(filename.as_ref().to_str().unwrap()).unwrap();
+        SnapshotInputStream::create(filename.as_ref(), &vtable_parameters())
+    }
 
-        let params = Worker_SnapshotParameters {
-            component_vtable_count: DATABASE.len() as u32,
-            component_vtables: DATABASE.to_worker_sdk(),
-            default_component_vtable: std::ptr::null(),
-        };
+    fn create(filename: &Path, params: &Worker_SnapshotParameters) -> Result<Self, SnapshotError> {
+        let filename_cstr = path_to_cstring(filename)?;
 
         let stream_ptr =
-            unsafe { Worker_SnapshotInputStream_Create(filename_cstr.as_ptr(), &params) };
+            unsafe { Worker_SnapshotInputStream_Create(filename_cstr.as_ptr(), params) };
 
         let state = unsafe { Worker_SnapshotInputStream_GetState(stream_ptr) };
         match Worker_StreamState::from(state.stream_state) {
-            Worker_StreamState_WORKER_STREAM_STATE_GOOD => {
-                Ok(SnapshotInputStream { ptr: stream_ptr })
-            }
+            Worker_StreamState_WORKER_STREAM_STATE_GOOD => Ok(SnapshotInputStream {
+                ptr: stream_ptr,
+                failed: false,
+            }),
             _ => {
                 unsafe { Worker_SnapshotInputStream_Destroy(stream_ptr) };
                 Err(SnapshotError::from(state))
@@ -114,26 +157,255 @@ impl SnapshotInputStream {
         unsafe { Worker_SnapshotInputStream_HasNext(self.ptr) != 0 }
     }
 
-    pub fn read_entity(&mut self) -> Result<Entity, SnapshotError> {
+    pub fn read_entity(&mut self) -> Result<(EntityId, Entity), SnapshotError> {
+        let entity = self.read_raw_entity()?;
+        let entity_id = EntityId::new(entity.entity_id);
+        unsafe { Entity::from_worker_sdk(entity) }
+            .map(|entity| (entity_id, entity))
+            .map_err(|error| SnapshotError::EntitySerializationFailure(error.to_string()))
+    }
+
+    // The entity is owned by the stream, and is only valid until the next entity is read.
+    fn read_raw_entity(&mut self) -> Result<&Worker_Entity, SnapshotError> {
         let entity_ptr = unsafe { Worker_SnapshotInputStream_ReadEntity(self.ptr) };
         let state = unsafe { Worker_SnapshotInputStream_GetState(self.ptr) };
 
         match Worker_StreamState::from(state.stream_state) {
-            Worker_StreamState_WORKER_STREAM_STATE_GOOD => unsafe {
-                let entity = Entity::from_worker_sdk(&*entity_ptr);
-                if let Err(message) = entity {
-                    Err(SnapshotError::EntitySerializationFailure(message))
-                } else {
-                    Ok(entity.unwrap())
-                }
-            },
+            Worker_StreamState_WORKER_STREAM_STATE_GOOD => Ok(unsafe { &*entity_ptr }),
             _ => Err(SnapshotError::from(state)),
         }
     }
 }
 
+impl Iterator for SnapshotInputStream {
+    type Item = Result<(EntityId, Entity), SnapshotError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || !self.has_next() {
+            return None;
+        }
+
+        let result = self.read_entity();
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
 impl Drop for SnapshotInputStream {
     fn drop(&mut self) {
         unsafe { Worker_SnapshotInputStream_Destroy(self.ptr) }
     }
 }
+
+/// Streams every entity from `input` through `f`, writing the results to `output`.
+///
+/// `f` is called with each entity in the input snapshot, and returns the entity to write
+/// to the output snapshot, or `None` to leave the entity out. Entities are processed one
+/// at a time, so this can be used to migrate snapshots that are too large to load into
+/// memory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use spatialos_sdk::worker::snapshot::{self, SnapshotInputStream, SnapshotOutputStream};
+///
+/// let input = SnapshotInputStream::new("default.snapshot")?;
+/// let output = SnapshotOutputStream::new("migrated.snapshot")?;
+///
+/// // Strip out every entity that has no components.
+/// snapshot::transform(input, output, |_, entity| {
+///     if entity.component_ids().next().is_some() {
+///         Some(entity)
+///     } else {
+///         None
+///     }
+/// })?;
+/// # Ok::<(), snapshot::SnapshotError>(())
+/// ```
+pub fn transform<F>(
+    input: SnapshotInputStream,
+    mut output: SnapshotOutputStream,
+    mut f: F,
+) -> Result<(), SnapshotError>
+where
+    F: FnMut(EntityId, Entity) -> Option<Entity>,
+{
+    for result in input {
+        let (entity_id, entity) = result?;
+        if let Some(entity) = f(entity_id, entity) {
+            output.write_entity(entity_id, &entity)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts the snapshot file at `filename` to JSON, using the schema definitions in
+/// `registry`.
+///
+/// The snapshot is read as raw schema data, so the components don't need generated code
+/// in this binary. The JSON has the form:
+///
+/// ```json
+/// {
+///     "entities": [
+///         {
+///             "entity_id": 1,
+///             "components": {
+///                 "improbable.Position": { "coords": { "x": 0.0, "y": 0.0, "z": 0.0 } }
+///             }
+///         }
+///     ]
+/// }
+/// ```
+///
+/// See the [`dynamic`](../dynamic/index.html) module for how schema values are written.
+/// Requires the `dynamic` feature.
+#[cfg(feature = "dynamic")]
+pub fn snapshot_to_json<P: AsRef<Path>>(
+    registry: &SchemaRegistry,
+    filename: P,
+) -> Result<Value, crate::Error> {
+    let mut input = SnapshotInputStream::create(filename.as_ref(), &passthrough_parameters())?;
+
+    let mut entities = Vec::new();
+    while input.has_next() {
+        let entity = input.read_raw_entity()?;
+        let raw_components =
+            unsafe { slice::from_raw_parts(entity.components, entity.component_count as usize) };
+
+        let mut components = BTreeMap::new();
+        for component in raw_components {
+            let data = SchemaComponentData {
+                internal: component.schema_type,
+            };
+            let value = registry.decode_component(component.component_id, &data)?;
+            components.insert(component.component_id, value);
+        }
+
+        let mut json = Map::new();
+        json.insert("entity_id".to_owned(), Value::from(entity.entity_id));
+        json.insert(
+            "components".to_owned(),
+            registry.entity_to_json(&components)?,
+        );
+        entities.push(Value::Object(json));
+    }
+
+    let mut json = Map::new();
+    json.insert("entities".to_owned(), Value::Array(entities));
+    Ok(Value::Object(json))
+}
+
+/// Writes the entities in `json` to a snapshot file at `filename`.
+///
+/// This is the inverse of [`snapshot_to_json`](fn.snapshot_to_json.html), and similarly
+/// doesn't need generated code for the components. Fields that are missing from the JSON
+/// are written with their default values, as described in
+/// [`SchemaRegistry::fill_component_defaults`](../dynamic/struct.SchemaRegistry.html#method.fill_component_defaults).
+#[cfg(feature = "dynamic")]
+pub fn snapshot_from_json<P: AsRef<Path>>(
+    registry: &SchemaRegistry,
+    json: &Value,
+    filename: P,
+) -> Result<(), crate::Error> {
+    let entities = json
+        .get("entities")
+        .and_then(Value::as_array)
+        .ok_or_else(|| crate::Error::decode("snapshot", "Expected an \"entities\" array."))?;
+
+    let mut output = SnapshotOutputStream::create(filename.as_ref(), &passthrough_parameters())?;
+    for entity in entities {
+        let entity_id = entity
+            .get("entity_id")
+            .and_then(Value::as_i64)
+            .ok_or_else(|| crate::Error::decode("snapshot", "Expected an \"entity_id\"."))?;
+        let mut components =
+            registry.entity_from_json(entity.get("components").unwrap_or(&Value::Null))?;
+
+        // The schema data is destroyed when `data` is dropped.
+        let mut data = Vec::with_capacity(components.len());
+        for (component_id, component) in &mut components {
+            registry.fill_component_defaults(*component_id, component)?;
+            let schema = registry.encode_component(*component_id, component)?;
+            data.push((*component_id, SchemaRef::owned(schema)));
+        }
+
+        let raw_components: Vec<_> = data
+            .iter()
+            .map(|(component_id, schema)| Worker_ComponentData {
+                reserved: ptr::null_mut(),
+                component_id: *component_id,
+                schema_type: schema.internal,
+                user_handle: ptr::null_mut(),
+            })
+            .collect();
+        output.write_raw_entity(&Worker_Entity {
+            entity_id,
+            components: raw_components.as_ptr(),
+            component_count: raw_components.len() as u32,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Converts an entity to a JSON object keyed by component name, using the schema
+/// definitions in `registry`.
+#[cfg(feature = "dynamic")]
+pub fn entity_to_json(registry: &SchemaRegistry, entity: &Entity) -> Result<Value, crate::Error> {
+    let mut components = BTreeMap::new();
+    for component_id in entity.component_ids() {
+        let data = entity
+            .schema(component_id)
+            .expect("Component IDs are taken from the entity")?;
+        components.insert(
+            component_id,
+            registry.decode_component(component_id, &data)?,
+        );
+    }
+    registry.entity_to_json(&components)
+}
+
+/// Creates an entity from a JSON object keyed by component name.
+///
+/// Unlike [`snapshot_from_json`](fn.snapshot_from_json.html), every component must have
+/// generated code linked into the worker. Missing fields are filled in the same way.
+#[cfg(feature = "dynamic")]
+pub fn entity_from_json(registry: &SchemaRegistry, json: &Value) -> Result<Entity, crate::Error> {
+    let mut entity = Entity::new();
+    for (component_id, mut component) in registry.entity_from_json(json)? {
+        registry.fill_component_defaults(component_id, &mut component)?;
+        let data = registry.encode_component(component_id, &component)?;
+        // `add_serialized` takes ownership of the component data.
+        unsafe { entity.add_serialized(component_id, data)? };
+    }
+    Ok(entity)
+}
+
+// Snapshot parameters that convert components using the generated vtables.
+fn vtable_parameters() -> Worker_SnapshotParameters {
+    Worker_SnapshotParameters {
+        component_vtable_count: DATABASE.len() as u32,
+        component_vtables: DATABASE.to_worker_sdk(),
+        default_component_vtable: ptr::null(),
+    }
+}
+
+// Snapshot parameters that leave every component as raw schema data.
+#[cfg(feature = "dynamic")]
+fn passthrough_parameters() -> Worker_SnapshotParameters {
+    Worker_SnapshotParameters {
+        component_vtable_count: 0,
+        component_vtables: ptr::null(),
+        default_component_vtable: &PASSTHROUGH_VTABLE,
+    }
+}
+
+fn path_to_cstring(path: &Path) -> Result<CString, SnapshotError> {
+    let path_str = path.to_str().ok_or_else(|| {
+        SnapshotError::InvalidPath(format!("{} is not valid UTF-8", path.display()))
+    })?;
+    CString::new(path_str)
+        .map_err(|_| SnapshotError::InvalidPath(format!("{} contains a nul byte", path.display())))
+}
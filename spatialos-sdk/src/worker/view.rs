@@ -0,0 +1,258 @@
+use crate::worker::{
+    component::{Component, ComponentId, HandleKind, OwnedHandle, DATABASE},
+    op::{OpList, WorkerOp},
+    {Authority, EntityId},
+};
+use std::{any::Any, collections::HashMap};
+
+/// A local cache of the entities and components visible to the worker.
+///
+/// The view is kept up to date by passing it every [`OpList`] received from the
+/// connection. It tracks which entities are in the worker's view, the data for each of
+/// their components, and the worker's authority over those components.
+///
+/// Ops received during a critical section are buffered and only applied once the
+/// critical section ends, so the view never exposes a partially-constructed entity.
+///
+/// Component data is only stored for components that have been registered in the
+/// component database, which is done automatically for generated components. The
+/// presence of and authority over unregistered components is still tracked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use spatialos_sdk::worker::{connection::Connection, view::View};
+/// # fn get_connection() -> spatialos_sdk::worker::connection::WorkerConnection { unimplemented!() }
+///
+/// let mut connection = get_connection();
+/// let mut view = View::new();
+///
+/// loop {
+///     let ops = connection.get_op_list(0);
+///     view.process_op_list(&ops);
+///
+///     for entity_id in view.entities() {
+///         // Update entities.
+///     }
+/// }
+/// ```
+///
+/// [`OpList`]: ../op/struct.OpList.html
+#[derive(Debug, Default)]
+pub struct View {
+    entities: HashMap<EntityId, ViewEntity>,
+    in_critical_section: bool,
+    pending_changes: Vec<Change>,
+}
+
+impl View {
+    pub fn new() -> Self {
+        View::default()
+    }
+
+    pub fn process_op_list(&mut self, op_list: &OpList) {
+        for op in op_list {
+            self.process_op(&op);
+        }
+    }
+
+    pub fn process_op(&mut self, op: &WorkerOp) {
+        let change = match op {
+            WorkerOp::CriticalSection(op) => {
+                self.in_critical_section = op.in_critical_section;
+                if !self.in_critical_section {
+                    let changes: Vec<_> = self.pending_changes.drain(..).collect();
+                    for change in changes {
+                        self.apply(change);
+                    }
+                }
+                return;
+            }
+            WorkerOp::AddEntity(op) => Change::AddEntity(op.entity_id),
+            WorkerOp::RemoveEntity(op) => Change::RemoveEntity(op.entity_id),
+            WorkerOp::AddComponent(op) => {
+                let data = op.component_data.handle();
+                let copy = match DATABASE.get_view_vtable(op.component_id) {
+                    Some(vtable) if !data.is_null() => {
+                        Some(unsafe { (vtable.copy_data)(data as *const _) })
+                    }
+                    _ => None,
+                };
+                Change::AddComponent(op.entity_id, op.component_id, copy)
+            }
+            WorkerOp::RemoveComponent(op) => Change::RemoveComponent(op.entity_id, op.component_id),
+            WorkerOp::ComponentUpdate(op) => {
+                let update = op.component_update.handle();
+                if update.is_null() {
+                    return;
+                }
+
+                // Updates for components without a vtable can't be applied to the view.
+                match unsafe { OwnedHandle::copy_from(op.component_id, HandleKind::Update, update) }
+                {
+                    Ok(handle) => Change::UpdateComponent(op.entity_id, handle),
+                    Err(_) => return,
+                }
+            }
+            WorkerOp::AuthorityChange(op) => {
+                Change::SetAuthority(op.entity_id, op.component_id, op.authority)
+            }
+            _ => return,
+        };
+
+        if self.in_critical_section {
+            self.pending_changes.push(change);
+        } else {
+            self.apply(change);
+        }
+    }
+
+    /// Returns `true` if the view has received the start of a critical section but not
+    /// the end. Ops received during a critical section are not visible until it ends.
+    pub fn is_in_critical_section(&self) -> bool {
+        self.in_critical_section
+    }
+
+    pub fn contains_entity(&self, entity_id: EntityId) -> bool {
+        self.entities.contains_key(&entity_id)
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = EntityId> + '_ {
+        self.entities.keys().cloned()
+    }
+
+    pub fn has_component<C: Component>(&self, entity_id: EntityId) -> bool {
+        match self.entities.get(&entity_id) {
+            Some(entity) => entity.components.contains_key(&C::ID),
+            None => false,
+        }
+    }
+
+    pub fn get<C: Component>(&self, entity_id: EntityId) -> Option<&C> {
+        self.entities
+            .get(&entity_id)
+            .and_then(|entity| entity.get::<C>())
+    }
+
+    /// Returns the worker's authority over component `C` on the entity, or `None` if the
+    /// entity or component is not in the view.
+    pub fn authority<C: Component>(&self, entity_id: EntityId) -> Option<Authority> {
+        let entity = self.entities.get(&entity_id)?;
+        if !entity.components.contains_key(&C::ID) {
+            return None;
+        }
+
+        Some(
+            entity
+                .authority
+                .get(&C::ID)
+                .cloned()
+                .unwrap_or(Authority::NotAuthoritative),
+        )
+    }
+
+    /// Returns `true` if the worker is authoritative over component `C` on the entity.
+    ///
+    /// This includes the case where authority loss is imminent, as the worker is still
+    /// authoritative until the authority change is received.
+    pub fn has_authority<C: Component>(&self, entity_id: EntityId) -> bool {
+        match self.authority::<C>(entity_id) {
+            Some(authority) => authority.has_authority(),
+            None => false,
+        }
+    }
+
+    pub fn is_authority_loss_imminent<C: Component>(&self, entity_id: EntityId) -> bool {
+        self.authority::<C>(entity_id) == Some(Authority::AuthorityLossImminent)
+    }
+
+    /// Iterates over the entities that have component `C`, along with the component data.
+    pub fn iter<C: Component>(&self) -> impl Iterator<Item = (EntityId, &C)> {
+        self.entities
+            .iter()
+            .filter_map(|(&entity_id, entity)| entity.get::<C>().map(|data| (entity_id, data)))
+    }
+
+    /// Iterates over the entities that have all of the given components.
+    pub fn query<'a>(
+        &'a self,
+        component_ids: &'a [ComponentId],
+    ) -> impl Iterator<Item = EntityId> + 'a {
+        self.entities
+            .iter()
+            .filter(move |(_, entity)| {
+                component_ids
+                    .iter()
+                    .all(|id| entity.components.contains_key(id))
+            })
+            .map(|(&entity_id, _)| entity_id)
+    }
+
+    fn apply(&mut self, change: Change) {
+        match change {
+            Change::AddEntity(entity_id) => {
+                self.entities.insert(entity_id, ViewEntity::default());
+            }
+            Change::RemoveEntity(entity_id) => {
+                self.entities.remove(&entity_id);
+            }
+            Change::AddComponent(entity_id, component_id, data) => {
+                if let Some(entity) = self.entities.get_mut(&entity_id) {
+                    entity.components.insert(component_id, data);
+                }
+            }
+            Change::RemoveComponent(entity_id, component_id) => {
+                if let Some(entity) = self.entities.get_mut(&entity_id) {
+                    entity.components.remove(&component_id);
+                    entity.authority.remove(&component_id);
+                }
+            }
+            Change::UpdateComponent(entity_id, update) => {
+                let component_id = update.component_id();
+                let data = self
+                    .entities
+                    .get_mut(&entity_id)
+                    .and_then(|entity| entity.components.get_mut(&component_id))
+                    .and_then(Option::as_mut);
+                let vtable = DATABASE.get_view_vtable(component_id);
+                if let (Some(data), Some(vtable)) = (data, vtable) {
+                    unsafe { (vtable.merge_update)(data.as_mut(), update.as_ptr()) };
+                }
+            }
+            Change::SetAuthority(entity_id, component_id, authority) => {
+                if let Some(entity) = self.entities.get_mut(&entity_id) {
+                    entity.authority.insert(component_id, authority);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ViewEntity {
+    // Component data is `None` for components that aren't registered in the
+    // component database.
+    components: HashMap<ComponentId, Option<Box<dyn Any + Send + Sync>>>,
+    authority: HashMap<ComponentId, Authority>,
+}
+
+impl ViewEntity {
+    fn get<C: Component>(&self) -> Option<&C> {
+        self.components
+            .get(&C::ID)
+            .and_then(Option::as_ref)
+            .and_then(|data| data.downcast_ref::<C>())
+    }
+}
+
+// A change to the view, extracted from an op. These are buffered while in a critical
+// section, so they must not borrow from the op list.
+#[derive(Debug)]
+enum Change {
+    AddEntity(EntityId),
+    RemoveEntity(EntityId),
+    AddComponent(EntityId, ComponentId, Option<Box<dyn Any + Send + Sync>>),
+    RemoveComponent(EntityId, ComponentId),
+    UpdateComponent(EntityId, OwnedHandle),
+    SetAuthority(EntityId, ComponentId, Authority),
+}
@@ -0,0 +1,67 @@
+//! The test suite's `Spatial.toml` derives `Eq` and `Hash` for generated code, and maps
+//! `test_suite.Vector3` to `Point`.
+
+use crate::dynamic_tests::registry;
+use crate::generated::test_suite::*;
+use crate::Point;
+use spatialos_sdk::worker::component::Component;
+use spatialos_sdk::worker::dynamic::SchemaValue;
+use spatialos_sdk_sys::worker::Schema_DestroyComponentData;
+use std::collections::{BTreeMap, HashSet};
+use std::hash::Hash;
+
+fn assert_eq_hash<T: Eq + Hash>() {}
+
+#[test]
+fn eq_and_hash_are_derived_where_valid() {
+    assert_eq_hash::<Color>();
+    assert_eq_hash::<EnumTest>();
+    assert_eq_hash::<RecursionTest>();
+    assert_eq_hash::<RecursionTestUpdate>();
+    assert_eq_hash::<RecursionTestCommandRequest>();
+
+    let mut paints = HashSet::new();
+    paints.insert(Paint { color: Color::RED });
+    paints.insert(Paint { color: Color::RED });
+    assert_eq!(1, paints.len());
+}
+
+#[test]
+fn mapped_fields_round_trip_through_schema() {
+    let mut waypoints = BTreeMap::new();
+    waypoints.insert("home".to_owned(), Point(4.0, 5.0, 6.0));
+    let component = TypeMappingTest {
+        position: Point(1.0, 2.0, 3.0),
+        target: Some(Point(-1.0, 0.0, 1.0)),
+        path: vec![Point(0.0, 0.0, 0.0), Point(1.0, 1.0, 1.0)],
+        waypoints,
+    };
+
+    let data = TypeMappingTest::to_data(&component).unwrap();
+    let deserialized = TypeMappingTest::from_data(&data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    assert_eq!(component, deserialized.unwrap());
+}
+
+#[test]
+fn mapped_fields_are_serialized_as_the_schema_type() {
+    let component = TypeMappingTest {
+        position: Point(1.0, 2.0, 3.0),
+        target: None,
+        path: Vec::new(),
+        waypoints: BTreeMap::new(),
+    };
+
+    let data = TypeMappingTest::to_data(&component).unwrap();
+    let value = registry().decode_component(TypeMappingTest::ID, &data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    match value.unwrap().field("position") {
+        Some(SchemaValue::Object(position)) => {
+            assert_eq!("test_suite.Vector3", position.type_name);
+            assert_eq!(Some(&SchemaValue::Double(2.0)), position.field("y"));
+        }
+        value => panic!("Expected an object, got {:?}", value),
+    }
+}
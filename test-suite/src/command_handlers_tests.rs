@@ -0,0 +1,103 @@
+use crate::generated::improbable::restricted::*;
+use spatialos_sdk::worker::command_handlers::CommandHandlers;
+use spatialos_sdk::worker::component::Component;
+use spatialos_sdk::worker::connection::Connection;
+use spatialos_sdk::worker::mock::{MockConnection, SentMessage};
+use spatialos_sdk::worker::EntityId;
+
+fn process(connection: &mut MockConnection, handlers: &mut CommandHandlers<MockConnection>) {
+    let ops = connection.get_op_list(0);
+    handlers.process_op_list(connection, &ops);
+}
+
+fn disconnect_request() -> WorkerCommandRequest {
+    WorkerCommandRequest::Disconnect(DisconnectRequest {})
+}
+
+#[test]
+fn registered_handler_sends_response() {
+    let mut connection = MockConnection::new("worker");
+    let mut handlers = CommandHandlers::new();
+    handlers.register::<Worker, _>(|entity_id, request| {
+        assert_eq!(EntityId::new(1), entity_id);
+        match request {
+            WorkerCommandRequest::Disconnect(_) => {
+                Ok(WorkerCommandResponse::Disconnect(DisconnectResponse {}))
+            }
+        }
+    });
+    assert!(handlers.is_registered(Worker::ID));
+
+    let request_id =
+        connection.push_command_request::<Worker>(EntityId::new(1), disconnect_request());
+    process(&mut connection, &mut handlers);
+
+    let responses = connection.sent_command_responses::<Worker>();
+    assert_eq!(1, responses.len());
+    assert_eq!(request_id, responses[0].0);
+}
+
+#[test]
+fn handler_errors_are_sent_as_failures() {
+    let mut connection = MockConnection::new("worker");
+    let mut handlers = CommandHandlers::new();
+    handlers.register::<Worker, _>(|_, _| Err("Not allowed\0".to_owned()));
+
+    let request_id =
+        connection.push_command_request::<Worker>(EntityId::new(1), disconnect_request());
+    process(&mut connection, &mut handlers);
+
+    match connection.sent_messages() {
+        [SentMessage::CommandFailure {
+            request_id: failed_id,
+            message,
+        }] => {
+            assert_eq!(request_id, *failed_id);
+            assert_eq!("Not allowed", message);
+        }
+        messages => panic!("Expected a single command failure, got {:?}", messages),
+    }
+}
+
+#[test]
+fn unhandled_commands_fail_immediately() {
+    let mut connection = MockConnection::new("worker");
+    let mut handlers = CommandHandlers::new();
+    handlers.register::<Worker, _>(|_, _| Err("Unreachable".to_owned()));
+    assert!(handlers.unregister::<Worker>());
+
+    let request_id =
+        connection.push_command_request::<Worker>(EntityId::new(1), disconnect_request());
+    process(&mut connection, &mut handlers);
+
+    match connection.sent_messages() {
+        [SentMessage::CommandFailure {
+            request_id: failed_id,
+            message,
+        }] => {
+            assert_eq!(request_id, *failed_id);
+            assert!(message.contains(&Worker::ID.to_string()));
+        }
+        messages => panic!("Expected a single command failure, got {:?}", messages),
+    }
+}
+
+#[test]
+fn command_handlers_take_priority_over_component_handlers() {
+    let mut connection = MockConnection::new("worker");
+    let mut handlers = CommandHandlers::new();
+    handlers.register::<Worker, _>(|_, _| Err("Component handler".to_owned()));
+    handlers.register_command::<WorkerDisconnectCommand, _>(|_, DisconnectRequest {}| {
+        Ok(DisconnectResponse {})
+    });
+
+    connection.push_command_request::<Worker>(EntityId::new(1), disconnect_request());
+    process(&mut connection, &mut handlers);
+    assert_eq!(1, connection.sent_command_responses::<Worker>().len());
+
+    assert!(handlers.unregister_command::<WorkerDisconnectCommand>());
+    connection.clear_sent_messages();
+    connection.push_command_request::<Worker>(EntityId::new(1), disconnect_request());
+    process(&mut connection, &mut handlers);
+    assert!(connection.sent_command_responses::<Worker>().is_empty());
+}
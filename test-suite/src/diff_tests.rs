@@ -0,0 +1,74 @@
+use crate::fixtures::{leaf, recursion_test};
+use crate::generated::test_suite::*;
+use spatialos_sdk::worker::component::ComponentData;
+use spatialos_sdk::worker::entity_builder::EntityBuilder;
+use std::collections::BTreeMap;
+
+#[test]
+fn equal_components_have_no_diff() {
+    assert_eq!(None, recursion_test().diff(&recursion_test()));
+}
+
+#[test]
+fn diff_only_sets_changed_fields() {
+    let mut new = recursion_test();
+    new.tree = None;
+
+    let update = recursion_test().diff(&new).unwrap();
+    assert_eq!(None, update.root);
+    assert_eq!(Some(None), update.tree);
+    assert!(update.node_event.is_empty());
+}
+
+#[test]
+fn diff_detects_nested_changes() {
+    let mut changed_child = recursion_test();
+    changed_child.root.child.as_mut().unwrap().value = 7;
+
+    let mut changed_list = recursion_test();
+    changed_list.root.children.push(leaf(8));
+
+    let mut changed_map = recursion_test();
+    changed_map
+        .root
+        .named_children
+        .get_mut("named")
+        .unwrap()
+        .value = 9;
+
+    for new in &[changed_child, changed_list, changed_map] {
+        let update = recursion_test().diff(new).unwrap();
+        assert_eq!(Some(&new.root), update.root.as_ref());
+        assert_eq!(None, update.tree);
+    }
+}
+
+#[test]
+fn merging_a_diff_gives_the_new_component() {
+    let mut new = recursion_test();
+    new.root.value = 10;
+    new.tree = Some(TreeA { value: 11, b: None });
+
+    let mut old = recursion_test();
+    let update = old.diff(&new).unwrap();
+    old.merge(update);
+    assert_eq!(new, old);
+    assert_eq!(None, old.diff(&new));
+}
+
+#[test]
+fn diff_compares_entity_fields_by_value() {
+    let entity_at = |x| EntityBuilder::new(x, 0.0, 0.0, "rusty").build().unwrap();
+    let component = |x| EntityTest {
+        entity: entity_at(x),
+        optional_entity: None,
+        entity_list: Vec::new(),
+        entity_map: BTreeMap::new(),
+    };
+
+    assert!(component(1.0).diff(&component(1.0)).is_none());
+
+    let update = component(1.0).diff(&component(2.0)).unwrap();
+    assert_eq!(Some(entity_at(2.0)), update.entity);
+    assert_eq!(None, update.optional_entity);
+}
@@ -0,0 +1,188 @@
+use crate::generated::improbable::restricted::*;
+use spatialos_sdk::worker::commands::{DeleteEntityRequest, EntityQueryRequest};
+use spatialos_sdk::worker::component::Command;
+use spatialos_sdk::worker::connection::Connection;
+use spatialos_sdk::worker::dispatcher::RequestDispatcher;
+use spatialos_sdk::worker::entity::Entity;
+use spatialos_sdk::worker::mock::MockConnection;
+use spatialos_sdk::worker::op::{
+    CreateEntityResponseOp, DeleteEntityResponseOp, EntityQueryResponseOp, OwnedWorkerOp,
+    QueryResponse, StatusCode,
+};
+use spatialos_sdk::worker::query::{EntityQuery, QueryConstraint, ResultType};
+use spatialos_sdk::worker::EntityId;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn command_responses_are_dispatched_to_their_callbacks() {
+    let mut connection = MockConnection::new("worker");
+    let mut dispatcher = RequestDispatcher::new();
+    let entity_id = EntityId::new(1);
+    let results = Rc::new(RefCell::new(Vec::new()));
+
+    let send = |dispatcher: &mut RequestDispatcher, connection: &mut MockConnection| {
+        let results = results.clone();
+        dispatcher.send_command_request::<Worker, _, _>(
+            connection,
+            entity_id,
+            WorkerCommandRequest::Disconnect(DisconnectRequest {}),
+            None,
+            Default::default(),
+            move |response| {
+                let result = match response {
+                    StatusCode::Success(WorkerCommandResponse::Disconnect(_)) => Ok(()),
+                    StatusCode::Timeout(message) => Err(message),
+                    _ => Err("unexpected status".to_owned()),
+                };
+                results.borrow_mut().push(result);
+            },
+        )
+    };
+    let first = send(&mut dispatcher, &mut connection);
+    let second = send(&mut dispatcher, &mut connection);
+    assert_eq!(2, dispatcher.pending_requests());
+
+    connection.push_command_response::<Worker>(
+        second,
+        entity_id,
+        StatusCode::Timeout("Timed out".to_owned()),
+    );
+    connection.push_command_response::<Worker>(
+        first,
+        entity_id,
+        StatusCode::Success(WorkerCommandResponse::Disconnect(DisconnectResponse {})),
+    );
+    dispatcher.process_op_list(&connection.get_op_list(0));
+
+    assert_eq!(vec![Err("Timed out".to_owned()), Ok(())], *results.borrow());
+    assert_eq!(0, dispatcher.pending_requests());
+}
+
+#[test]
+fn callbacks_are_only_invoked_once() {
+    let mut connection = MockConnection::new("worker");
+    let mut dispatcher = RequestDispatcher::new();
+    let calls = Rc::new(RefCell::new(0));
+
+    let calls_ref = calls.clone();
+    let request_id = dispatcher.send_create_entity_request(
+        &mut connection,
+        Entity::new(),
+        None,
+        None,
+        move |result| match result {
+            StatusCode::Success(entity_id) => {
+                assert_eq!(EntityId::new(5), entity_id);
+                *calls_ref.borrow_mut() += 1;
+            }
+            _ => panic!("Expected entity creation to succeed"),
+        },
+    );
+
+    for _ in 0..2 {
+        connection.push_op(OwnedWorkerOp::CreateEntityResponse(
+            CreateEntityResponseOp {
+                request_id,
+                status_code: StatusCode::Success(EntityId::new(5)),
+            },
+        ));
+    }
+    dispatcher.process_op_list(&connection.get_op_list(0));
+
+    assert_eq!(1, *calls.borrow());
+}
+
+#[test]
+fn world_command_failures_are_delivered() {
+    let mut connection = MockConnection::new("worker");
+    let mut dispatcher = RequestDispatcher::new();
+    let deleted = Rc::new(RefCell::new(None));
+    let queried = Rc::new(RefCell::new(None));
+
+    let deleted_ref = deleted.clone();
+    let delete_id = dispatcher.send_delete_entity_request(
+        &mut connection,
+        DeleteEntityRequest(EntityId::new(3)),
+        None,
+        move |entity_id, result| {
+            *deleted_ref.borrow_mut() = Some((entity_id, result));
+        },
+    );
+
+    let queried_ref = queried.clone();
+    let query_id = dispatcher.send_entity_query_request(
+        &mut connection,
+        EntityQueryRequest(EntityQuery::new(
+            QueryConstraint::EntityId(EntityId::new(3)),
+            ResultType::Count,
+        )),
+        None,
+        move |result| {
+            *queried_ref.borrow_mut() = Some(result);
+        },
+    );
+
+    connection.push_op(OwnedWorkerOp::DeleteEntityResponse(
+        DeleteEntityResponseOp {
+            request_id: delete_id,
+            entity_id: EntityId::new(3),
+            status_code: StatusCode::PermissionDenied("Denied".to_owned()),
+        },
+    ));
+    connection.push_op(OwnedWorkerOp::EntityQueryResponse(EntityQueryResponseOp {
+        request_id: query_id,
+        status_code: StatusCode::Success(QueryResponse::Result(0)),
+    }));
+    dispatcher.process_op_list(&connection.get_op_list(0));
+
+    let deleted = deleted.borrow_mut().take();
+    match deleted {
+        Some((entity_id, StatusCode::PermissionDenied(message))) => {
+            assert_eq!(EntityId::new(3), entity_id);
+            assert_eq!("Denied", message);
+        }
+        _ => panic!("Expected the delete entity request to be denied"),
+    }
+    let queried = queried.borrow_mut().take();
+    match queried {
+        Some(StatusCode::Success(QueryResponse::Result(count))) => assert_eq!(0, count),
+        _ => panic!("Expected a successful query"),
+    }
+}
+
+#[test]
+fn typed_command_responses_are_dispatched() {
+    let mut connection = MockConnection::new("worker");
+    let mut dispatcher = RequestDispatcher::new();
+    let entity_id = EntityId::new(1);
+    let received = Rc::new(RefCell::new(false));
+
+    let received_ref = received.clone();
+    let request_id = dispatcher.send_command::<WorkerDisconnectCommand, _, _>(
+        &mut connection,
+        entity_id,
+        DisconnectRequest {},
+        None,
+        Default::default(),
+        move |response| match response {
+            StatusCode::Success(DisconnectResponse {}) => *received_ref.borrow_mut() = true,
+            _ => panic!("Expected a successful response"),
+        },
+    );
+
+    let requests = connection.sent_command_requests::<Worker>();
+    assert_eq!(1, requests.len());
+    assert!(WorkerDisconnectCommand::request(requests[0].1).is_some());
+
+    connection.push_command_response::<Worker>(
+        request_id,
+        entity_id,
+        StatusCode::Success(WorkerDisconnectCommand::into_response(
+            DisconnectResponse {},
+        )),
+    );
+    dispatcher.process_op_list(&connection.get_op_list(0));
+
+    assert!(*received.borrow());
+}
@@ -0,0 +1,221 @@
+use crate::fixtures::recursion_test;
+use crate::generated::test_suite::*;
+use serde_json::json;
+use spatialos_sdk::worker::component::Component;
+use spatialos_sdk::worker::dynamic::{FieldValue, SchemaObjectValue, SchemaRegistry, SchemaValue};
+use spatialos_sdk::worker::entity::Entity;
+use spatialos_sdk::worker::snapshot::{entity_from_json, entity_to_json};
+use spatialos_sdk::Error;
+use spatialos_sdk_sys::worker::Schema_DestroyComponentData;
+
+// The bundle is written by `cargo spatial codegen`, which has to run before the tests are built.
+pub fn registry() -> SchemaRegistry {
+    SchemaRegistry::load(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/build/schema/bundle.json"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn enum_fields_are_decoded_with_names() {
+    let component = EnumTest {
+        paint: Paint {
+            color: Color::GREEN,
+        },
+    };
+
+    let data = EnumTest::to_data(&component).unwrap();
+    let value = registry().decode_component(EnumTest::ID, &data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    let value = value.unwrap();
+    assert_eq!("test_suite.EnumTest", value.type_name);
+    match value.field("paint") {
+        Some(SchemaValue::Object(paint)) => {
+            assert_eq!("test_suite.Paint", paint.type_name);
+            assert_eq!(
+                Some(&SchemaValue::Enum {
+                    name: "GREEN".to_owned(),
+                    value: 1
+                }),
+                paint.field("color")
+            );
+        }
+        value => panic!("Expected an object, got {:?}", value),
+    }
+}
+
+#[test]
+fn decoded_components_encode_to_the_same_data() {
+    let component = recursion_test();
+
+    let registry = registry();
+    let data = RecursionTest::to_data(&component).unwrap();
+    let value = registry.decode_component(RecursionTest::ID, &data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    let data = registry
+        .encode_component(RecursionTest::ID, &value.unwrap())
+        .unwrap();
+    let decoded = RecursionTest::from_data(&data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    assert_eq!(
+        format!("{:?}", component),
+        format!("{:?}", decoded.unwrap())
+    );
+}
+
+#[test]
+fn data_components_use_the_fields_of_their_data_type() {
+    let component = InventoryTest {
+        capacity: 10,
+        items: vec!["sword".to_owned(), "shield".to_owned()],
+    };
+
+    let registry = registry();
+    let data = InventoryTest::to_data(&component).unwrap();
+    let value = registry.decode_component(InventoryTest::ID, &data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    let value = value.unwrap();
+    assert_eq!(Some(&SchemaValue::Int32(10)), value.field("capacity"));
+    assert_eq!(
+        Some(&SchemaValue::List(vec![
+            SchemaValue::String("sword".to_owned()),
+            SchemaValue::String("shield".to_owned()),
+        ])),
+        value.field("items")
+    );
+
+    let data = registry
+        .encode_component(InventoryTest::ID, &value)
+        .unwrap();
+    let decoded = InventoryTest::from_data(&data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    assert_eq!(component, decoded.unwrap());
+}
+
+#[test]
+fn missing_fields_are_left_unset() {
+    let value = SchemaObjectValue {
+        type_name: "test_suite.RecursionTest".to_owned(),
+        fields: vec![FieldValue {
+            name: "tree".to_owned(),
+            field_id: 2,
+            value: SchemaValue::Option(None),
+        }],
+    };
+
+    let data = registry()
+        .encode_component(RecursionTest::ID, &value)
+        .unwrap();
+    let decoded = RecursionTest::from_data(&data);
+    unsafe { Schema_DestroyComponentData(data.internal) };
+
+    let decoded = decoded.unwrap();
+    assert_eq!(0, decoded.root.value);
+    assert!(decoded.tree.is_none());
+}
+
+#[test]
+fn missing_fields_are_filled_with_defaults() {
+    let registry = registry();
+    let mut value = registry
+        .component_from_json(TypeMappingTest::ID, &json!({ "path": [{ "x": 1.0 }] }))
+        .unwrap();
+    registry
+        .fill_component_defaults(TypeMappingTest::ID, &mut value)
+        .unwrap();
+
+    assert_eq!(
+        json!({
+            "position": { "x": 0.0, "y": 0.0, "z": 0.0 },
+            "target": null,
+            "path": [{ "x": 1.0, "y": 0.0, "z": 0.0 }],
+            "waypoints": {}
+        }),
+        registry.object_to_json(&value).unwrap()
+    );
+
+    let mut value = registry
+        .component_from_json(EnumTest::ID, &json!({}))
+        .unwrap();
+    registry
+        .fill_component_defaults(EnumTest::ID, &mut value)
+        .unwrap();
+    assert_eq!(
+        json!({ "paint": { "color": "RED" } }),
+        registry.object_to_json(&value).unwrap()
+    );
+}
+
+#[test]
+fn entities_from_json_fill_in_missing_fields() {
+    let registry = registry();
+    let json = json!({
+        "test_suite.EnumTest": {},
+        "test_suite.InventoryTest": { "items": ["sword"] }
+    });
+
+    let entity = entity_from_json(&registry, &json).unwrap();
+    assert_eq!(Color::RED, entity.get::<EnumTest>().unwrap().paint.color);
+    assert_eq!(
+        &InventoryTest {
+            capacity: 0,
+            items: vec!["sword".to_owned()],
+        },
+        entity.get::<InventoryTest>().unwrap()
+    );
+}
+
+#[test]
+fn values_of_the_wrong_type_are_rejected() {
+    let value = SchemaObjectValue {
+        type_name: "test_suite.Paint".to_owned(),
+        fields: vec![FieldValue {
+            name: "color".to_owned(),
+            field_id: 1,
+            value: SchemaValue::Enum {
+                name: "BLUE".to_owned(),
+                value: 2,
+            },
+        }],
+    };
+    let component = SchemaObjectValue {
+        type_name: "test_suite.EnumTest".to_owned(),
+        fields: vec![FieldValue {
+            name: "paint".to_owned(),
+            field_id: 2,
+            value: SchemaValue::Object(value),
+        }],
+    };
+
+    match registry().encode_component(EnumTest::ID, &component) {
+        Err(Error::SchemaEncode { component_id, .. }) => {
+            assert_eq!(Some(EnumTest::ID), component_id)
+        }
+        result => panic!("Expected an encode error, got {:?}", result),
+    }
+}
+
+#[test]
+fn entities_round_trip_through_json() {
+    let registry = registry();
+    let mut entity = Entity::new();
+    entity
+        .set(EnumTest {
+            paint: Paint {
+                color: Color::GREEN,
+            },
+        })
+        .unwrap();
+
+    let json = entity_to_json(&registry, &entity).unwrap();
+    assert_eq!("GREEN", json["test_suite.EnumTest"]["paint"]["color"]);
+
+    let entity = entity_from_json(&registry, &json).unwrap();
+    assert_eq!(Color::GREEN, entity.get::<EnumTest>().unwrap().paint.color);
+}
@@ -2,6 +2,7 @@ use crate::generated::improbable::*;
 use approx;
 use spatialos_sdk::worker::component::Component;
 use spatialos_sdk::worker::entity_builder::EntityBuilder;
+use spatialos_sdk::Error;
 
 #[test]
 fn position_is_serialized_correctly() {
@@ -122,3 +123,23 @@ fn error_is_returned_if_invalid_entity() {
 
     assert!(result.is_err());
 }
+
+#[test]
+fn duplicate_components_are_reported() {
+    let mut builder = EntityBuilder::new(0.0, 0.0, 0.0, "rusty");
+    builder.add_component(
+        Position {
+            coords: Coordinates {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        },
+        "rusty",
+    );
+
+    match builder.build() {
+        Err(Error::DuplicateComponent(id)) => assert_eq!(Position::ID, id),
+        result => panic!("Expected a duplicate component error, got {:?}", result),
+    }
+}
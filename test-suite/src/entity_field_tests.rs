@@ -0,0 +1,57 @@
+use crate::generated::improbable::*;
+use crate::generated::test_suite::*;
+use approx;
+use spatialos_sdk::worker::component::Component;
+use spatialos_sdk::worker::entity::Entity;
+use spatialos_sdk::worker::entity_builder::EntityBuilder;
+use std::collections::BTreeMap;
+
+fn entity_at(x: f64) -> Entity {
+    EntityBuilder::new(x, 0.0, 0.0, "rusty").build().unwrap()
+}
+
+fn x_of(entity: &Entity) -> f64 {
+    entity.get::<Position>().unwrap().coords.x
+}
+
+#[test]
+fn entity_fields_round_trip_through_schema() {
+    let mut entity_map = BTreeMap::new();
+    entity_map.insert("prefab".to_owned(), entity_at(4.0));
+
+    let component = EntityTest {
+        entity: entity_at(1.0),
+        optional_entity: Some(entity_at(2.0)),
+        entity_list: vec![entity_at(3.0), entity_at(3.5)],
+        entity_map,
+    };
+
+    let data = EntityTest::to_data(&component).unwrap();
+    let deserialized = EntityTest::from_data(&data).unwrap();
+
+    approx::assert_abs_diff_eq!(1.0, x_of(&deserialized.entity));
+    approx::assert_abs_diff_eq!(2.0, x_of(deserialized.optional_entity.as_ref().unwrap()));
+    assert_eq!(2, deserialized.entity_list.len());
+    approx::assert_abs_diff_eq!(3.5, x_of(&deserialized.entity_list[1]));
+    approx::assert_abs_diff_eq!(4.0, x_of(&deserialized.entity_map["prefab"]));
+
+    let acl = deserialized.entity.get::<EntityAcl>().unwrap();
+    assert_eq!("rusty", acl.read_acl.attribute_set[0].attribute[0]);
+}
+
+#[test]
+fn empty_entity_fields_round_trip_through_schema() {
+    let component = EntityTest {
+        entity: Entity::new(),
+        optional_entity: None,
+        entity_list: Vec::new(),
+        entity_map: BTreeMap::new(),
+    };
+
+    let data = EntityTest::to_data(&component).unwrap();
+    let deserialized = EntityTest::from_data(&data).unwrap();
+
+    assert_eq!(0, deserialized.entity.component_ids().count());
+    assert!(deserialized.optional_entity.is_none());
+    assert!(deserialized.entity_list.is_empty());
+}
@@ -0,0 +1,55 @@
+use crate::generated::test_suite::*;
+use spatialos_sdk::worker::component::{Component, TypeConversion};
+use spatialos_sdk::worker::internal::schema::{
+    SchemaComponentData, SchemaEnum, SchemaObject, SchemaObjectField, SchemaPrimitiveField,
+};
+use spatialos_sdk::worker::Authority;
+use spatialos_sdk::Error;
+use std::convert::TryFrom;
+
+#[test]
+fn unknown_enum_values_are_decode_errors() {
+    let mut data = SchemaComponentData::new();
+    data.fields_mut()
+        .field::<SchemaObject>(2)
+        .add()
+        .field::<SchemaEnum>(1)
+        .add(7);
+
+    match EnumTest::from_data(&data) {
+        Err(Error::SchemaDecode {
+            component_id,
+            field_id,
+            expected_type,
+            ..
+        }) => {
+            assert_eq!(Some(EnumTest::ID), component_id);
+            assert_eq!(Some(1), field_id);
+            assert_eq!("test_suite.Color", expected_type);
+        }
+        result => panic!("Expected a decode error, got {:?}", result),
+    }
+}
+
+#[test]
+fn known_enum_values_round_trip() {
+    let component = EnumTest {
+        paint: Paint {
+            color: Color::GREEN,
+        },
+    };
+
+    let data = EnumTest::to_data(&component).unwrap();
+    let paint = Paint::from_type(&data.fields().field::<SchemaObject>(2).get_or_default()).unwrap();
+    assert_eq!(Color::GREEN, paint.color);
+    assert!(Color::try_from(2).is_err());
+}
+
+#[test]
+fn unknown_authority_is_an_error() {
+    assert_eq!(Authority::Authoritative, Authority::try_from(1).unwrap());
+    match Authority::try_from(10) {
+        Err(Error::UnknownValue { value, .. }) => assert_eq!(10, value),
+        result => panic!("Expected an unknown value error, got {:?}", result),
+    }
+}
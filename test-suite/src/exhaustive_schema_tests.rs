@@ -0,0 +1,183 @@
+use crate::dynamic_tests::registry;
+use spatialos_sdk::worker::component::ComponentId;
+use spatialos_sdk::worker::dynamic::{FieldValue, SchemaObjectValue, SchemaRegistry, SchemaValue};
+use spatialos_sdk::worker::snapshot::entity_from_json;
+use spatialos_sdk::worker::EntityId;
+use spatialos_sdk_code_generator::schema_bundle::{
+    self, ComponentDefinition, FieldDefinition, FieldDefinition_FieldType, PrimitiveType,
+    TypeReference,
+};
+use std::collections::BTreeMap;
+use std::fs;
+
+// Options, lists and maps are only filled in down to this depth, so that sample values of
+// recursive types are finite.
+const MAX_SAMPLE_DEPTH: usize = 2;
+
+// Returns the components of the exhaustive test schema, which CI copies into
+// `schema/exhaustive` from the SDK downloaded with `--with-test-schema`.
+fn exhaustive_components() -> Vec<ComponentDefinition> {
+    let json = fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/build/schema/bundle.json"
+    ))
+    .unwrap();
+    let components: Vec<ComponentDefinition> = schema_bundle::load_bundle(&json)
+        .unwrap()
+        .schema_files
+        .into_iter()
+        .filter(|file| file.canonical_path.starts_with("exhaustive/"))
+        .flat_map(|file| file.components)
+        .collect();
+
+    assert!(
+        !components.is_empty(),
+        "The exhaustive test schema wasn't found in schema/exhaustive, see ci/install.sh"
+    );
+    components
+}
+
+fn component_fields<'a>(
+    registry: &'a SchemaRegistry,
+    component: &'a ComponentDefinition,
+) -> &'a [FieldDefinition] {
+    match component.data_definition {
+        Some(ref data_definition) => &registry.type_definition(data_definition).unwrap().fields,
+        None => &component.fields,
+    }
+}
+
+fn sample_object(
+    registry: &SchemaRegistry,
+    type_name: &str,
+    fields: &[FieldDefinition],
+    depth: usize,
+) -> SchemaObjectValue {
+    SchemaObjectValue {
+        type_name: type_name.to_owned(),
+        fields: fields
+            .iter()
+            .map(|field| FieldValue {
+                name: field.name.clone(),
+                field_id: field.field_id,
+                value: sample_field(registry, &field.field_type, depth),
+            })
+            .collect(),
+    }
+}
+
+fn sample_field(
+    registry: &SchemaRegistry,
+    field_type: &FieldDefinition_FieldType,
+    depth: usize,
+) -> SchemaValue {
+    let nested = depth < MAX_SAMPLE_DEPTH;
+    match field_type {
+        FieldDefinition_FieldType::Singular { type_reference } => {
+            sample_value(registry, type_reference, depth)
+        }
+        FieldDefinition_FieldType::Option { inner_type } if nested => SchemaValue::Option(Some(
+            Box::new(sample_value(registry, inner_type, depth + 1)),
+        )),
+        FieldDefinition_FieldType::List { inner_type } if nested => {
+            SchemaValue::List(vec![sample_value(registry, inner_type, depth + 1)])
+        }
+        FieldDefinition_FieldType::Map {
+            key_type,
+            value_type,
+        } if nested => SchemaValue::Map(vec![(
+            sample_value(registry, key_type, depth + 1),
+            sample_value(registry, value_type, depth + 1),
+        )]),
+        FieldDefinition_FieldType::Option { .. } => SchemaValue::Option(None),
+        FieldDefinition_FieldType::List { .. } => SchemaValue::List(Vec::new()),
+        FieldDefinition_FieldType::Map { .. } => SchemaValue::Map(Vec::new()),
+    }
+}
+
+// Returns a value that differs from the type's default, so that fields which aren't written or
+// read by the generated code are caught.
+fn sample_value(
+    registry: &SchemaRegistry,
+    value_type: &TypeReference,
+    depth: usize,
+) -> SchemaValue {
+    match value_type {
+        TypeReference::Primitive(primitive) => match primitive {
+            PrimitiveType::Invalid => panic!("Encountered invalid primitive."),
+            PrimitiveType::Int32 | PrimitiveType::Sint32 | PrimitiveType::Sfixed32 => {
+                SchemaValue::Int32(-7)
+            }
+            PrimitiveType::Int64 | PrimitiveType::Sint64 | PrimitiveType::Sfixed64 => {
+                SchemaValue::Int64(-7_000_000_000)
+            }
+            PrimitiveType::Uint32 | PrimitiveType::Fixed32 => SchemaValue::Uint32(7),
+            PrimitiveType::Uint64 | PrimitiveType::Fixed64 => SchemaValue::Uint64(7_000_000_000),
+            PrimitiveType::Bool => SchemaValue::Bool(true),
+            PrimitiveType::Float => SchemaValue::Float(1.5),
+            PrimitiveType::Double => SchemaValue::Double(2.5),
+            PrimitiveType::String => SchemaValue::String("sample".to_owned()),
+            PrimitiveType::EntityId => SchemaValue::EntityId(EntityId::new(7)),
+            PrimitiveType::Bytes => SchemaValue::Bytes(vec![1, 2, 3]),
+            PrimitiveType::Entity => SchemaValue::Entity(BTreeMap::new()),
+        },
+        TypeReference::Enum(enum_name) => {
+            let last = registry
+                .enum_definition(enum_name)
+                .and_then(|definition| definition.values.last())
+                .unwrap();
+            SchemaValue::Enum {
+                name: last.name.clone(),
+                value: last.value,
+            }
+        }
+        TypeReference::Type(type_name) => {
+            let fields = &registry.type_definition(type_name).unwrap().fields;
+            SchemaValue::Object(sample_object(registry, type_name, fields, depth))
+        }
+    }
+}
+
+// Converts the component to its generated type and back, and checks that it's unchanged.
+fn assert_round_trips(
+    registry: &SchemaRegistry,
+    component_id: ComponentId,
+    component: &SchemaObjectValue,
+) {
+    let mut json = serde_json::Map::new();
+    json.insert(
+        component.type_name.clone(),
+        registry.object_to_json(component).unwrap(),
+    );
+    let entity = entity_from_json(registry, &json.into())
+        .unwrap_or_else(|e| panic!("Failed to convert {}: {}", component.type_name, e));
+
+    let data = entity.schema(component_id).unwrap().unwrap();
+    let decoded = registry.decode_component(component_id, &data).unwrap();
+    assert_eq!(component, &decoded);
+}
+
+#[test]
+fn exhaustive_components_round_trip_sample_data() {
+    let registry = registry();
+    for component in exhaustive_components() {
+        let fields = component_fields(&registry, &component);
+        let value = sample_object(&registry, &component.qualified_name, fields, 0);
+        assert_round_trips(&registry, component.component_id, &value);
+    }
+}
+
+#[test]
+fn exhaustive_components_round_trip_default_data() {
+    let registry = registry();
+    for component in exhaustive_components() {
+        let mut value = SchemaObjectValue {
+            type_name: component.qualified_name.clone(),
+            fields: Vec::new(),
+        };
+        registry
+            .fill_component_defaults(component.component_id, &mut value)
+            .unwrap();
+        assert_round_trips(&registry, component.component_id, &value);
+    }
+}
@@ -0,0 +1,26 @@
+use crate::generated::test_suite::*;
+use std::collections::BTreeMap;
+
+pub fn leaf(value: i32) -> Node {
+    Node {
+        value,
+        child: None,
+        children: Vec::new(),
+        named_children: BTreeMap::new(),
+    }
+}
+
+// A component that sets every kind of recursive field in `Node`.
+pub fn recursion_test() -> RecursionTest {
+    let mut named_children = BTreeMap::new();
+    named_children.insert("named".to_owned(), leaf(3));
+    RecursionTest {
+        root: Node {
+            value: 1,
+            child: Some(Box::new(leaf(2))),
+            children: vec![leaf(4), leaf(5)],
+            named_children,
+        },
+        tree: Some(TreeA { value: 6, b: None }),
+    }
+}
@@ -0,0 +1,298 @@
+//! Ops received without internal serialization only carry schema data. These tests build
+//! such ops by hand and check that they give the same typed results as the ops produced
+//! with internal serialization, which carry user handles.
+
+use crate::generated::test_suite::*;
+use spatialos_sdk::worker::component::Component;
+use spatialos_sdk::worker::connection::Connection;
+use spatialos_sdk::worker::mock::MockConnection;
+use spatialos_sdk::worker::op::{QueryResponse, StatusCode, WorkerOp};
+use spatialos_sdk::worker::snapshot::SnapshotInputStream;
+use spatialos_sdk::worker::vtable::PASSTHROUGH_VTABLE;
+use spatialos_sdk::worker::{EntityId, RequestId};
+use spatialos_sdk_sys::worker::*;
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::CString;
+use std::ptr;
+
+fn node(value: i32) -> Node {
+    Node {
+        value,
+        child: Some(Box::new(Node {
+            value: value + 1,
+            child: None,
+            children: Vec::new(),
+            named_children: BTreeMap::new(),
+        })),
+        children: Vec::new(),
+        named_children: BTreeMap::new(),
+    }
+}
+
+fn component() -> RecursionTest {
+    RecursionTest {
+        root: node(1),
+        tree: Some(TreeA { value: 3, b: None }),
+    }
+}
+
+fn passthrough_data(schema_type: *mut Schema_ComponentData) -> Worker_ComponentData {
+    Worker_ComponentData {
+        reserved: ptr::null_mut(),
+        component_id: RecursionTest::ID,
+        schema_type,
+        user_handle: ptr::null_mut(),
+    }
+}
+
+fn make_op(op_type: Worker_OpType, op: Worker_Op_Union) -> Worker_Op {
+    Worker_Op {
+        op_type: op_type as u8,
+        op,
+    }
+}
+
+// Passes the first op produced by the mock connection, which uses user handles, to `f`.
+fn with_internal_op<F: FnOnce(WorkerOp)>(connection: &mut MockConnection, f: F) {
+    let ops = connection.get_op_list(0);
+    f(ops.iter().next().expect("Expected an op"));
+}
+
+#[test]
+fn add_component_op_is_deserialized_from_schema_data() {
+    let mut connection = MockConnection::new("worker");
+    connection.push_add_component(EntityId::new(1), component());
+    with_internal_op(&mut connection, |op| match op {
+        WorkerOp::AddComponent(op) => assert_eq!(&component(), op.get::<RecursionTest>().unwrap()),
+        op => panic!("Expected an AddComponent op, got {:?}", op),
+    });
+
+    let schema = RecursionTest::to_data(&component()).unwrap();
+    let raw_op = make_op(
+        Worker_OpType_WORKER_OP_TYPE_ADD_COMPONENT,
+        Worker_Op_Union {
+            add_component: Worker_AddComponentOp {
+                entity_id: 1,
+                data: passthrough_data(schema.internal),
+            },
+        },
+    );
+
+    match WorkerOp::from(&raw_op) {
+        WorkerOp::AddComponent(op) => assert_eq!(&component(), op.get::<RecursionTest>().unwrap()),
+        op => panic!("Expected an AddComponent op, got {:?}", op),
+    }
+
+    unsafe { Schema_DestroyComponentData(schema.internal) };
+}
+
+#[test]
+fn component_update_op_is_deserialized_from_schema_data() {
+    let update = RecursionTestUpdate {
+        root: Some(node(5)),
+        tree: Some(None),
+        node_event: vec![node(7)],
+    };
+
+    let mut connection = MockConnection::new("worker");
+    connection.push_component_update::<RecursionTest>(EntityId::new(1), update.clone());
+    with_internal_op(&mut connection, |op| match op {
+        WorkerOp::ComponentUpdate(op) => assert_eq!(&update, op.get::<RecursionTest>().unwrap()),
+        op => panic!("Expected a ComponentUpdate op, got {:?}", op),
+    });
+
+    let schema = RecursionTest::to_update(&update).unwrap();
+    let raw_op = make_op(
+        Worker_OpType_WORKER_OP_TYPE_COMPONENT_UPDATE,
+        Worker_Op_Union {
+            component_update: Worker_ComponentUpdateOp {
+                entity_id: 1,
+                update: Worker_ComponentUpdate {
+                    reserved: ptr::null_mut(),
+                    component_id: RecursionTest::ID,
+                    schema_type: schema.internal,
+                    user_handle: ptr::null_mut(),
+                },
+            },
+        },
+    );
+
+    match WorkerOp::from(&raw_op) {
+        WorkerOp::ComponentUpdate(op) => assert_eq!(&update, op.get::<RecursionTest>().unwrap()),
+        op => panic!("Expected a ComponentUpdate op, got {:?}", op),
+    }
+
+    unsafe { Schema_DestroyComponentUpdate(schema.internal) };
+}
+
+#[test]
+fn command_request_op_is_deserialized_from_schema_data() {
+    let request = RecursionTestCommandRequest::Echo(node(9));
+
+    let mut connection = MockConnection::new("worker");
+    connection.push_command_request::<RecursionTest>(EntityId::new(1), request.clone());
+    with_internal_op(&mut connection, |op| match op {
+        WorkerOp::CommandRequest(op) => assert_eq!(&request, op.get::<RecursionTest>().unwrap()),
+        op => panic!("Expected a CommandRequest op, got {:?}", op),
+    });
+
+    let schema = RecursionTest::to_request(&request).unwrap();
+    let caller_worker_id = CString::new("caller").unwrap();
+    let raw_op = make_op(
+        Worker_OpType_WORKER_OP_TYPE_COMMAND_REQUEST,
+        Worker_Op_Union {
+            command_request: Worker_CommandRequestOp {
+                request_id: 1,
+                entity_id: 1,
+                timeout_millis: 0,
+                caller_worker_id: caller_worker_id.as_ptr(),
+                caller_attribute_set: Worker_WorkerAttributes {
+                    attribute_count: 0,
+                    attributes: ptr::null_mut(),
+                },
+                request: Worker_CommandRequest {
+                    reserved: ptr::null_mut(),
+                    component_id: RecursionTest::ID,
+                    command_index: RecursionTest::get_request_command_index(&request),
+                    schema_type: schema.internal,
+                    user_handle: ptr::null_mut(),
+                },
+            },
+        },
+    );
+
+    match WorkerOp::from(&raw_op) {
+        WorkerOp::CommandRequest(op) => assert_eq!(&request, op.get::<RecursionTest>().unwrap()),
+        op => panic!("Expected a CommandRequest op, got {:?}", op),
+    }
+
+    unsafe { Schema_DestroyCommandRequest(schema.internal) };
+}
+
+#[test]
+fn command_response_op_is_deserialized_from_schema_data() {
+    let response = RecursionTestCommandResponse::Echo(node(11));
+
+    let mut connection = MockConnection::new("worker");
+    connection.push_command_response::<RecursionTest>(
+        RequestId::new(1),
+        EntityId::new(1),
+        StatusCode::Success(response.clone()),
+    );
+    with_internal_op(&mut connection, |op| match op {
+        WorkerOp::CommandResponse(op) => match &op.response {
+            StatusCode::Success(internal) => {
+                assert_eq!(&response, internal.get::<RecursionTest>().unwrap())
+            }
+            status => panic!("Expected a successful response, got {:?}", status),
+        },
+        op => panic!("Expected a CommandResponse op, got {:?}", op),
+    });
+
+    let schema = RecursionTest::to_response(&response).unwrap();
+    let raw_op = make_op(
+        Worker_OpType_WORKER_OP_TYPE_COMMAND_RESPONSE,
+        Worker_Op_Union {
+            command_response: Worker_CommandResponseOp {
+                request_id: 1,
+                entity_id: 1,
+                status_code: Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS as u8,
+                message: ptr::null(),
+                response: Worker_CommandResponse {
+                    reserved: ptr::null_mut(),
+                    component_id: RecursionTest::ID,
+                    command_index: RecursionTest::get_response_command_index(&response),
+                    schema_type: schema.internal,
+                    user_handle: ptr::null_mut(),
+                },
+            },
+        },
+    );
+
+    match WorkerOp::from(&raw_op) {
+        WorkerOp::CommandResponse(op) => match &op.response {
+            StatusCode::Success(schema_only) => {
+                assert_eq!(&response, schema_only.get::<RecursionTest>().unwrap())
+            }
+            status => panic!("Expected a successful response, got {:?}", status),
+        },
+        op => panic!("Expected a CommandResponse op, got {:?}", op),
+    }
+
+    unsafe { Schema_DestroyCommandResponse(schema.internal) };
+}
+
+#[test]
+fn entity_query_results_are_deserialized_from_schema_data() {
+    let schema = RecursionTest::to_data(&component()).unwrap();
+    let components = [passthrough_data(schema.internal)];
+    let entities = [Worker_Entity {
+        entity_id: 1,
+        component_count: components.len() as u32,
+        components: components.as_ptr(),
+    }];
+
+    let raw_op = make_op(
+        Worker_OpType_WORKER_OP_TYPE_ENTITY_QUERY_RESPONSE,
+        Worker_Op_Union {
+            entity_query_response: Worker_EntityQueryResponseOp {
+                request_id: 1,
+                status_code: Worker_StatusCode_WORKER_STATUS_CODE_SUCCESS as u8,
+                message: ptr::null(),
+                result_count: entities.len() as u32,
+                results: entities.as_ptr(),
+            },
+        },
+    );
+
+    let entity = match WorkerOp::from(&raw_op) {
+        WorkerOp::EntityQueryResponse(op) => match op.status_code {
+            StatusCode::Success(QueryResponse::Snapshot(mut entities)) => {
+                entities.remove(&EntityId::new(1)).unwrap()
+            }
+            status => panic!("Expected a snapshot result, got {:?}", status),
+        },
+        op => panic!("Expected an EntityQueryResponse op, got {:?}", op),
+    };
+
+    // The entity owns its own copy of the data, so it outlives the schema data in the op.
+    unsafe { Schema_DestroyComponentData(schema.internal) };
+
+    assert_eq!(&component(), entity.get::<RecursionTest>().unwrap());
+}
+
+#[test]
+fn snapshot_entities_are_deserialized_from_schema_data() {
+    let snapshot_path = env::temp_dir().join("internal_serialization.snapshot");
+    let path = CString::new(snapshot_path.to_str().unwrap()).unwrap();
+
+    // Write the entity as raw schema data, without going through the generated vtables.
+    let parameters = Worker_SnapshotParameters {
+        component_vtable_count: 0,
+        component_vtables: ptr::null(),
+        default_component_vtable: &PASSTHROUGH_VTABLE,
+    };
+    let schema = RecursionTest::to_data(&component()).unwrap();
+    let components = [passthrough_data(schema.internal)];
+    unsafe {
+        let stream = Worker_SnapshotOutputStream_Create(path.as_ptr(), &parameters);
+        Worker_SnapshotOutputStream_WriteEntity(
+            stream,
+            &Worker_Entity {
+                entity_id: 1,
+                component_count: components.len() as u32,
+                components: components.as_ptr(),
+            },
+        );
+        Worker_SnapshotOutputStream_Destroy(stream);
+        Schema_DestroyComponentData(schema.internal);
+    }
+
+    let mut snapshot = SnapshotInputStream::new(snapshot_path).unwrap();
+    let (entity_id, entity) = snapshot.read_entity().unwrap();
+
+    assert_eq!(EntityId::new(1), entity_id);
+    assert_eq!(&component(), entity.get::<RecursionTest>().unwrap());
+    assert!(!snapshot.has_next());
+}
@@ -1,7 +1,44 @@
 #[rustfmt::skip]
 pub mod generated;
+mod point;
 
+pub use point::Point;
+
+#[cfg(test)]
+pub mod codegen_options_tests;
+#[cfg(test)]
+pub mod command_handlers_tests;
+#[cfg(test)]
+pub mod diff_tests;
+#[cfg(test)]
+pub mod dispatcher_tests;
+#[cfg(test)]
+pub mod dynamic_tests;
 #[cfg(test)]
 pub mod entity_builder_tests;
 #[cfg(test)]
+pub mod entity_field_tests;
+#[cfg(test)]
+pub mod error_tests;
+#[cfg(test)]
+pub mod exhaustive_schema_tests;
+#[cfg(test)]
+pub mod fixtures;
+#[cfg(test)]
+pub mod internal_serialization_tests;
+#[cfg(test)]
+pub mod mock_connection_tests;
+#[cfg(test)]
+pub mod op_stream_tests;
+#[cfg(test)]
+pub mod recursive_type_tests;
+#[cfg(test)]
+pub mod schema_access_tests;
+#[cfg(test)]
+pub mod serde_tests;
+#[cfg(test)]
 pub mod snapshot_integration_tests;
+#[cfg(test)]
+pub mod update_tests;
+#[cfg(test)]
+pub mod view_tests;
@@ -0,0 +1,206 @@
+use crate::generated::improbable::restricted::*;
+use crate::generated::improbable::*;
+use approx;
+use spatialos_sdk::worker::component::{Component, UpdateParameters};
+use spatialos_sdk::worker::connection::Connection;
+use spatialos_sdk::worker::mock::{MockConnection, SentMessage};
+use spatialos_sdk::worker::op::{OwnedWorkerOp, StatusCode, WorkerOp};
+use spatialos_sdk::worker::{Authority, EntityId, LogLevel};
+
+#[test]
+fn scripted_ops_are_returned_in_order() {
+    let mut connection = MockConnection::new("worker");
+    let entity_id = EntityId::new(1);
+
+    connection.push_add_entity(entity_id);
+    connection.push_add_component(
+        entity_id,
+        Position {
+            coords: Coordinates {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+        },
+    );
+    connection.push_authority_change(entity_id, Position::ID, Authority::Authoritative);
+
+    let op_list = connection.get_op_list(0);
+    assert_eq!(3, op_list.len());
+
+    let ops: Vec<WorkerOp> = op_list.iter().collect();
+    match &ops[0] {
+        WorkerOp::AddEntity(op) => assert_eq!(entity_id, op.entity_id),
+        _ => panic!("Expected an AddEntity op"),
+    }
+    match &ops[1] {
+        WorkerOp::AddComponent(op) => {
+            let position = op.get::<Position>().unwrap();
+            approx::assert_abs_diff_eq!(2.0, position.coords.y);
+            assert!(op.get::<Metadata>().is_none());
+        }
+        _ => panic!("Expected an AddComponent op"),
+    }
+    match &ops[2] {
+        WorkerOp::AuthorityChange(op) => assert!(op.authority.has_authority()),
+        _ => panic!("Expected an AuthorityChange op"),
+    }
+
+    // Ops are only returned once.
+    assert!(connection.get_op_list(0).is_empty());
+}
+
+#[test]
+fn command_requests_can_be_responded_to() {
+    let mut connection = MockConnection::new("worker");
+    let entity_id = EntityId::new(1);
+
+    let request_id = connection.push_command_request::<Worker>(
+        entity_id,
+        WorkerCommandRequest::Disconnect(DisconnectRequest {}),
+    );
+
+    for op in &connection.get_op_list(0) {
+        if let WorkerOp::CommandRequest(op) = op {
+            assert_eq!(request_id, op.request_id);
+            assert!(op.get::<Worker>().is_some());
+            connection.send_command_response::<Worker>(
+                op.request_id,
+                WorkerCommandResponse::Disconnect(DisconnectResponse {}),
+            );
+        }
+    }
+
+    let responses = connection.sent_command_responses::<Worker>();
+    assert_eq!(1, responses.len());
+    assert_eq!(request_id, responses[0].0);
+}
+
+#[test]
+fn command_responses_can_be_scripted() {
+    let mut connection = MockConnection::new("worker");
+    let entity_id = EntityId::new(1);
+
+    let request_id = connection.send_command_request::<Worker>(
+        entity_id,
+        WorkerCommandRequest::Disconnect(DisconnectRequest {}),
+        None,
+        Default::default(),
+    );
+    assert_eq!(1, connection.sent_command_requests::<Worker>().len());
+
+    connection.push_command_response::<Worker>(
+        request_id,
+        entity_id,
+        StatusCode::Success(WorkerCommandResponse::Disconnect(DisconnectResponse {})),
+    );
+    connection.push_command_response::<Worker>(
+        request_id,
+        entity_id,
+        StatusCode::Timeout("Timed out".to_owned()),
+    );
+
+    let op_list = connection.get_op_list(0);
+    let ops: Vec<WorkerOp> = op_list.iter().collect();
+    match &ops[0] {
+        WorkerOp::CommandResponse(op) => {
+            assert_eq!(request_id, op.request_id);
+            match &op.response {
+                StatusCode::Success(response) => assert!(response.get::<Worker>().is_some()),
+                _ => panic!("Expected a successful response"),
+            }
+        }
+        _ => panic!("Expected a CommandResponse op"),
+    }
+    match &ops[1] {
+        WorkerOp::CommandResponse(op) => match &op.response {
+            StatusCode::Timeout(message) => assert_eq!("Timed out", message),
+            _ => panic!("Expected a timeout"),
+        },
+        _ => panic!("Expected a CommandResponse op"),
+    }
+}
+
+#[test]
+fn sent_messages_are_recorded() {
+    let mut connection = MockConnection::new("worker").with_attribute("server");
+    let entity_id = EntityId::new(1);
+
+    assert_eq!("worker", connection.get_worker_id());
+    assert_eq!(&["server".to_owned()], connection.get_worker_attributes());
+
+    connection.send_log_message(LogLevel::Warn, "logger", "message", Some(entity_id));
+    connection.send_component_update::<Position>(
+        entity_id,
+        PositionUpdate {
+            coords: Some(Coordinates {
+                x: 0.0,
+                y: 5.0,
+                z: 0.0,
+            }),
+        },
+        UpdateParameters::new(),
+    );
+    connection.send_authority_loss_imminent_acknowledgement(entity_id, Position::ID);
+
+    let messages = connection.sent_messages();
+    assert_eq!(3, messages.len());
+    match &messages[0] {
+        SentMessage::LogMessage { level, message, .. } => {
+            assert_eq!(LogLevel::Warn, *level);
+            assert_eq!("message", message);
+        }
+        _ => panic!("Expected a log message"),
+    }
+
+    let updates = connection.sent_component_updates::<Position>();
+    assert_eq!(1, updates.len());
+    assert_eq!(entity_id, updates[0].0);
+    assert!(updates[0].1.coords.is_some());
+    assert!(connection.sent_component_updates::<Metadata>().is_empty());
+
+    connection.clear_sent_messages();
+    assert!(connection.sent_messages().is_empty());
+}
+
+#[test]
+fn command_failure_rejects_nul_bytes() {
+    let mut connection = MockConnection::new("worker");
+    let request_id = connection.push_command_request::<Worker>(
+        EntityId::new(1),
+        WorkerCommandRequest::Disconnect(DisconnectRequest {}),
+    );
+
+    assert!(connection
+        .send_command_failure(request_id, "bad\0message")
+        .is_err());
+    assert!(connection
+        .send_command_failure(request_id, "failed")
+        .is_ok());
+    assert_eq!(1, connection.sent_messages().len());
+}
+
+#[test]
+fn ops_can_be_owned_and_sent_across_threads() {
+    let mut connection = MockConnection::new("worker");
+    let entity_id = EntityId::new(1);
+    connection.push_add_component(
+        entity_id,
+        Metadata {
+            entity_type: "test".to_owned(),
+        },
+    );
+
+    let owned_ops: Vec<OwnedWorkerOp> = connection
+        .get_op_list(0)
+        .iter()
+        .map(|op| op.to_owned().unwrap())
+        .collect();
+
+    let handle = std::thread::spawn(move || match &owned_ops[0] {
+        OwnedWorkerOp::AddComponent(op) => op.get::<Metadata>().unwrap().entity_type.clone(),
+        _ => panic!("Expected an AddComponent op"),
+    });
+
+    assert_eq!("test", handle.join().unwrap());
+}
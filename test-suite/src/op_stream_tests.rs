@@ -0,0 +1,57 @@
+use futures::{executor::block_on, StreamExt};
+use spatialos_sdk::worker::connection::{Connection, OpStream};
+use spatialos_sdk::worker::mock::MockConnection;
+use spatialos_sdk::worker::op::{DisconnectOp, OwnedWorkerOp, WorkerOp};
+use spatialos_sdk::worker::EntityId;
+
+#[test]
+fn op_stream_yields_op_lists_until_disconnected() {
+    let mut connection = MockConnection::new("worker");
+    connection.push_add_entity(EntityId::new(1));
+
+    let mut stream = OpStream::new(connection, 0);
+
+    let op_list = block_on(stream.next()).unwrap();
+    assert_eq!(1, op_list.len());
+
+    stream
+        .connection()
+        .push_op(OwnedWorkerOp::Disconnect(DisconnectOp {
+            reason: "Disconnected".to_owned(),
+        }));
+
+    let op_list = block_on(stream.next()).unwrap();
+    match op_list.iter().next() {
+        Some(WorkerOp::Disconnect(op)) => assert_eq!("Disconnected", op.reason),
+        _ => panic!("Expected a Disconnect op"),
+    }
+
+    assert!(block_on(stream.next()).is_none());
+}
+
+#[test]
+fn op_stream_connection_can_be_used_while_fetching() {
+    let mut stream = OpStream::new(MockConnection::new("worker"), 0);
+
+    // Start a fetch that won't complete, as there are no ops to receive.
+    let waker = futures::task::noop_waker();
+    let mut cx = std::task::Context::from_waker(&waker);
+    assert!(stream.poll_next_unpin(&mut cx).is_pending());
+
+    assert_eq!("worker", stream.connection().get_worker_id());
+    assert_eq!("worker", stream.into_inner().get_worker_id());
+}
+
+#[test]
+fn dropping_a_pending_op_stream_stops_fetching() {
+    let mut stream = OpStream::new(MockConnection::new("worker"), 0);
+
+    // The mock connection never receives any ops, so the fetch thread would keep
+    // fetching forever if the stream didn't stop it when dropped.
+    let waker = futures::task::noop_waker();
+    let mut cx = std::task::Context::from_waker(&waker);
+    assert!(stream.poll_next_unpin(&mut cx).is_pending());
+
+    // Dropping the stream joins the fetch thread, so this only returns once it stops.
+    drop(stream);
+}
@@ -0,0 +1,23 @@
+use crate::generated::test_suite::Vector3;
+use spatialos_sdk::serde::{Deserialize, Serialize};
+
+/// Stands in for an external math type, which `test_suite.Vector3` is mapped to in `Spatial.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "spatialos_sdk::serde")]
+pub struct Point(pub f64, pub f64, pub f64);
+
+impl From<Vector3> for Point {
+    fn from(vector: Vector3) -> Self {
+        Point(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Point> for Vector3 {
+    fn from(point: Point) -> Self {
+        Vector3 {
+            x: point.0,
+            y: point.1,
+            z: point.2,
+        }
+    }
+}
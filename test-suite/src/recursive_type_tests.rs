@@ -0,0 +1,132 @@
+use crate::fixtures::leaf;
+use crate::generated::test_suite::*;
+use spatialos_sdk::worker::component::{Component, ComponentData};
+use spatialos_sdk::worker::internal::schema::SchemaCommandRequest;
+use spatialos_sdk::Error;
+use std::collections::BTreeMap;
+
+fn tree() -> Node {
+    let mut named_children = BTreeMap::new();
+    named_children.insert(
+        "named".to_owned(),
+        Node {
+            child: Some(Box::new(leaf(4))),
+            ..leaf(3)
+        },
+    );
+
+    Node {
+        value: 1,
+        child: Some(Box::new(Node {
+            child: Some(Box::new(leaf(5))),
+            ..leaf(2)
+        })),
+        children: vec![leaf(6), leaf(7)],
+        named_children,
+    }
+}
+
+fn assert_tree(node: &Node) {
+    assert_eq!(1, node.value);
+
+    let child = node.child.as_ref().unwrap();
+    assert_eq!(2, child.value);
+    assert_eq!(5, child.child.as_ref().unwrap().value);
+    assert!(child.child.as_ref().unwrap().child.is_none());
+
+    let values: Vec<i32> = node.children.iter().map(|child| child.value).collect();
+    assert_eq!(vec![6, 7], values);
+
+    let named = &node.named_children["named"];
+    assert_eq!(3, named.value);
+    assert_eq!(4, named.child.as_ref().unwrap().value);
+}
+
+#[test]
+fn recursive_component_data_round_trips() {
+    let component = RecursionTest {
+        root: tree(),
+        tree: Some(TreeA {
+            value: 1,
+            b: Some(Box::new(TreeB {
+                a: Box::new(TreeA { value: 2, b: None }),
+            })),
+        }),
+    };
+
+    let data = RecursionTest::to_data(&component).unwrap();
+    let deserialized = RecursionTest::from_data(&data).unwrap();
+
+    assert_tree(&deserialized.root);
+    let tree = deserialized.tree.unwrap();
+    assert_eq!(1, tree.value);
+    let inner = &tree.b.unwrap().a;
+    assert_eq!(2, inner.value);
+    assert!(inner.b.is_none());
+}
+
+#[test]
+fn recursive_component_update_round_trips() {
+    let update = RecursionTestUpdate {
+        root: Some(tree()),
+        tree: Some(None),
+        node_event: vec![tree(), leaf(8)],
+    };
+
+    let serialized = RecursionTest::to_update(&update).unwrap();
+    let deserialized = RecursionTest::from_update(&serialized).unwrap();
+
+    assert_tree(deserialized.root.as_ref().unwrap());
+    assert!(deserialized.tree.unwrap().is_none());
+    assert_eq!(2, deserialized.node_event.len());
+    assert_tree(&deserialized.node_event[0]);
+    assert_eq!(8, deserialized.node_event[1].value);
+}
+
+#[test]
+fn recursive_component_data_merges_updates() {
+    let mut component = RecursionTest {
+        root: leaf(0),
+        tree: None,
+    };
+
+    component.merge(RecursionTestUpdate {
+        root: Some(tree()),
+        ..Default::default()
+    });
+
+    assert_tree(&component.root);
+}
+
+#[test]
+fn recursive_commands_round_trip() {
+    let request = RecursionTestCommandRequest::Echo(tree());
+    let serialized = RecursionTest::to_request(&request).unwrap();
+    let index = RecursionTest::get_request_command_index(&request);
+    match RecursionTest::from_request(index, &serialized).unwrap() {
+        RecursionTestCommandRequest::Echo(node) => assert_tree(&node),
+    }
+
+    let response = RecursionTestCommandResponse::Echo(tree());
+    let serialized = RecursionTest::to_response(&response).unwrap();
+    let index = RecursionTest::get_response_command_index(&response);
+    match RecursionTest::from_response(index, &serialized).unwrap() {
+        RecursionTestCommandResponse::Echo(node) => assert_tree(&node),
+    }
+}
+
+#[test]
+fn unknown_command_index_is_a_decode_error() {
+    let request = SchemaCommandRequest::new();
+    match RecursionTest::from_request(100, &request) {
+        Err(Error::SchemaDecode {
+            component_id,
+            expected_type,
+            ..
+        }) => {
+            assert_eq!(Some(RecursionTest::ID), component_id);
+            assert_eq!("test_suite.RecursionTest", expected_type);
+        }
+        result => panic!("Expected a decode error, got {:?}", result),
+    }
+}
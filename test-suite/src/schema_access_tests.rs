@@ -0,0 +1,93 @@
+use crate::generated::improbable::restricted::*;
+use crate::generated::improbable::*;
+use spatialos_sdk::worker::component::Component;
+use spatialos_sdk::worker::connection::Connection;
+use spatialos_sdk::worker::entity_builder::EntityBuilder;
+use spatialos_sdk::worker::internal::schema::{
+    SchemaDouble, SchemaObject, SchemaObjectField, SchemaPrimitiveField, SchemaString,
+    SchemaStringField,
+};
+use spatialos_sdk::worker::mock::MockConnection;
+use spatialos_sdk::worker::op::WorkerOp;
+use spatialos_sdk::worker::EntityId;
+
+fn position(x: f64, y: f64, z: f64) -> Position {
+    Position {
+        coords: Coordinates { x, y, z },
+    }
+}
+
+fn read_coords(coords: &SchemaObject) -> (f64, f64, f64) {
+    (
+        coords.field::<SchemaDouble>(1).get_or_default(),
+        coords.field::<SchemaDouble>(2).get_or_default(),
+        coords.field::<SchemaDouble>(3).get_or_default(),
+    )
+}
+
+#[test]
+fn add_component_op_exposes_schema_data() {
+    let mut connection = MockConnection::new("worker");
+    connection.push_add_component(EntityId::new(1), position(1.0, 2.0, 3.0));
+
+    let ops = connection.get_op_list(0);
+    match ops.iter().next() {
+        Some(WorkerOp::AddComponent(op)) => {
+            let data = op.schema().unwrap();
+            let coords = data.fields().field::<SchemaObject>(1).get_or_default();
+            assert_eq!((1.0, 2.0, 3.0), read_coords(&coords));
+        }
+        op => panic!("Expected an AddComponent op, got {:?}", op),
+    }
+}
+
+#[test]
+fn component_update_op_exposes_schema_update() {
+    let mut connection = MockConnection::new("worker");
+    connection.push_component_update::<Metadata>(
+        EntityId::new(1),
+        MetadataUpdate {
+            entity_type: Some("tree".to_owned()),
+        },
+    );
+
+    let ops = connection.get_op_list(0);
+    match ops.iter().next() {
+        Some(WorkerOp::ComponentUpdate(op)) => {
+            let update = op.schema().unwrap();
+            let fields = update.fields();
+            assert_eq!("tree", fields.field::<SchemaString>(1).get_or_default());
+            assert!(update.cleared_fields().is_empty());
+        }
+        op => panic!("Expected a ComponentUpdate op, got {:?}", op),
+    }
+}
+
+#[test]
+fn command_request_op_exposes_schema_request() {
+    let mut connection = MockConnection::new("worker");
+    connection.push_command_request::<Worker>(
+        EntityId::new(1),
+        WorkerCommandRequest::Disconnect(DisconnectRequest {}),
+    );
+
+    let ops = connection.get_op_list(0);
+    match ops.iter().next() {
+        Some(WorkerOp::CommandRequest(op)) => {
+            let request = op.schema().unwrap();
+            assert!(request.object().unique_field_ids().is_empty());
+        }
+        op => panic!("Expected a CommandRequest op, got {:?}", op),
+    }
+}
+
+#[test]
+fn entity_exposes_schema_data() {
+    let entity = EntityBuilder::new(4.0, 5.0, 6.0, "worker").build().unwrap();
+
+    assert!(entity.schema(Persistence::ID).is_none());
+
+    let data = entity.schema(Position::ID).unwrap().unwrap();
+    let coords = data.fields().field::<SchemaObject>(1).get_or_default();
+    assert_eq!((4.0, 5.0, 6.0), read_coords(&coords));
+}
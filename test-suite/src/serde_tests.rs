@@ -0,0 +1,85 @@
+use crate::fixtures::recursion_test;
+use crate::generated::test_suite::*;
+use serde_json::json;
+use spatialos_sdk::worker::entity::Entity;
+use spatialos_sdk::worker::entity_builder::EntityBuilder;
+use spatialos_sdk::worker::EntityId;
+use std::collections::BTreeMap;
+
+#[test]
+fn components_round_trip_through_json() {
+    let component = RecursionTest {
+        tree: None,
+        ..recursion_test()
+    };
+
+    let json = serde_json::to_value(&component).unwrap();
+    assert_eq!(2, json["root"]["child"]["value"]);
+    assert_eq!(3, json["root"]["named_children"]["named"]["value"]);
+    assert!(json["tree"].is_null());
+
+    let deserialized: RecursionTest = serde_json::from_value(json).unwrap();
+    assert_eq!(component, deserialized);
+}
+
+#[test]
+fn enums_are_serialized_by_name() {
+    let component = EnumTest {
+        paint: Paint {
+            color: Color::GREEN,
+        },
+    };
+
+    let json = serde_json::to_value(&component).unwrap();
+    assert_eq!(json!({ "paint": { "color": "GREEN" } }), json);
+}
+
+#[test]
+fn entity_ids_are_serialized_as_numbers() {
+    let json = serde_json::to_value(EntityId::new(5)).unwrap();
+    assert_eq!(json!(5), json);
+    assert_eq!(EntityId::new(5), serde_json::from_value(json).unwrap());
+}
+
+#[test]
+fn updates_only_contain_set_fields() {
+    let update = RecursionTestUpdate {
+        root: None,
+        tree: Some(None),
+        node_event: Vec::new(),
+    };
+
+    let json = serde_json::to_value(&update).unwrap();
+    assert_eq!(json!({ "tree": null }), json);
+
+    // A field that's present but `null` clears the option, rather than leaving it unchanged.
+    let deserialized: RecursionTestUpdate = serde_json::from_value(json).unwrap();
+    assert_eq!(update, deserialized);
+}
+
+#[test]
+fn entity_fields_round_trip_through_json() {
+    let component = EntityTest {
+        entity: EntityBuilder::new(1.0, 2.0, 3.0, "rusty").build().unwrap(),
+        optional_entity: None,
+        entity_list: Vec::new(),
+        entity_map: BTreeMap::new(),
+    };
+
+    let json = serde_json::to_string(&component).unwrap();
+    let deserialized: EntityTest = serde_json::from_str(&json).unwrap();
+    assert_eq!(component, deserialized);
+}
+
+#[test]
+fn entity_components_are_base64_strings_in_json() {
+    let entity = EntityBuilder::new(1.0, 2.0, 3.0, "rusty").build().unwrap();
+
+    let json = serde_json::to_value(&entity).unwrap();
+    let components = json.as_object().unwrap();
+    assert_eq!(entity.component_ids().count(), components.len());
+    assert!(components.values().all(|data| data.is_string()));
+
+    let deserialized: Entity = serde_json::from_value(json).unwrap();
+    assert_eq!(entity, deserialized);
+}
@@ -1,5 +1,6 @@
 use approx;
 use spatialos_sdk::worker::{entity::Entity, snapshot::*, EntityId};
+use spatialos_sdk::Error;
 use std::env;
 
 use crate::generated::improbable::*;
@@ -36,7 +37,8 @@ pub fn create_and_read_snapshot() {
 
         assert!(snapshot.has_next());
 
-        let entity = snapshot.read_entity().expect("Error");
+        let (entity_id, entity) = snapshot.read_entity().expect("Error");
+        assert_eq!(EntityId::new(1), entity_id);
 
         let position = entity.get::<Position>();
         assert!(position.is_some());
@@ -56,7 +58,122 @@ pub fn create_and_read_snapshot() {
     }
 }
 
-fn get_test_entity() -> Result<Entity, String> {
+#[test]
+pub fn iterate_snapshot() {
+    let snapshot_path = env::temp_dir().join("test_iterate.snapshot");
+
+    {
+        let mut output = SnapshotOutputStream::new(snapshot_path.clone()).expect("Error");
+        for id in 1..=3 {
+            let entity = get_test_entity().expect("Error");
+            output
+                .write_entity(EntityId::new(id), &entity)
+                .expect("Error");
+        }
+    }
+
+    let ids = SnapshotInputStream::new(snapshot_path)
+        .expect("Error")
+        .map(|result| result.expect("Error").0)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![EntityId::new(1), EntityId::new(2), EntityId::new(3)],
+        ids
+    );
+}
+
+#[test]
+pub fn transform_snapshot() {
+    let input_path = env::temp_dir().join("test_transform_input.snapshot");
+    let output_path = env::temp_dir().join("test_transform_output.snapshot");
+
+    {
+        let mut output = SnapshotOutputStream::new(input_path.clone()).expect("Error");
+        for id in 1..=4 {
+            let entity = get_test_entity().expect("Error");
+            output
+                .write_entity(EntityId::new(id), &entity)
+                .expect("Error");
+        }
+    }
+
+    transform(
+        SnapshotInputStream::new(input_path).expect("Error"),
+        SnapshotOutputStream::new(output_path.clone()).expect("Error"),
+        |entity_id, mut entity| {
+            if entity_id.id % 2 == 0 {
+                return None;
+            }
+
+            entity
+                .set(Position {
+                    coords: Coordinates {
+                        x: entity_id.id as f64,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                })
+                .expect("Error");
+            Some(entity)
+        },
+    )
+    .expect("Error");
+
+    let entities = SnapshotInputStream::new(output_path)
+        .expect("Error")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Error");
+    assert_eq!(2, entities.len());
+    for (entity_id, entity) in entities {
+        assert_eq!(1, entity_id.id % 2);
+        let position = entity.get::<Position>().expect("Error");
+        approx::assert_abs_diff_eq!(entity_id.id as f64, position.coords.x);
+    }
+}
+
+#[test]
+pub fn snapshot_json_round_trip() {
+    let input_path = env::temp_dir().join("test_json_input.snapshot");
+    let output_path = env::temp_dir().join("test_json_output.snapshot");
+    let registry = crate::dynamic_tests::registry();
+
+    {
+        let entity = get_test_entity().expect("Error");
+        SnapshotOutputStream::new(input_path.clone())
+            .expect("Error")
+            .write_entity(EntityId::new(1), &entity)
+            .expect("Error");
+    }
+
+    let json = snapshot_to_json(&registry, input_path).expect("Error");
+    let entity = &json["entities"][0];
+    assert_eq!(1, entity["entity_id"]);
+    assert_eq!(
+        10.0,
+        entity["components"]["improbable.Position"]["coords"]["x"]
+    );
+    assert!(entity["components"]["improbable.Persistence"].is_object());
+    assert_eq!(
+        "RustWorker",
+        entity["components"]["improbable.EntityAcl"]["read_acl"]["attribute_set"][0]["attribute"]
+            [0]
+    );
+
+    snapshot_from_json(&registry, &json, output_path.clone()).expect("Error");
+
+    let entities = SnapshotInputStream::new(output_path)
+        .expect("Error")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Error");
+    assert_eq!(1, entities.len());
+    let (entity_id, entity) = &entities[0];
+    assert_eq!(EntityId::new(1), *entity_id);
+    let position = entity.get::<Position>().expect("Error");
+    approx::assert_abs_diff_eq!(10.0, position.coords.x);
+    assert!(entity.get::<Persistence>().is_some());
+}
+
+fn get_test_entity() -> Result<Entity, Error> {
     let mut builder = EntityBuilder::new(10.0, -10.0, 0.0, "RustWorker");
     builder.set_persistent("RustWorker");
     builder.build()
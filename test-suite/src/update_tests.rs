@@ -0,0 +1,89 @@
+use crate::generated::test_suite::*;
+use crate::Point;
+use spatialos_sdk::worker::component::{Component, ComponentData, ComponentUpdate};
+use spatialos_sdk_sys::worker::Schema_DestroyComponentUpdate;
+use std::collections::BTreeMap;
+
+fn node(value: i32) -> Node {
+    Node {
+        value,
+        child: None,
+        children: Vec::new(),
+        named_children: BTreeMap::new(),
+    }
+}
+
+fn round_trip(update: &RecursionTestUpdate) -> RecursionTestUpdate {
+    let serialized = RecursionTest::to_update(update).unwrap();
+    let deserialized = RecursionTest::from_update(&serialized);
+    unsafe { Schema_DestroyComponentUpdate(serialized.internal) };
+    deserialized.unwrap()
+}
+
+#[test]
+fn events_round_trip_through_schema_updates() {
+    let update = RecursionTestUpdate {
+        root: Some(node(1)),
+        node_event: vec![node(2), node(3)],
+        ..Default::default()
+    };
+
+    assert_eq!(update, round_trip(&update));
+}
+
+#[test]
+fn merging_updates_appends_events() {
+    let mut update = RecursionTestUpdate {
+        node_event: vec![node(1)],
+        ..Default::default()
+    };
+    update.merge(RecursionTestUpdate {
+        root: Some(node(2)),
+        node_event: vec![node(3)],
+        ..Default::default()
+    });
+
+    assert_eq!(Some(node(2)), update.root);
+    assert_eq!(vec![node(1), node(3)], update.node_event);
+}
+
+fn cleared_update() -> TypeMappingTestUpdate {
+    TypeMappingTestUpdate {
+        position: None,
+        target: Some(None),
+        path: Some(Vec::new()),
+        waypoints: Some(BTreeMap::new()),
+    }
+}
+
+#[test]
+fn emptied_fields_are_written_as_cleared_fields() {
+    let update = cleared_update();
+    let serialized = TypeMappingTest::to_update(&update).unwrap();
+    let mut cleared_fields = serialized.cleared_fields();
+    let deserialized = TypeMappingTest::from_update(&serialized);
+    unsafe { Schema_DestroyComponentUpdate(serialized.internal) };
+
+    cleared_fields.sort();
+    assert_eq!(vec![2, 3, 4], cleared_fields);
+    assert_eq!(update, deserialized.unwrap());
+}
+
+#[test]
+fn merging_cleared_fields_clears_the_component() {
+    let mut waypoints = BTreeMap::new();
+    waypoints.insert("home".to_owned(), Point(1.0, 1.0, 1.0));
+    let mut component = TypeMappingTest {
+        position: Point(0.0, 0.0, 0.0),
+        target: Some(Point(1.0, 2.0, 3.0)),
+        path: vec![Point(4.0, 5.0, 6.0)],
+        waypoints,
+    };
+
+    component.merge(cleared_update());
+
+    assert_eq!(Point(0.0, 0.0, 0.0), component.position);
+    assert_eq!(None, component.target);
+    assert!(component.path.is_empty());
+    assert!(component.waypoints.is_empty());
+}
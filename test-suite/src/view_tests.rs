@@ -0,0 +1,132 @@
+use crate::generated::improbable::*;
+use approx;
+use spatialos_sdk::worker::component::Component;
+use spatialos_sdk::worker::connection::Connection;
+use spatialos_sdk::worker::mock::MockConnection;
+use spatialos_sdk::worker::op::{CriticalSectionOp, OwnedWorkerOp};
+use spatialos_sdk::worker::view::View;
+use spatialos_sdk::worker::{Authority, EntityId};
+
+fn position(x: f64) -> Position {
+    Position {
+        coords: Coordinates { x, y: 0.0, z: 0.0 },
+    }
+}
+
+fn process(connection: &mut MockConnection, view: &mut View) {
+    view.process_op_list(&connection.get_op_list(0));
+}
+
+#[test]
+fn view_tracks_entities_and_component_data() {
+    let mut connection = MockConnection::new("worker");
+    let mut view = View::new();
+    let entity_id = EntityId::new(1);
+
+    connection.push_add_entity(entity_id);
+    connection.push_add_component(entity_id, position(1.0));
+    process(&mut connection, &mut view);
+
+    assert!(view.contains_entity(entity_id));
+    assert!(view.has_component::<Position>(entity_id));
+    assert!(!view.has_component::<Metadata>(entity_id));
+    approx::assert_abs_diff_eq!(1.0, view.get::<Position>(entity_id).unwrap().coords.x);
+
+    connection.push_component_update::<Position>(
+        entity_id,
+        PositionUpdate {
+            coords: Some(Coordinates {
+                x: 5.0,
+                y: 0.0,
+                z: 0.0,
+            }),
+        },
+    );
+    process(&mut connection, &mut view);
+    approx::assert_abs_diff_eq!(5.0, view.get::<Position>(entity_id).unwrap().coords.x);
+
+    connection.push_remove_component::<Position>(entity_id);
+    process(&mut connection, &mut view);
+    assert!(view.get::<Position>(entity_id).is_none());
+
+    connection.push_remove_entity(entity_id);
+    process(&mut connection, &mut view);
+    assert!(!view.contains_entity(entity_id));
+}
+
+#[test]
+fn view_tracks_authority() {
+    let mut connection = MockConnection::new("worker");
+    let mut view = View::new();
+    let entity_id = EntityId::new(1);
+
+    connection.push_add_entity(entity_id);
+    connection.push_add_component(entity_id, position(0.0));
+    process(&mut connection, &mut view);
+    assert_eq!(
+        Some(Authority::NotAuthoritative),
+        view.authority::<Position>(entity_id)
+    );
+
+    connection.push_authority_change(entity_id, Position::ID, Authority::Authoritative);
+    process(&mut connection, &mut view);
+    assert!(view.has_authority::<Position>(entity_id));
+
+    connection.push_authority_change(entity_id, Position::ID, Authority::AuthorityLossImminent);
+    process(&mut connection, &mut view);
+    assert!(view.has_authority::<Position>(entity_id));
+    assert!(view.is_authority_loss_imminent::<Position>(entity_id));
+
+    connection.push_authority_change(entity_id, Position::ID, Authority::NotAuthoritative);
+    process(&mut connection, &mut view);
+    assert!(!view.has_authority::<Position>(entity_id));
+}
+
+#[test]
+fn view_queries_entities_with_components() {
+    let mut connection = MockConnection::new("worker");
+    let mut view = View::new();
+
+    for id in 1..=3 {
+        let entity_id = EntityId::new(id);
+        connection.push_add_entity(entity_id);
+        connection.push_add_component(entity_id, position(id as f64));
+    }
+    connection.push_add_component(
+        EntityId::new(2),
+        Metadata {
+            entity_type: "test".to_owned(),
+        },
+    );
+    process(&mut connection, &mut view);
+
+    assert_eq!(3, view.iter::<Position>().count());
+
+    let matching: Vec<EntityId> = view.query(&[Position::ID, Metadata::ID]).collect();
+    assert_eq!(vec![EntityId::new(2)], matching);
+}
+
+#[test]
+fn view_buffers_ops_during_critical_section() {
+    let mut connection = MockConnection::new("worker");
+    let mut view = View::new();
+    let entity_id = EntityId::new(1);
+
+    connection.push_op(OwnedWorkerOp::CriticalSection(CriticalSectionOp {
+        in_critical_section: true,
+    }));
+    connection.push_add_entity(entity_id);
+    process(&mut connection, &mut view);
+
+    assert!(view.is_in_critical_section());
+    assert!(!view.contains_entity(entity_id));
+
+    connection.push_add_component(entity_id, position(0.0));
+    connection.push_op(OwnedWorkerOp::CriticalSection(CriticalSectionOp {
+        in_critical_section: false,
+    }));
+    process(&mut connection, &mut view);
+
+    assert!(!view.is_in_critical_section());
+    assert!(view.has_component::<Position>(entity_id));
+}